@@ -5,15 +5,17 @@
 //! the kernel.
 
 use derive_more::Display;
-use kernel::error::{ResultDecl, ResultInstantiatedDecl};
+use kernel::error::{Error, ResultDecl, ResultInstantiatedDecl};
 use kernel::memory::arena::Arena;
-use kernel::memory::declaration::builder::{declaration, instance, var, BuilderTrait, InstantiatedBuilderTrait};
+use kernel::memory::declaration::builder::{declaration, instance, var, BuilderTrait, ErrorKind, InstantiatedBuilderTrait};
 use kernel::memory::declaration::{Declaration, InstantiatedDeclaration};
+use kernel::memory::level::builder::VecBuilderTrait;
 use kernel::trace::{Trace, Traceable};
 
 use super::Buildable;
 use crate::builder::{level, term};
 use crate::location::Location;
+use crate::scope::Scope;
 
 /// Template of declarations.
 #[derive(Clone, Debug, Display, PartialEq, Eq)]
@@ -43,15 +45,15 @@ impl<'build> Buildable<'build> for Builder<'build> {
     /// # Errors
     /// If the declaration could not be built, yields an error indicating the reason
     #[inline]
-    fn realise<'arena>(&self, arena: &mut Arena<'arena>) -> ResultDecl<'arena> {
-        arena.build_declaration(self.as_closure())
+    fn realise<'arena>(&self, arena: &mut Arena<'arena>, scope: &Scope) -> ResultDecl<'arena> {
+        arena.build_declaration(self.as_closure(scope))
     }
 
-    /// Associates a builder to a builder trait.
+    /// Associates a builder to a builder trait, the names it uses being resolved in `scope`.
     #[inline]
-    fn as_closure(&'build self) -> Self::Closure {
-        |arena| match *self {
-            Builder::Decl(ref term, ref vars) => declaration(term.as_closure(), vars.as_slice())(arena),
+    fn as_closure(&'build self, scope: &'build Scope) -> Self::Closure {
+        move |arena| match *self {
+            Builder::Decl(ref term, ref vars) => declaration(term.as_closure(scope), vars.as_slice())(arena),
         }
     }
 }
@@ -102,20 +104,36 @@ impl<'build> Buildable<'build> for InstantiatedBuilder<'build> {
     /// # Errors
     /// If the instantiated declaration could not be built, yields an error indicating the reason
     #[inline]
-    fn realise<'arena>(&self, arena: &mut Arena<'arena>) -> ResultInstantiatedDecl<'arena> {
-        arena.build_instantiated_declaration(self.as_closure())
+    fn realise<'arena>(&self, arena: &mut Arena<'arena>, scope: &Scope) -> ResultInstantiatedDecl<'arena> {
+        arena.build_instantiated_declaration(self.as_closure(scope))
     }
 
-    /// Associates a builder to a builder trait.
+    /// Associates a builder to a builder trait, the names it uses being resolved in `scope`.
     #[inline]
-    fn as_closure(&'build self) -> Self::Closure {
-        |arena, lvl_env| match *self {
-            InstantiatedBuilder::Instance(ref decl, ref levels) => instance(decl.as_closure(), levels.as_closure())(arena, lvl_env),
-            InstantiatedBuilder::Var(name, ref levels) => var(name, levels.as_closure())(arena, lvl_env),
+    fn as_closure(&'build self, scope: &'build Scope) -> Self::Closure {
+        move |arena, lvl_env| match *self {
+            InstantiatedBuilder::Instance(ref decl, ref levels) => {
+                instance(decl.as_closure(scope), levels.as_closure(scope))(arena, lvl_env)
+            },
+            InstantiatedBuilder::Var(name, ref levels) => resolved(name, levels.as_closure(scope), scope)(arena, lvl_env),
         }
     }
 }
 
+/// Returns a builder creating the declaration bound to the full name `name` refers to in `scope`,
+/// instantiated with the universe levels `levels`.
+pub(crate) fn resolved<'build, V: VecBuilderTrait<'build>>(
+    name: &'build str,
+    levels: V,
+    scope: &'build Scope,
+) -> impl InstantiatedBuilderTrait<'build> {
+    move |arena, lvl_env| match *scope.resolve(name, arena) {
+        [ref full_name] => var(full_name, levels)(arena, lvl_env),
+        [] => var(name, levels)(arena, lvl_env),
+        ref names => Err(Error::new(ErrorKind::AmbiguousDeclaration(name.to_owned(), names.join(", ")).into())),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::term::{Builder, Payload};
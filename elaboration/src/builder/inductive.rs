@@ -0,0 +1,171 @@
+//! Builder types for [`Inductive`] types.
+//!
+//! This is a naive description of inductive types. It can be transformed into concrete inductive
+//! types through the [`BuiderTrait`](kernel::memory::inductive::builder::BuilderTrait) declared
+//! in the kernel.
+
+use core::fmt;
+
+use kernel::error::ResultInductive;
+use kernel::memory::arena::Arena;
+use kernel::memory::inductive::builder::{inductive, BuilderTrait};
+use kernel::memory::inductive::Inductive;
+use kernel::trace::{Trace, Traceable};
+
+use super::Buildable;
+use crate::builder::term;
+use crate::location::Location;
+use crate::scope::Scope;
+
+/// Template of inductive types.
+///
+/// The parameters of the inductive type are expected to be already abstracted over, both in the
+/// arity and in the type of every constructor.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Builder<'build> {
+    /// The name of the inductive type, with its location.
+    pub name: (Location, &'build str),
+
+    /// The universe variables of the inductive type.
+    pub vars: Vec<&'build str>,
+
+    /// The number of uniform parameters.
+    pub params: usize,
+
+    /// The type of the inductive type.
+    pub arity: Box<term::Builder<'build>>,
+
+    /// The constructors of the inductive type, with the location of their names.
+    pub constructors: Vec<((Location, &'build str), term::Builder<'build>)>,
+}
+
+impl<'build> Builder<'build> {
+    /// Returns the names the arity and the constructors refer to, without duplicates and in order
+    /// of appearance.
+    #[inline]
+    #[must_use]
+    pub fn free_names(&self) -> Vec<&'build str> {
+        let mut names = Vec::new();
+
+        self.arity.collect_free_names(&mut Vec::new(), &mut names);
+        self.constructors.iter().for_each(|constructor| constructor.1.collect_free_names(&mut Vec::new(), &mut names));
+
+        names
+    }
+}
+
+impl<'build> fmt::Display for Builder<'build> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (_, name) = self.name;
+
+        write!(f, "{name}")?;
+
+        if !self.vars.is_empty() {
+            write!(f, ".{{{}}}", self.vars.join(", "))?;
+        }
+
+        write!(f, ": {} :=", self.arity)?;
+
+        self.constructors
+            .iter()
+            .try_for_each(|&((_, name), ref type_)| write!(f, " | {name}: {type_}"))
+    }
+}
+
+impl<'build> Traceable<Location> for Builder<'build> {
+    /// Applies a trace, where the inductive type is seen as the pair made of its arity and of the
+    /// right-nested sequence of its constructors (see [`kernel::memory::inductive::builder`]).
+    #[inline]
+    fn apply_trace(&self, trace: &[Trace]) -> Location {
+        let mut trace = trace.iter().rev();
+
+        match trace.next() {
+            Some(&Trace::Left) => self.arity.apply_trace(&trace.rev().copied().collect::<Vec<_>>()),
+
+            Some(&Trace::Right) => {
+                // counts the constructors skipped, and drops the step entering the current one
+                let index = trace.by_ref().take_while(|&&step| step == Trace::Right).count();
+
+                self.constructors
+                    .get(index)
+                    .map_or(self.name.0, |&(_, ref type_)| type_.apply_trace(&trace.rev().copied().collect::<Vec<_>>()))
+            },
+
+            Some(&Trace::Middle) => unreachable!("invalid trace"),
+
+            None => self.name.0,
+        }
+    }
+}
+
+impl<'build> Buildable<'build> for Builder<'build> {
+    type Output<'arena> = Inductive<'arena>;
+
+    type Closure = impl BuilderTrait<'build>;
+
+    /// Realises a builder into an [`Inductive`]. This internally uses functions described in the
+    /// [builder](`kernel::memory::inductive::builder`) module.
+    ///
+    /// # Errors
+    /// If the inductive type could not be built, yields an error indicating the reason
+    #[inline]
+    fn realise<'arena>(&self, arena: &mut Arena<'arena>, scope: &Scope) -> ResultInductive<'arena> {
+        arena.build_inductive(self.as_closure(scope))
+    }
+
+    /// Associates a builder to a builder trait, the names it uses being resolved in `scope`.
+    #[inline]
+    fn as_closure(&'build self, scope: &'build Scope) -> Self::Closure {
+        move |arena| {
+            let constructors = self
+                .constructors
+                .iter()
+                .map(|&((_, name), ref type_)| (name, type_.as_closure(scope)))
+                .collect();
+
+            inductive(self.name.1, self.vars.as_slice(), self.params, self.arity.as_closure(scope), constructors)(arena)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::term::{Builder as Term, Payload};
+
+    #[test]
+    fn inductive_trace() {
+        // The following inductive type is ill-typed, and locations do not have any meaning.
+        // We want to test that the trace is correctly applied.
+        let builder = Builder {
+            name: (Location::new((1, 1), (1, 1)), "I"),
+            vars: vec![],
+            params: 0,
+            arity: Box::new(Term::new(Location::new((2, 2), (2, 2)), Payload::Prop)),
+            constructors: vec![
+                ((Location::new((3, 3), (3, 3)), "c1"), Term::new(Location::new((4, 4), (4, 4)), Payload::Prop)),
+                (
+                    (Location::new((5, 5), (5, 5)), "c2"),
+                    Term::new(
+                        Location::new((6, 6), (6, 6)),
+                        Payload::Prod(
+                            "x",
+                            Box::new(Term::new(Location::new((7, 7), (7, 7)), Payload::Prop)),
+                            Box::new(Term::new(Location::new((8, 8), (8, 8)), Payload::Var("I"))),
+                        ),
+                    ),
+                ),
+            ],
+        };
+
+        // Beware, the trace has to be applied in the reverse order (depth-first).
+        assert_eq!(builder.apply_trace(&[]), Location::new((1, 1), (1, 1)));
+        assert_eq!(builder.apply_trace(&[Trace::Left]), Location::new((2, 2), (2, 2)));
+
+        assert_eq!(builder.apply_trace(&[Trace::Left, Trace::Right]), Location::new((4, 4), (4, 4)));
+        assert_eq!(builder.apply_trace(&[Trace::Left, Trace::Right, Trace::Right]), Location::new((6, 6), (6, 6)));
+        assert_eq!(builder.apply_trace(&[Trace::Left, Trace::Left, Trace::Right, Trace::Right]), Location::new((7, 7), (7, 7)));
+        assert_eq!(builder.apply_trace(&[Trace::Right, Trace::Left, Trace::Right, Trace::Right]), Location::new((8, 8), (8, 8)));
+    }
+}
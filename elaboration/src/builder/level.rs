@@ -11,6 +11,7 @@ use kernel::memory::level::builder::{const_, imax, max, plus, succ, var, zero, B
 use kernel::memory::level::Level;
 
 use super::Buildable;
+use crate::scope::Scope;
 
 /// Limit of universe level
 static UNIVERSE_LIMIT: u32 = 15;
@@ -68,26 +69,9 @@ impl<'build> Builder<'build> {
             Builder::Var(_) => 0,
         }
     }
-}
 
-impl<'build> Buildable<'build> for Builder<'build> {
-    type Output<'arena> = Level<'arena>;
-
-    type Closure = impl BuilderTrait<'build>;
-
-    /// Realises a builder into a [`Level`]. This internally uses functions described in
-    /// the [builder](`kernel::memory::level::builder`) module.
-    ///
-    /// # Errors
-    /// If the level could not be built, yields an error indicating the reason.
-    #[inline]
-    fn realise<'arena>(&self, arena: &mut Arena<'arena>) -> ResultLevel<'arena> {
-        arena.build_level(self.as_closure())
-    }
-
-    /// Associates a builder to a builder trait.
-    #[inline]
-    fn as_closure(&'build self) -> Self::Closure {
+    /// Associates a builder to a builder trait, whose universe variables are all local.
+    fn local_closure(&'build self) -> impl BuilderTrait<'build> {
         |arena, env| {
             let depth = self.max_depth();
 
@@ -101,16 +85,39 @@ impl<'build> Buildable<'build> for Builder<'build> {
             match *self {
                 Builder::Zero => zero()(arena, env),
                 Builder::Const(c) => const_(c)(arena, env),
-                Builder::Plus(ref u, n) => plus(u.as_closure(), n)(arena, env),
-                Builder::Succ(ref l) => succ(l.as_closure())(arena, env),
-                Builder::Max(ref l, ref r) => max(l.as_closure(), r.as_closure())(arena, env),
-                Builder::IMax(ref l, ref r) => imax(l.as_closure(), r.as_closure())(arena, env),
+                Builder::Plus(ref u, n) => plus(u.local_closure(), n)(arena, env),
+                Builder::Succ(ref l) => succ(l.local_closure())(arena, env),
+                Builder::Max(ref l, ref r) => max(l.local_closure(), r.local_closure())(arena, env),
+                Builder::IMax(ref l, ref r) => imax(l.local_closure(), r.local_closure())(arena, env),
                 Builder::Var(s) => var(s)(arena, env),
             }
         }
     }
 }
 
+impl<'build> Buildable<'build> for Builder<'build> {
+    type Output<'arena> = Level<'arena>;
+
+    type Closure = impl BuilderTrait<'build>;
+
+    /// Realises a builder into a [`Level`]. This internally uses functions described in
+    /// the [builder](`kernel::memory::level::builder`) module.
+    ///
+    /// # Errors
+    /// If the level could not be built, yields an error indicating the reason.
+    #[inline]
+    fn realise<'arena>(&self, arena: &mut Arena<'arena>, scope: &Scope) -> ResultLevel<'arena> {
+        arena.build_level(self.as_closure(scope))
+    }
+
+    /// Associates a builder to a builder trait. Universe variables are all local, and are not
+    /// resolved in the scope.
+    #[inline]
+    fn as_closure(&'build self, _: &'build Scope) -> Self::Closure {
+        self.local_closure()
+    }
+}
+
 impl<'build> Buildable<'build> for Vec<Builder<'build>> {
     type Output<'arena> = Vec<Level<'arena>>;
 
@@ -122,16 +129,16 @@ impl<'build> Buildable<'build> for Vec<Builder<'build>> {
     /// # Errors
     /// If the level could not be built, yields an error indicating the reason.
     #[inline]
-    fn realise<'arena>(&self, arena: &mut Arena<'arena>) -> Result<'arena, Vec<Level<'arena>>> {
-        arena.build_vec_level(self.as_closure())
+    fn realise<'arena>(&self, arena: &mut Arena<'arena>, scope: &Scope) -> Result<'arena, Vec<Level<'arena>>> {
+        arena.build_vec_level(self.as_closure(scope))
     }
 
     /// Associates a builder to a builder trait.
     #[inline]
-    fn as_closure(&'build self) -> Self::Closure {
-        |arena, env| {
+    fn as_closure(&'build self, scope: &'build Scope) -> Self::Closure {
+        move |arena, env| {
             self.iter()
-                .map(|level_builder| level_builder.as_closure()(arena, env))
+                .map(|level_builder| level_builder.as_closure(scope)(arena, env))
                 .collect::<Result<Vec<_>>>()
         }
     }
@@ -1,12 +1,17 @@
 //! Builders are high-level representation of terms that can be *realised* into [`Arena`] dwellers.
 
 pub mod declaration;
+pub mod inductive;
 pub mod level;
+pub mod structure;
+pub mod tactic;
 pub mod term;
 
 use kernel::error::Result;
 use kernel::memory::arena::Arena;
 
+use crate::scope::Scope;
+
 /// The trait of types that can be built into an arena-dependent element.
 pub trait Buildable<'build> {
     /// The closure used to build the [`Output`] element.
@@ -20,14 +25,14 @@ pub trait Buildable<'build> {
     /// The corresponding element to build.
     type Output<'arena>;
 
-    /// Realise a builder into a [`Output`].
+    /// Realise a builder into a [`Output`], the names it uses being resolved in `scope`.
     ///
     /// # Errors
     /// If the [`Output`] could not be built, yields an error indicating the reason.
     ///
     /// [`Output`]: Buildable::Output
-    fn realise<'arena>(&self, arena: &mut Arena<'arena>) -> Result<'arena, Self::Output<'arena>>;
+    fn realise<'arena>(&self, arena: &mut Arena<'arena>, scope: &Scope) -> Result<'arena, Self::Output<'arena>>;
 
-    /// Associates a builder to a builder trait.
-    fn as_closure(&'build self) -> Self::Closure;
+    /// Associates a builder to a builder trait, the names it uses being resolved in `scope`.
+    fn as_closure(&'build self, scope: &'build Scope) -> Self::Closure;
 }
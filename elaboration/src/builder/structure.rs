@@ -0,0 +1,198 @@
+//! Builder types for structures.
+//!
+//! A structure is an [`Inductive`] type without indices and with a single constructor, whose
+//! fields are accessed through projections. It can be transformed into a concrete inductive type
+//! through the [`BuiderTrait`](kernel::memory::inductive::builder::BuilderTrait) declared in the
+//! kernel.
+
+use core::fmt;
+
+use kernel::error::ResultInductive;
+use kernel::memory::arena::Arena;
+use kernel::memory::inductive::builder::{structure, BuilderTrait};
+use kernel::memory::inductive::Inductive;
+use kernel::trace::{Trace, Traceable};
+
+use super::Buildable;
+use crate::builder::term;
+use crate::location::Location;
+use crate::scope::Scope;
+
+/// Template of structures.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Builder<'build> {
+    /// The name of the structure, with its location.
+    pub name: (Location, &'build str),
+
+    /// The universe variables of the structure.
+    pub vars: Vec<&'build str>,
+
+    /// The parameters of the structure, in order.
+    pub params: Vec<(&'build str, term::Builder<'build>)>,
+
+    /// The sort of the structure, if explicitly given.
+    pub sort: Option<Box<term::Builder<'build>>>,
+
+    /// The fields of the structure, with the location of their names.
+    pub fields: Vec<((Location, &'build str), term::Builder<'build>)>,
+}
+
+impl<'build> Builder<'build> {
+    /// Returns the names of the fields of the structure, in order.
+    #[inline]
+    pub fn field_names(&self) -> impl Iterator<Item = &'build str> + '_ {
+        self.fields.iter().map(|&((_, name), _)| name)
+    }
+
+    /// Returns the names the parameters, the sort and the fields refer to, without duplicates and
+    /// in order of appearance.
+    ///
+    /// The parameters and the fields are bound in the types of the following ones.
+    #[inline]
+    #[must_use]
+    pub fn free_names(&self) -> Vec<&'build str> {
+        let mut bound = Vec::new();
+        let mut names = Vec::new();
+
+        for &(name, ref type_) in &self.params {
+            type_.collect_free_names(&mut bound, &mut names);
+            bound.push(name);
+        }
+
+        if let Some(ref sort) = self.sort {
+            sort.collect_free_names(&mut bound, &mut names);
+        }
+
+        for &((_, name), ref type_) in &self.fields {
+            type_.collect_free_names(&mut bound, &mut names);
+            bound.push(name);
+        }
+
+        names
+    }
+}
+
+impl<'build> fmt::Display for Builder<'build> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (_, name) = self.name;
+
+        write!(f, "{name}")?;
+
+        if !self.vars.is_empty() {
+            write!(f, ".{{{}}}", self.vars.join(", "))?;
+        }
+
+        self.params.iter().try_for_each(|&(name, ref type_)| write!(f, " ({name}: {type_})"))?;
+
+        if let Some(ref sort) = self.sort {
+            write!(f, ": {sort}")?;
+        }
+
+        write!(f, " where")?;
+
+        self.fields.iter().enumerate().try_for_each(|(index, &((_, name), ref type_))| {
+            let separator = if index == 0 { "" } else { ";" };
+            write!(f, "{separator} {name}: {type_}")
+        })
+    }
+}
+
+impl<'build> Traceable<Location> for Builder<'build> {
+    /// Applies a trace, where the structure is seen as the inductive type it stands for (see
+    /// [`kernel::memory::inductive::builder`]): parameters are reached through the arity, fields
+    /// through the constructor.
+    #[inline]
+    fn apply_trace(&self, trace: &[Trace]) -> Location {
+        let mut trace = trace.iter().rev();
+
+        let (binders, conclusion) = match trace.next() {
+            Some(&Trace::Left) => (self.params.len(), self.sort.as_deref()),
+            Some(&Trace::Right) if trace.next() == Some(&Trace::Left) => (self.params.len() + self.fields.len(), None),
+            _ => return self.name.0,
+        };
+
+        // counts the binders skipped, and drops the step entering the current one
+        let index = trace.clone().take_while(|&&step| step == Trace::Right).count();
+        let mut trace = trace.skip(index);
+
+        let type_ = if index == binders {
+            conclusion
+        } else if trace.next() == Some(&Trace::Left) {
+            self.params
+                .get(index)
+                .map(|&(_, ref type_)| type_)
+                .or_else(|| self.fields.get(index - self.params.len()).map(|&(_, ref type_)| type_))
+        } else {
+            None
+        };
+
+        type_.map_or(self.name.0, |type_| type_.apply_trace(&trace.rev().copied().collect::<Vec<_>>()))
+    }
+}
+
+impl<'build> Buildable<'build> for Builder<'build> {
+    type Output<'arena> = Inductive<'arena>;
+
+    type Closure = impl BuilderTrait<'build>;
+
+    /// Realises a builder into an [`Inductive`]. This internally uses functions described in the
+    /// [builder](`kernel::memory::inductive::builder`) module.
+    ///
+    /// # Errors
+    /// If the structure could not be built, yields an error indicating the reason
+    #[inline]
+    fn realise<'arena>(&self, arena: &mut Arena<'arena>, scope: &Scope) -> ResultInductive<'arena> {
+        arena.build_inductive(self.as_closure(scope))
+    }
+
+    /// Associates a builder to a builder trait, the names it uses being resolved in `scope`.
+    #[inline]
+    fn as_closure(&'build self, scope: &'build Scope) -> Self::Closure {
+        move |arena| {
+            let params = self.params.iter().map(|&(name, ref type_)| (name, type_.as_closure(scope))).collect();
+            let sort = self.sort.as_ref().map(|sort| sort.as_closure(scope));
+            let fields = self.fields.iter().map(|&((_, name), ref type_)| (name, type_.as_closure(scope))).collect();
+
+            structure(self.name.1, self.vars.as_slice(), params, sort, fields)(arena)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::term::{Builder as Term, Payload};
+
+    #[test]
+    fn structure_trace() {
+        // The following structure is ill-typed, and locations do not have any meaning.
+        // We want to test that the trace is correctly applied.
+        let builder = Builder {
+            name: (Location::new((1, 1), (1, 1)), "S"),
+            vars: vec![],
+            params: vec![("A", Term::new(Location::new((2, 2), (2, 2)), Payload::Prop))],
+            sort: Some(Box::new(Term::new(Location::new((3, 3), (3, 3)), Payload::Prop))),
+            fields: vec![
+                ((Location::new((4, 4), (4, 4)), "x"), Term::new(Location::new((5, 5), (5, 5)), Payload::Var("A"))),
+                ((Location::new((6, 6), (6, 6)), "y"), Term::new(Location::new((7, 7), (7, 7)), Payload::Var("x"))),
+            ],
+        };
+
+        // Beware, the trace has to be applied in the reverse order (depth-first).
+        assert_eq!(builder.apply_trace(&[]), Location::new((1, 1), (1, 1)));
+        assert_eq!(builder.apply_trace(&[Trace::Left, Trace::Left]), Location::new((2, 2), (2, 2)));
+        assert_eq!(builder.apply_trace(&[Trace::Right, Trace::Left]), Location::new((3, 3), (3, 3)));
+
+        assert_eq!(builder.apply_trace(&[Trace::Left, Trace::Left, Trace::Right]), Location::new((2, 2), (2, 2)));
+        assert_eq!(builder.apply_trace(&[Trace::Left, Trace::Right, Trace::Left, Trace::Right]), Location::new((5, 5), (5, 5)));
+        assert_eq!(
+            builder.apply_trace(&[Trace::Left, Trace::Right, Trace::Right, Trace::Left, Trace::Right]),
+            Location::new((7, 7), (7, 7))
+        );
+        assert_eq!(
+            builder.apply_trace(&[Trace::Right, Trace::Right, Trace::Right, Trace::Left, Trace::Right]),
+            Location::new((1, 1), (1, 1))
+        );
+    }
+}
@@ -0,0 +1,70 @@
+//! Builder types for tactics.
+//!
+//! Tactics only make sense to the [elaborator](crate::elaborator), which runs them to build the
+//! terms of the `by` blocks they appear in.
+
+use derive_more::{Constructor, Deref, Display};
+
+use crate::builder::term;
+use crate::location::Location;
+
+/// Wrapper template of [`Payload`], including [`Location`].
+#[derive(Clone, Constructor, Debug, Deref, Display, PartialEq, Eq)]
+#[display(fmt = "{payload}")]
+pub struct Builder<'build> {
+    /// Location of the tactic.
+    location: Location,
+
+    /// The payload of the builder.
+    #[deref]
+    payload: Payload<'build>,
+}
+
+/// Template of tactics.
+///
+/// Each tactic acts on the first goal of a `by` block, and replaces it with the goals it leaves.
+#[derive(Clone, Debug, Display, PartialEq, Eq)]
+pub enum Payload<'build> {
+    /// Introduces the arguments of a goal which is a product, with the given names. Introduces
+    /// a single argument, named after its binder, if no name is given.
+    #[display(fmt = "intro{}", "_0.iter().map(|name| format!(\" {name}\")).collect::<String>()")]
+    Intro(Vec<&'build str>),
+
+    /// Solves the goal with the given term.
+    #[display(fmt = "exact {_0}")]
+    Exact(Box<term::Builder<'build>>),
+
+    /// Solves the goal with the given term, applied to as many new goals as needed.
+    #[display(fmt = "apply {_0}")]
+    Apply(Box<term::Builder<'build>>),
+
+    /// Solves the goal with the given term, whose holes become new goals.
+    #[display(fmt = "refine {_0}")]
+    Refine(Box<term::Builder<'build>>),
+
+    /// Replaces the left-hand side of the given equality with its right-hand side in the goal, or
+    /// the converse if the flag is set.
+    #[display(fmt = "rewrite {}{_1}", "if *_0 { \"<- \" } else { \"\" }")]
+    Rewrite(bool, Box<term::Builder<'build>>),
+
+    /// Proves the goal by induction on the given natural number hypothesis.
+    #[display(fmt = "induction {_0}")]
+    Induction(&'build str),
+
+    /// Replaces the goal with `False`.
+    #[display(fmt = "exfalso")]
+    Exfalso,
+
+    /// Solves the goal with one of the hypotheses.
+    #[display(fmt = "assumption")]
+    Assumption,
+}
+
+impl<'build> Builder<'build> {
+    /// Returns the location of the tactic.
+    #[inline]
+    #[must_use]
+    pub const fn location(&self) -> Location {
+        self.location
+    }
+}
@@ -5,16 +5,20 @@
 //! the kernel.
 
 use derive_more::{Constructor, Deref, Display};
-use kernel::error::ResultTerm;
+use kernel::error::{Error, ResultTerm};
 use kernel::memory::arena::Arena;
-use kernel::memory::declaration::builder as builder_declaration;
-use kernel::memory::term::builder::{abs, app, decl, prod, prop, sort, type_, var, BuilderTrait};
+use kernel::memory::level::builder::VecBuilderTrait;
+use kernel::memory::term::builder::{
+    abs, app, decl, let_, nat_lit, prod, prop, sort, type_, var, var_instance, BuilderTrait, ErrorKind,
+};
 use kernel::memory::term::Term;
 use kernel::trace::{Trace, Traceable};
+use num_bigint::BigUint;
 
 use super::Buildable;
-use crate::builder::{declaration, level};
+use crate::builder::{declaration, level, tactic};
 use crate::location::Location;
+use crate::scope::Scope;
 
 /// Wrapper template of [`Payload`], including [`Location`].
 #[derive(Clone, Constructor, Debug, Deref, Display, PartialEq, Eq)]
@@ -67,21 +71,51 @@ pub enum Payload<'build> {
     #[display(fmt = "\u{03A0} {_0}: {_1} \u{02192} {_2}")]
     Prod(&'build str, Box<Builder<'build>>, Box<Builder<'build>>),
 
+    /// A lambda-abstraction whose argument is implicit.
+    #[display(fmt = "\u{003BB} {{{_0}: {_1}}} \u{02192} {_2}")]
+    ImplicitAbs(&'build str, Box<Builder<'build>>, Box<Builder<'build>>),
+
+    /// A dependent product whose argument is implicit.
+    #[display(fmt = "\u{03A0} {{{_0}: {_1}}} \u{02192} {_2}")]
+    ImplicitProd(&'build str, Box<Builder<'build>>, Box<Builder<'build>>),
+
+    /// A term whose implicit arguments are all given explicitly.
+    #[display(fmt = "@{_0}")]
+    Explicit(Box<Builder<'build>>),
+
+    #[display(fmt = "let {_0}: {_1} := {_2}; {_3}")]
+    Let(&'build str, Box<Builder<'build>>, Box<Builder<'build>>, Box<Builder<'build>>),
+
     Decl(Box<declaration::InstantiatedBuilder<'build>>),
+
+    /// A natural number literal.
+    NatLit(BigUint),
+
+    /// A hole, possibly named, standing for a term left for the elaborator to report.
+    #[display(fmt = "{}", "_0.map_or_else(|| \"_\".to_owned(), |name| format!(\"?{name}\"))")]
+    Hole(Option<&'build str>),
+
+    /// A tactic block, standing for the term built by its tactics.
+    #[display(fmt = "by {}", "_0.iter().map(ToString::to_string).collect::<Vec<_>>().join(\"; \")")]
+    By(Vec<tactic::Builder<'build>>),
 }
 
 impl<'build> Traceable<Location> for Builder<'build> {
     #[inline]
     fn apply_trace(&self, trace: &[Trace]) -> Location {
-        let builder = trace.iter().rev().fold(self, |builder, trace| match (trace, &builder.payload) {
+        let builder = trace.iter().rev().fold(self, |builder, trace| match (trace, &builder.explicit().payload) {
             (Trace::Left, Payload::App(lhs, _)) => lhs,
             (Trace::Right, Payload::App(_, rhs)) => rhs,
 
-            (Trace::Left, Payload::Abs(_, lhs, _)) => lhs,
-            (Trace::Right, Payload::Abs(_, _, rhs)) => rhs,
+            (Trace::Left, Payload::Abs(_, lhs, _) | Payload::ImplicitAbs(_, lhs, _)) => lhs,
+            (Trace::Right, Payload::Abs(_, _, rhs) | Payload::ImplicitAbs(_, _, rhs)) => rhs,
 
-            (Trace::Left, Payload::Prod(_, lhs, _)) => lhs,
-            (Trace::Right, Payload::Prod(_, _, rhs)) => rhs,
+            (Trace::Left, Payload::Prod(_, lhs, _) | Payload::ImplicitProd(_, lhs, _)) => lhs,
+            (Trace::Right, Payload::Prod(_, _, rhs) | Payload::ImplicitProd(_, _, rhs)) => rhs,
+
+            (Trace::Left, Payload::Let(_, type_, _, _)) => type_,
+            (Trace::Middle, Payload::Let(_, _, value, _)) => value,
+            (Trace::Right, Payload::Let(_, _, _, body)) => body,
 
             _ => unreachable!("invalid trace"),
         });
@@ -90,6 +124,99 @@ impl<'build> Traceable<Location> for Builder<'build> {
     }
 }
 
+impl<'build> Builder<'build> {
+    /// Returns the location of the term.
+    #[inline]
+    #[must_use]
+    pub const fn location(&self) -> Location {
+        self.location
+    }
+
+    /// Returns the term itself, stripped of the markers indicating that its implicit arguments are
+    /// given explicitly, which are meaningless to the kernel.
+    #[inline]
+    #[must_use]
+    pub fn explicit(&self) -> &Self {
+        match self.payload {
+            Payload::Explicit(ref term) => term.explicit(),
+            _ => self,
+        }
+    }
+
+    /// Returns the names the term refers to which are not bound in the term itself, without
+    /// duplicates and in order of appearance.
+    ///
+    /// In a tactic block, the names introduced by a tactic are bound in the following ones.
+    #[inline]
+    #[must_use]
+    pub fn free_names(&self) -> Vec<&'build str> {
+        let mut names = Vec::new();
+        self.collect_free_names(&mut Vec::new(), &mut names);
+        names
+    }
+
+    /// Appends to `names` the names the term refers to which are neither in `bound` nor in
+    /// `names` already.
+    pub(crate) fn collect_free_names(&self, bound: &mut Vec<&'build str>, names: &mut Vec<&'build str>) {
+        match self.payload {
+            Payload::Var(name) | Payload::VarInstance(name, _) => {
+                if !bound.contains(&name) && !names.contains(&name) {
+                    names.push(name);
+                }
+            },
+            Payload::Decl(ref decl_builder) => match **decl_builder {
+                declaration::InstantiatedBuilder::Var(name, _) => {
+                    if !bound.contains(&name) && !names.contains(&name) {
+                        names.push(name);
+                    }
+                },
+                declaration::InstantiatedBuilder::Instance(ref decl_builder, _) => {
+                    let declaration::Builder::Decl(ref term, _) = **decl_builder;
+                    term.collect_free_names(bound, names);
+                },
+            },
+            Payload::App(ref l, ref r) => {
+                l.collect_free_names(bound, names);
+                r.collect_free_names(bound, names);
+            },
+            Payload::Abs(s, ref arg, ref body)
+            | Payload::Prod(s, ref arg, ref body)
+            | Payload::ImplicitAbs(s, ref arg, ref body)
+            | Payload::ImplicitProd(s, ref arg, ref body) => {
+                arg.collect_free_names(bound, names);
+                bound.push(s);
+                body.collect_free_names(bound, names);
+                bound.pop();
+            },
+            Payload::Explicit(ref term) => term.collect_free_names(bound, names),
+            Payload::Let(s, ref type_, ref value, ref body) => {
+                type_.collect_free_names(bound, names);
+                value.collect_free_names(bound, names);
+                bound.push(s);
+                body.collect_free_names(bound, names);
+                bound.pop();
+            },
+            Payload::By(ref tactics) => {
+                let depth = bound.len();
+
+                for tactic in tactics {
+                    match **tactic {
+                        tactic::Payload::Intro(ref intros) => bound.extend(intros),
+                        tactic::Payload::Exact(ref term)
+                        | tactic::Payload::Apply(ref term)
+                        | tactic::Payload::Refine(ref term)
+                        | tactic::Payload::Rewrite(_, ref term) => term.collect_free_names(bound, names),
+                        tactic::Payload::Induction(_) | tactic::Payload::Exfalso | tactic::Payload::Assumption => (),
+                    }
+                }
+
+                bound.truncate(depth);
+            },
+            Payload::Prop | Payload::Type(_) | Payload::Sort(_) | Payload::NatLit(_) | Payload::Hole(_) => (),
+        }
+    }
+}
+
 impl<'build> Buildable<'build> for Builder<'build> {
     type Output<'arena> = Term<'arena>;
 
@@ -101,25 +228,74 @@ impl<'build> Buildable<'build> for Builder<'build> {
     /// # Errors
     /// If the term could not be built, yields an error indicating the reason.
     #[inline]
-    fn realise<'arena>(&self, arena: &mut Arena<'arena>) -> ResultTerm<'arena> {
-        arena.build(self.as_closure())
+    fn realise<'arena>(&self, arena: &mut Arena<'arena>, scope: &Scope) -> ResultTerm<'arena> {
+        arena.build(self.as_closure(scope))
     }
 
-    /// Associates a builder to a builder trait.
+    /// Associates a builder to a builder trait, the names it uses being resolved in `scope`.
     #[inline]
-    fn as_closure(&'build self) -> Self::Closure {
-        |arena, env, lvl_env, depth| match **self {
+    fn as_closure(&'build self, scope: &'build Scope) -> Self::Closure {
+        move |arena, env, lvl_env, depth| match **self {
             Payload::Prop => prop()(arena, env, lvl_env, depth),
-            Payload::Var(s) => var(s)(arena, env, lvl_env, depth),
+            Payload::Var(s) => resolved(s, scope)(arena, env, lvl_env, depth),
             Payload::VarInstance(name, ref levels) => {
-                decl(builder_declaration::var(name, levels.as_closure()))(arena, env, lvl_env, depth)
+                resolved_instance(name, levels.as_closure(scope), scope)(arena, env, lvl_env, depth)
+            },
+            Payload::Type(ref level) => type_(level.as_closure(scope))(arena, env, lvl_env, depth),
+            Payload::Sort(ref level) => sort(level.as_closure(scope))(arena, env, lvl_env, depth),
+            Payload::App(ref l, ref r) => app(l.as_closure(scope), r.as_closure(scope))(arena, env, lvl_env, depth),
+            Payload::Abs(s, ref arg, ref body) => abs(s, arg.as_closure(scope), body.as_closure(scope))(arena, env, lvl_env, depth),
+            Payload::Prod(s, ref arg, ref body) => {
+                prod(s, arg.as_closure(scope), body.as_closure(scope))(arena, env, lvl_env, depth)
+            },
+            Payload::ImplicitAbs(s, ref arg, ref body) => {
+                abs(s, arg.as_closure(scope), body.as_closure(scope))(arena, env, lvl_env, depth)
+            },
+            Payload::ImplicitProd(s, ref arg, ref body) => {
+                prod(s, arg.as_closure(scope), body.as_closure(scope))(arena, env, lvl_env, depth)
+            },
+            Payload::Explicit(ref term) => term.as_closure(scope)(arena, env, lvl_env, depth),
+            Payload::Let(s, ref type_, ref value, ref body) => {
+                let_(s, type_.as_closure(scope), value.as_closure(scope), body.as_closure(scope))(arena, env, lvl_env, depth)
             },
-            Payload::Type(ref level) => type_(level.as_closure())(arena, env, lvl_env, depth),
-            Payload::Sort(ref level) => sort(level.as_closure())(arena, env, lvl_env, depth),
-            Payload::App(ref l, ref r) => app(l.as_closure(), r.as_closure())(arena, env, lvl_env, depth),
-            Payload::Abs(s, ref arg, ref body) => abs(s, arg.as_closure(), body.as_closure())(arena, env, lvl_env, depth),
-            Payload::Prod(s, ref arg, ref body) => prod(s, arg.as_closure(), body.as_closure())(arena, env, lvl_env, depth),
-            Payload::Decl(ref decl_builder) => decl(decl_builder.as_closure())(arena, env, lvl_env, depth),
+            Payload::Decl(ref decl_builder) => decl(decl_builder.as_closure(scope))(arena, env, lvl_env, depth),
+            Payload::NatLit(ref n) => nat_lit(n)(arena, env, lvl_env, depth),
+            // only the elaborator knows about holes and tactics: to the kernel, they are unknown identifiers
+            Payload::Hole(name) => var(name.unwrap_or("_"))(arena, env, lvl_env, depth),
+            Payload::By(_) => var("by")(arena, env, lvl_env, depth),
+        }
+    }
+}
+
+/// Returns a closure building the term `name` refers to: the local variable `name` if there is
+/// one, and otherwise the term bound to the full name `name` refers to in `scope`.
+fn resolved<'build>(name: &'build str, scope: &'build Scope) -> impl BuilderTrait<'build> {
+    move |arena, env, lvl_env, depth| {
+        if env.contains_key(name) {
+            return var(name)(arena, env, lvl_env, depth);
+        }
+
+        // an inductive type refers to itself under its full name while it is being built
+        match *scope.resolve_with(name, |name| env.contains_key(name) || arena.is_bound(name)) {
+            [ref full_name] => var(full_name)(arena, env, lvl_env, depth),
+            [] => var(name)(arena, env, lvl_env, depth),
+            ref names => Err(Error::new(ErrorKind::AmbiguousName(name.to_owned(), names.join(", ")).into())),
+        }
+    }
+}
+
+/// Returns a builder creating the term `name` refers to in `scope`, instantiated with the universe
+/// levels `levels`.
+fn resolved_instance<'build, V: VecBuilderTrait<'build> + 'build>(
+    name: &'build str,
+    levels: V,
+    scope: &'build Scope,
+) -> impl BuilderTrait<'build> {
+    move |arena, env, lvl_env, depth| {
+        // an inductive type refers to itself, under its full name, while it is being built
+        match *scope.resolve_with(name, |name| env.contains_key(name)) {
+            [ref full_name] => var_instance(full_name, levels)(arena, env, lvl_env, depth),
+            _ => decl(declaration::resolved(name, levels, scope))(arena, env, lvl_env, depth),
         }
     }
 }
@@ -165,4 +341,28 @@ mod tests {
         assert_eq!(builder.apply_trace(&[Trace::Left, Trace::Right]), Location::new((6, 6), (6, 6)));
         assert_eq!(builder.apply_trace(&[Trace::Right, Trace::Right]), Location::new((7, 7), (7, 7)));
     }
+
+    #[test]
+    fn builder_free_names() {
+        let build = |payload| Box::new(Builder::new(Location::default(), payload));
+        let tactic = |payload| tactic::Builder::new(Location::default(), payload);
+
+        // fun x : Nat => f x (by intro h; exact g h y x)
+        let g_h = build(Payload::App(build(Payload::Var("g")), build(Payload::Var("h"))));
+        let g_h_y = build(Payload::App(g_h, build(Payload::Var("y"))));
+        let block = Payload::By(vec![
+            tactic(tactic::Payload::Intro(vec!["h"])),
+            tactic(tactic::Payload::Exact(build(Payload::App(g_h_y, build(Payload::Var("x")))))),
+        ]);
+        let builder = Builder::new(
+            Location::default(),
+            Payload::Abs(
+                "x",
+                build(Payload::Var("Nat")),
+                build(Payload::App(build(Payload::App(build(Payload::Var("f")), build(Payload::Var("x")))), build(block))),
+            ),
+        );
+
+        assert_eq!(builder.free_names(), vec!["Nat", "f", "g", "y"]);
+    }
 }
@@ -0,0 +1,455 @@
+//! Expressions manipulated by the elaborator.
+//!
+//! Expressions are terms in de Bruijn notation, like kernel [terms](Term), which may additionally
+//! contain metavariables. Closed kernel terms are embedded as *atoms*, which are only looked into
+//! when the elaborator needs to, for instance, to expose the product hidden behind the type of a
+//! constant.
+
+use alloc::rc::Rc;
+use core::fmt;
+
+use kernel::memory::term::{pretty, Payload as TermPayload, Term};
+
+use crate::builder::term::Builder;
+
+/// An expression, as shared by the elaborator.
+pub(super) type Expr<'build, 'arena> = Rc<Node<'build, 'arena>>;
+
+/// A node of an expression.
+#[derive(Clone, Debug)]
+pub(super) struct Node<'build, 'arena> {
+    /// The content of the node.
+    pub(super) payload: Payload<'build, 'arena>,
+
+    /// The part of the input this node was elaborated from, if any.
+    pub(super) origin: Option<&'build Builder<'build>>,
+}
+
+impl PartialEq for Node<'_, '_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.payload == other.payload
+    }
+}
+
+impl Eq for Node<'_, '_> {}
+
+/// A binder, as found in abstractions and products.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) struct Binder<'build> {
+    /// The name of the bound variable, `_` if it has none.
+    pub(super) name: &'build str,
+
+    /// Whether the argument is implicit.
+    pub(super) implicit: bool,
+}
+
+impl<'build> Binder<'build> {
+    /// The binder of an explicit argument without name.
+    pub(super) const ANONYMOUS: Self = Binder {
+        name: "_",
+        implicit: false,
+    };
+}
+
+/// The content of an expression node.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) enum Payload<'build, 'arena> {
+    /// A variable, with its de Bruijn index.
+    Var(usize),
+
+    /// A metavariable, to be applied to the variables of the context it was created in.
+    Meta(usize),
+
+    /// A closed kernel term.
+    Atom(Term<'arena>),
+
+    /// The application of an expression to another.
+    App(Expr<'build, 'arena>, Expr<'build, 'arena>),
+
+    /// A lambda-abstraction, with the type of its argument and its body.
+    Abs(Binder<'build>, Expr<'build, 'arena>, Expr<'build, 'arena>),
+
+    /// A dependent product, with the type of its argument and its body.
+    Prod(Binder<'build>, Expr<'build, 'arena>, Expr<'build, 'arena>),
+
+    /// A local definition, with its type, its value and its body.
+    Let(Binder<'build>, Expr<'build, 'arena>, Expr<'build, 'arena>, Expr<'build, 'arena>),
+}
+
+use Payload::{Abs, App, Atom, Let, Meta, Prod, Var};
+
+/// Returns an expression with the given payload and no origin.
+pub(super) fn new<'build, 'arena>(payload: Payload<'build, 'arena>) -> Expr<'build, 'arena> {
+    Rc::new(Node {
+        payload,
+        origin: None,
+    })
+}
+
+/// Returns an expression with the given payload and origin.
+pub(super) fn located<'build, 'arena>(payload: Payload<'build, 'arena>, origin: &'build Builder<'build>) -> Expr<'build, 'arena> {
+    Rc::new(Node {
+        payload,
+        origin: Some(origin),
+    })
+}
+
+/// Returns `expr`, whose origin is set to `origin` should it have none.
+pub(super) fn inherit<'build, 'arena>(expr: Expr<'build, 'arena>, origin: Option<&'build Builder<'build>>) -> Expr<'build, 'arena> {
+    if expr.origin.is_some() || origin.is_none() {
+        return expr;
+    }
+
+    Rc::new(Node {
+        payload: expr.payload.clone(),
+        origin,
+    })
+}
+
+/// Returns an expression with the same origin as `expr` and the payload `payload`.
+fn rebuild<'build, 'arena>(expr: &Expr<'build, 'arena>, payload: Payload<'build, 'arena>) -> Expr<'build, 'arena> {
+    Rc::new(Node {
+        payload,
+        origin: expr.origin,
+    })
+}
+
+/// Returns the application of `fun` to all the arguments in `args`.
+pub(super) fn apply<'build, 'arena>(fun: Expr<'build, 'arena>, args: &[Expr<'build, 'arena>]) -> Expr<'build, 'arena> {
+    args.iter().fold(fun, |acc, arg| new(App(acc, Rc::clone(arg))))
+}
+
+/// Returns the head of `expr`, seen as a series of applications, along with its arguments.
+///
+/// Applications hidden in atoms are exposed.
+pub(super) fn spine<'build, 'arena>(expr: &Expr<'build, 'arena>) -> (Expr<'build, 'arena>, Vec<Expr<'build, 'arena>>) {
+    let mut head = Rc::clone(expr);
+    let mut args = Vec::new();
+
+    loop {
+        head = match head.payload {
+            App(ref fun, ref arg) => {
+                args.push(Rc::clone(arg));
+                Rc::clone(fun)
+            },
+            Atom(term) if matches!(*term, TermPayload::App(..)) => expose(head),
+            _ => break,
+        };
+    }
+
+    args.reverse();
+    (head, args)
+}
+
+/// Returns the expression `expr` where all variables with de Bruijn index larger than `depth`
+/// are offset by `offset`.
+pub(super) fn shift<'build, 'arena>(expr: &Expr<'build, 'arena>, offset: usize, depth: usize) -> Expr<'build, 'arena> {
+    match expr.payload {
+        Var(index) if index > depth => rebuild(expr, Var(index + offset)),
+        App(ref fun, ref arg) => rebuild(expr, App(shift(fun, offset, depth), shift(arg, offset, depth))),
+        Abs(binder, ref arg_type, ref body) => {
+            rebuild(expr, Abs(binder, shift(arg_type, offset, depth), shift(body, offset, depth + 1)))
+        },
+        Prod(binder, ref arg_type, ref body) => {
+            rebuild(expr, Prod(binder, shift(arg_type, offset, depth), shift(body, offset, depth + 1)))
+        },
+        Let(binder, ref type_, ref value, ref body) => {
+            rebuild(expr, Let(binder, shift(type_, offset, depth), shift(value, offset, depth), shift(body, offset, depth + 1)))
+        },
+        _ => Rc::clone(expr),
+    }
+}
+
+/// Returns the expression `expr` where the variables of de Bruijn index between `depth + 1` and
+/// `depth + subs.len()` are replaced by the corresponding expressions in `subs`, the last one
+/// replacing the variable of index `depth + 1`. Variables of greater index are decremented
+/// accordingly.
+pub(super) fn substitute<'build, 'arena>(
+    expr: &Expr<'build, 'arena>,
+    subs: &[Expr<'build, 'arena>],
+    depth: usize,
+) -> Expr<'build, 'arena> {
+    match expr.payload {
+        Var(index) if index > depth => match subs.len().checked_sub(index - depth) {
+            Some(position) => subs
+                .get(position)
+                .map_or_else(|| Rc::clone(expr), |sub| inherit(shift(sub, depth, 0), expr.origin)),
+            None => rebuild(expr, Var(index - subs.len())),
+        },
+        App(ref fun, ref arg) => rebuild(expr, App(substitute(fun, subs, depth), substitute(arg, subs, depth))),
+        Abs(binder, ref arg_type, ref body) => {
+            rebuild(expr, Abs(binder, substitute(arg_type, subs, depth), substitute(body, subs, depth + 1)))
+        },
+        Prod(binder, ref arg_type, ref body) => {
+            rebuild(expr, Prod(binder, substitute(arg_type, subs, depth), substitute(body, subs, depth + 1)))
+        },
+        Let(binder, ref type_, ref value, ref body) => rebuild(
+            expr,
+            Let(binder, substitute(type_, subs, depth), substitute(value, subs, depth), substitute(body, subs, depth + 1)),
+        ),
+        _ => Rc::clone(expr),
+    }
+}
+
+/// Returns the expression `expr` where each variable of de Bruijn index `index` free at depth
+/// `depth` is replaced by `sub(index - depth)`, shifted appropriately, if it is defined.
+pub(super) fn replace<'build, 'arena, F>(expr: &Expr<'build, 'arena>, sub: &F, depth: usize) -> Expr<'build, 'arena>
+where
+    F: Fn(usize) -> Option<Expr<'build, 'arena>>,
+{
+    match expr.payload {
+        Var(index) if index > depth => {
+            sub(index - depth).map_or_else(|| Rc::clone(expr), |sub| inherit(shift(&sub, depth, 0), expr.origin))
+        },
+        App(ref fun, ref arg) => rebuild(expr, App(replace(fun, sub, depth), replace(arg, sub, depth))),
+        Abs(binder, ref arg_type, ref body) => {
+            rebuild(expr, Abs(binder, replace(arg_type, sub, depth), replace(body, sub, depth + 1)))
+        },
+        Prod(binder, ref arg_type, ref body) => {
+            rebuild(expr, Prod(binder, replace(arg_type, sub, depth), replace(body, sub, depth + 1)))
+        },
+        Let(binder, ref type_, ref value, ref body) => {
+            rebuild(expr, Let(binder, replace(type_, sub, depth), replace(value, sub, depth), replace(body, sub, depth + 1)))
+        },
+        _ => Rc::clone(expr),
+    }
+}
+
+/// Returns the expression `expr` where each atom `term` is replaced by `sub(term)`.
+pub(super) fn map_atoms<'build, 'arena, F>(expr: &Expr<'build, 'arena>, sub: &mut F) -> Expr<'build, 'arena>
+where
+    F: FnMut(Term<'arena>) -> Term<'arena>,
+{
+    match expr.payload {
+        Atom(term) => rebuild(expr, Atom(sub(term))),
+        App(ref fun, ref arg) => rebuild(expr, App(map_atoms(fun, sub), map_atoms(arg, sub))),
+        Abs(binder, ref arg_type, ref body) => rebuild(expr, Abs(binder, map_atoms(arg_type, sub), map_atoms(body, sub))),
+        Prod(binder, ref arg_type, ref body) => rebuild(expr, Prod(binder, map_atoms(arg_type, sub), map_atoms(body, sub))),
+        Let(binder, ref type_, ref value, ref body) => {
+            rebuild(expr, Let(binder, map_atoms(type_, sub), map_atoms(value, sub), map_atoms(body, sub)))
+        },
+        Var(_) | Meta(_) => Rc::clone(expr),
+    }
+}
+
+/// Returns the body `body` of a binder, where the bound variable is replaced by `sub`.
+pub(super) fn instantiate<'build, 'arena>(body: &Expr<'build, 'arena>, sub: &Expr<'build, 'arena>) -> Expr<'build, 'arena> {
+    substitute(body, core::slice::from_ref(sub), 0)
+}
+
+/// Returns the expression `expr` where the variables free at depth `depth` are renamed according
+/// to `vars`: the variable of index `vars[k]` is given index `vars.len() - k`, which amounts to
+/// abstracting over these variables. Returns `None` if `expr` has another free variable.
+pub(super) fn rename<'build, 'arena>(expr: &Expr<'build, 'arena>, vars: &[usize], depth: usize) -> Option<Expr<'build, 'arena>> {
+    Some(match expr.payload {
+        Var(index) if index > depth => {
+            let position = vars.iter().position(|&var| var == index - depth)?;
+            rebuild(expr, Var(vars.len() - position + depth))
+        },
+        App(ref fun, ref arg) => rebuild(expr, App(rename(fun, vars, depth)?, rename(arg, vars, depth)?)),
+        Abs(binder, ref arg_type, ref body) => {
+            rebuild(expr, Abs(binder, rename(arg_type, vars, depth)?, rename(body, vars, depth + 1)?))
+        },
+        Prod(binder, ref arg_type, ref body) => {
+            rebuild(expr, Prod(binder, rename(arg_type, vars, depth)?, rename(body, vars, depth + 1)?))
+        },
+        Let(binder, ref type_, ref value, ref body) => {
+            rebuild(expr, Let(binder, rename(type_, vars, depth)?, rename(value, vars, depth)?, rename(body, vars, depth + 1)?))
+        },
+        _ => Rc::clone(expr),
+    })
+}
+
+/// Returns the expression `expr`, living at depth `depth`, abstracted over the occurrences of
+/// `target`: these become the variable of de Bruijn index `depth + 1`, and the other variables
+/// free at depth `depth` are shifted accordingly.
+///
+/// Occurrences are found syntactically. Atoms are looked into when `target` is itself an atom.
+pub(super) fn generalise<'build, 'arena>(
+    expr: &Expr<'build, 'arena>,
+    target: &Expr<'build, 'arena>,
+    depth: usize,
+) -> Expr<'build, 'arena> {
+    if *expr == shift(target, depth, 0) {
+        return rebuild(expr, Var(depth + 1));
+    }
+
+    match expr.payload {
+        Var(index) if index > depth => rebuild(expr, Var(index + 1)),
+        Atom(term) if matches!(target.payload, Atom(_)) => match view(term) {
+            Some(view) => {
+                let generalised = generalise(&view, target, depth);
+                if generalised == view { Rc::clone(expr) } else { generalised }
+            },
+            None => Rc::clone(expr),
+        },
+        App(ref fun, ref arg) => rebuild(expr, App(generalise(fun, target, depth), generalise(arg, target, depth))),
+        Abs(binder, ref arg_type, ref body) => {
+            rebuild(expr, Abs(binder, generalise(arg_type, target, depth), generalise(body, target, depth + 1)))
+        },
+        Prod(binder, ref arg_type, ref body) => {
+            rebuild(expr, Prod(binder, generalise(arg_type, target, depth), generalise(body, target, depth + 1)))
+        },
+        Let(binder, ref type_, ref value, ref body) => rebuild(
+            expr,
+            Let(binder, generalise(type_, target, depth), generalise(value, target, depth), generalise(body, target, depth + 1)),
+        ),
+        _ => Rc::clone(expr),
+    }
+}
+
+/// Indicates whether the predicate `pred` holds for some node of `expr`.
+pub(super) fn any<'build, 'arena, P>(expr: &Expr<'build, 'arena>, pred: &P) -> bool
+where
+    P: Fn(&Payload<'build, 'arena>) -> bool,
+{
+    pred(&expr.payload)
+        || match expr.payload {
+            App(ref fun, ref arg) => any(fun, pred) || any(arg, pred),
+            Abs(_, ref arg_type, ref body) | Prod(_, ref arg_type, ref body) => any(arg_type, pred) || any(body, pred),
+            Let(_, ref type_, ref value, ref body) => any(type_, pred) || any(value, pred) || any(body, pred),
+            _ => false,
+        }
+}
+
+/// Indicates whether `expr` contains a metavariable.
+pub(super) fn has_meta(expr: &Expr) -> bool {
+    any(expr, &|payload| matches!(*payload, Meta(_)))
+}
+
+/// Returns the expression corresponding to the kernel term `term`.
+///
+/// Closed subterms are kept as atoms.
+pub(super) fn read<'build, 'arena>(term: Term<'arena>) -> Expr<'build, 'arena> {
+    if term.is_closed() {
+        return new(Atom(term));
+    }
+
+    match *term {
+        TermPayload::Var(index, _) => new(Var(index.into())),
+        TermPayload::App(fun, arg) => new(App(read(fun), read(arg))),
+        TermPayload::Abs(arg_type, body) => new(Abs(Binder::ANONYMOUS, read(arg_type), read(body))),
+        TermPayload::Prod(arg_type, body) => new(Prod(Binder::ANONYMOUS, read(arg_type), read(body))),
+        TermPayload::Let(type_, value, body) => new(Let(Binder::ANONYMOUS, read(type_), read(value), read(body))),
+        _ => new(Atom(term)),
+    }
+}
+
+/// Returns the first layer of the atom `term`, if it has a structure the elaborator can look
+/// into.
+pub(super) fn view<'build, 'arena>(term: Term<'arena>) -> Option<Expr<'build, 'arena>> {
+    match *term {
+        TermPayload::App(fun, arg) => Some(new(App(read(fun), read(arg)))),
+        TermPayload::Abs(arg_type, body) => Some(new(Abs(Binder::ANONYMOUS, read(arg_type), read(body)))),
+        TermPayload::Prod(arg_type, body) => Some(new(Prod(Binder::ANONYMOUS, read(arg_type), read(body)))),
+        TermPayload::Let(type_, value, body) => Some(new(Let(Binder::ANONYMOUS, read(type_), read(value), read(body)))),
+        _ => None,
+    }
+}
+
+/// Returns `expr`, where an atom at its root is replaced by its first layer, if possible.
+pub(super) fn expose<'build, 'arena>(expr: Expr<'build, 'arena>) -> Expr<'build, 'arena> {
+    match expr.payload {
+        Atom(term) => match view(term) {
+            Some(view) => inherit(view, expr.origin),
+            None => expr,
+        },
+        _ => expr,
+    }
+}
+
+/// Writes `expr` in `out`, where `names` are the names of the variables of its context, from the
+/// outermost one.
+pub(super) fn write<W: fmt::Write>(out: &mut W, names: &mut Vec<String>, expr: &Expr) -> fmt::Result {
+    match expr.payload {
+        Var(index) => match names.len().checked_sub(index).and_then(|position| names.get(position)) {
+            Some(name) => write!(out, "{name}"),
+            None => write!(out, "#{index}"),
+        },
+        Meta(id) => write!(out, "?{id}"),
+        Atom(term) => write!(out, "{}", pretty::Term(term)),
+        App(ref fun, ref arg) => {
+            write_parenthesised(out, names, fun, matches!(fun.payload, Abs(..) | Prod(..) | Let(..)))?;
+            write!(out, " ")?;
+            write_parenthesised(out, names, arg, is_compound(arg))
+        },
+        Abs(binder, ref arg_type, ref body) => {
+            write!(out, "fun ")?;
+            write_binder(out, names, binder, arg_type, true)?;
+            write!(out, " => ")?;
+            write_body(out, names, binder, body)
+        },
+        Prod(binder, ref arg_type, ref body) => {
+            if binder.name == "_" && !binder.implicit && !any(body, &|payload| *payload == Var(1)) {
+                write_parenthesised(out, names, arg_type, matches!(arg_type.payload, Abs(..) | Prod(..)))?;
+            } else {
+                write_binder(out, names, binder, arg_type, true)?;
+            }
+
+            write!(out, " -> ")?;
+            write_body(out, names, binder, body)
+        },
+        Let(binder, ref type_, ref value, ref body) => {
+            write!(out, "let ")?;
+            write_binder(out, names, binder, type_, false)?;
+            write!(out, " := ")?;
+            write(out, names, value)?;
+            write!(out, "; ")?;
+            write_body(out, names, binder, body)
+        },
+    }
+}
+
+/// Indicates whether `expr` needs parentheses when it is the argument of an application.
+fn is_compound(expr: &Expr) -> bool {
+    match expr.payload {
+        App(..) | Abs(..) | Prod(..) | Let(..) => true,
+        Atom(term) => term.to_string().contains(' '),
+        Var(_) | Meta(_) => false,
+    }
+}
+
+/// Writes `expr` in `out`, between parentheses if `parenthesised` holds.
+fn write_parenthesised<W: fmt::Write>(out: &mut W, names: &mut Vec<String>, expr: &Expr, parenthesised: bool) -> fmt::Result {
+    if parenthesised {
+        write!(out, "(")?;
+        write(out, names, expr)?;
+        write!(out, ")")
+    } else {
+        write(out, names, expr)
+    }
+}
+
+/// Writes the binder `binder` of type `arg_type` in `out`, between delimiters if `delimited`
+/// holds or if it is implicit.
+fn write_binder<W: fmt::Write>(
+    out: &mut W,
+    names: &mut Vec<String>,
+    binder: Binder,
+    arg_type: &Expr,
+    delimited: bool,
+) -> fmt::Result {
+    let (open, close) = match (binder.implicit, delimited) {
+        (true, _) => ("{", "}"),
+        (false, true) => ("(", ")"),
+        (false, false) => ("", ""),
+    };
+
+    write!(out, "{open}{}: ", binder_name(names, binder))?;
+    write(out, names, arg_type)?;
+    write!(out, "{close}")
+}
+
+/// Writes the body `body` of the binder `binder` in `out`.
+fn write_body<W: fmt::Write>(out: &mut W, names: &mut Vec<String>, binder: Binder, body: &Expr) -> fmt::Result {
+    names.push(binder_name(names, binder));
+    let result = write(out, names, body);
+    names.pop();
+    result
+}
+
+/// Returns the name under which the variable bound by `binder` is displayed, in a context whose
+/// variables are named `names`.
+fn binder_name(names: &[String], binder: Binder) -> String {
+    if binder.name == "_" { format!("x{}", names.len()) } else { binder.name.to_owned() }
+}
@@ -0,0 +1,562 @@
+//! Bidirectional elaboration of builders.
+//!
+//! Terms are elaborated against an expected type whenever one is known, which lets abstractions
+//! and implicit arguments be elaborated with the information it carries. Parts of the input
+//! without implicit binders nor metavariables are realised and typed directly by the kernel.
+
+use alloc::rc::Rc;
+
+use kernel::error::ResultTerm;
+use kernel::memory::arena::Arena;
+use kernel::memory::context::Context as KernelContext;
+use kernel::memory::declaration::builder as declaration;
+use kernel::memory::level::Level;
+use kernel::memory::term::builder::{decl, prod, sort, var, Environment};
+use kernel::memory::term::{Payload as TermPayload, Term};
+
+use super::expr::{expose, inherit, instantiate, located, new, read, shift, spine, substitute, Binder, Expr, Payload};
+use super::unify::var_type;
+use super::{Context, Elaborator, Entry, Error, ErrorKind, Hole, Result};
+use crate::builder::term::{Builder, Payload as BuilderPayload};
+use crate::builder::Buildable;
+use crate::location::Location;
+
+impl<'build, 'env, 'arena> Elaborator<'build, 'env, 'arena> {
+    /// Runs `f` in the context `ctx` extended with `entry`.
+    fn under<T, F>(&mut self, ctx: &mut Context<'build, 'arena>, entry: Entry<'build, 'arena>, f: F) -> Result<'build, 'arena, T>
+    where
+        F: FnOnce(&mut Self, &mut Context<'build, 'arena>) -> Result<'build, 'arena, T>,
+    {
+        ctx.push(entry);
+        let result = f(self, ctx);
+        ctx.pop();
+        result
+    }
+
+    /// Infers the type of `builder` in the context `ctx`, inserting its trailing implicit
+    /// arguments.
+    pub(super) fn infer(
+        &mut self,
+        ctx: &mut Context<'build, 'arena>,
+        builder: &'build Builder<'build>,
+        arena: &mut Arena<'arena>,
+    ) -> Result<'build, 'arena, (Expr<'build, 'arena>, Expr<'build, 'arena>)> {
+        match **builder {
+            BuilderPayload::Var(_) | BuilderPayload::VarInstance(..) | BuilderPayload::Explicit(_) => {
+                let (expr, type_, implicits) = self.infer_head(ctx, builder, arena)?;
+                self.insert_implicits(ctx, builder, expr, type_, implicits, 0, arena)
+            },
+
+            BuilderPayload::App(..) => self.infer_app(ctx, builder, arena),
+
+            BuilderPayload::Abs(name, ref arg_type, ref body) | BuilderPayload::ImplicitAbs(name, ref arg_type, ref body) => {
+                let binder = Binder {
+                    name,
+                    implicit: matches!(**builder, BuilderPayload::ImplicitAbs(..)),
+                };
+
+                let (arg_type, _) = self.infer_type(ctx, arg_type, arena)?;
+                let entry = Entry {
+                    name,
+                    type_: Rc::clone(&arg_type),
+                    value: None,
+                };
+                let (body, body_type) = self.under(ctx, entry, |this, ctx| this.infer(ctx, body, arena))?;
+
+                Ok((
+                    located(Payload::Abs(binder, Rc::clone(&arg_type), body), builder),
+                    new(Payload::Prod(binder, arg_type, body_type)),
+                ))
+            },
+
+            BuilderPayload::Prod(name, ref arg_type, ref body) | BuilderPayload::ImplicitProd(name, ref arg_type, ref body) => {
+                let binder = Binder {
+                    name,
+                    implicit: matches!(**builder, BuilderPayload::ImplicitProd(..)),
+                };
+
+                let (arg_type, arg_sort) = self.infer_type(ctx, arg_type, arena)?;
+                let entry = Entry {
+                    name,
+                    type_: Rc::clone(&arg_type),
+                    value: None,
+                };
+                let (body, body_sort) = self.under(ctx, entry, |this, ctx| this.infer_type(ctx, body, arena))?;
+                let sort = product_sort(builder, &arg_sort, &body_sort, arena)?;
+
+                Ok((located(Payload::Prod(binder, arg_type, body), builder), sort))
+            },
+
+            BuilderPayload::Let(name, ref type_, ref value, ref body) => {
+                let binder = Binder {
+                    name,
+                    implicit: false,
+                };
+
+                let (type_, _) = self.infer_type(ctx, type_, arena)?;
+                let value = self.check(ctx, value, &type_, arena)?;
+                let entry = Entry {
+                    name,
+                    type_: Rc::clone(&type_),
+                    value: Some(Rc::clone(&value)),
+                };
+                let (body, body_type) = self.under(ctx, entry, |this, ctx| this.infer(ctx, body, arena))?;
+
+                Ok((located(Payload::Let(binder, type_, Rc::clone(&value), body), builder), instantiate(&body_type, &value)))
+            },
+
+            BuilderPayload::Hole(name) => {
+                let sort = self.new_sort(builder, arena)?;
+                let type_ = self.new_meta(ctx, &sort, builder.location());
+
+                Ok((self.hole(ctx, builder, name, &type_), type_))
+            },
+
+            BuilderPayload::By(ref tactics) => {
+                let sort = self.new_sort(builder, arena)?;
+                let type_ = self.new_meta(ctx, &sort, builder.location());
+
+                Ok((self.by(ctx, builder, tactics, &type_, arena)?, type_))
+            },
+
+            BuilderPayload::Prop
+            | BuilderPayload::Type(_)
+            | BuilderPayload::Sort(_)
+            | BuilderPayload::Decl(_)
+            | BuilderPayload::NatLit(_) => self.constant(builder, arena),
+        }
+    }
+
+    /// Infers the type of `builder`, which must be a type, in the context `ctx`, and returns it
+    /// along with this type, which is a sort.
+    ///
+    /// # Errors
+    /// If `builder` is not a type, yields an error indicating the reason.
+    pub(super) fn infer_type(
+        &mut self,
+        ctx: &mut Context<'build, 'arena>,
+        builder: &'build Builder<'build>,
+        arena: &mut Arena<'arena>,
+    ) -> Result<'build, 'arena, (Expr<'build, 'arena>, Expr<'build, 'arena>)> {
+        let (expr, type_) = self.infer(ctx, builder, arena)?;
+        let sort = self.whnf(ctx, &type_, arena);
+
+        // the type of a hole used as a type is only known to be a sort
+        if self.is_flex(&sort) {
+            let new_sort = self.new_sort(builder, arena)?;
+            self.constrain(ctx, &new_sort, &sort, builder.location(), arena)?;
+
+            return Ok((expr, new_sort));
+        }
+
+        match sort.payload {
+            Payload::Atom(term) if matches!(*term, TermPayload::Sort(_)) => Ok((expr, sort)),
+            _ => Err(Error::Elaboration(builder.location(), ErrorKind::NotAType(self.show(ctx, &expr, arena)))),
+        }
+    }
+
+    /// Elaborates `builder` in the context `ctx`, checking that it has type `expected`.
+    pub(super) fn check(
+        &mut self,
+        ctx: &mut Context<'build, 'arena>,
+        builder: &'build Builder<'build>,
+        expected: &Expr<'build, 'arena>,
+        arena: &mut Arena<'arena>,
+    ) -> Result<'build, 'arena, Expr<'build, 'arena>> {
+        let introduced = match **builder {
+            BuilderPayload::Abs(..) => Some(self.whnf(ctx, expected, arena)),
+            BuilderPayload::ImplicitAbs(..) | BuilderPayload::Hole(_) | BuilderPayload::By(_) => None,
+            _ => Some(expose(self.zonk(expected))),
+        };
+
+        let introduced = introduced.filter(|expected| matches!(expected.payload, Payload::Prod(binder, ..) if binder.implicit));
+
+        if let Some(&Payload::Prod(binder, ref arg_type, ref body)) = introduced.as_ref().map(|expected| &expected.payload) {
+            let entry = Entry {
+                name: Binder::ANONYMOUS.name,
+                type_: Rc::clone(arg_type),
+                value: None,
+            };
+            let body = self.under(ctx, entry, |this, ctx| this.check(ctx, builder, body, arena))?;
+
+            return Ok(located(Payload::Abs(binder, Rc::clone(arg_type), body), builder));
+        }
+
+        match **builder {
+            BuilderPayload::Abs(name, ref arg_type, ref body) | BuilderPayload::ImplicitAbs(name, ref arg_type, ref body) => {
+                let expected = self.whnf(ctx, expected, arena);
+
+                if let Payload::Prod(expected_binder, ref expected_arg_type, ref expected_body) = expected.payload {
+                    let binder = Binder {
+                        name,
+                        implicit: expected_binder.implicit,
+                    };
+
+                    let (arg_type_expr, _) = self.infer_type(ctx, arg_type, arena)?;
+                    self.constrain(ctx, expected_arg_type, &arg_type_expr, arg_type.location(), arena)?;
+
+                    let entry = Entry {
+                        name,
+                        type_: Rc::clone(&arg_type_expr),
+                        value: None,
+                    };
+                    let body = self.under(ctx, entry, |this, ctx| this.check(ctx, body, expected_body, arena))?;
+
+                    return Ok(located(Payload::Abs(binder, arg_type_expr, body), builder));
+                }
+            },
+
+            BuilderPayload::Hole(name) => return Ok(self.hole(ctx, builder, name, expected)),
+
+            BuilderPayload::By(ref tactics) => return self.by(ctx, builder, tactics, expected, arena),
+
+            BuilderPayload::Let(name, ref type_, ref value, ref body) => {
+                let binder = Binder {
+                    name,
+                    implicit: false,
+                };
+
+                let (type_, _) = self.infer_type(ctx, type_, arena)?;
+                let value = self.check(ctx, value, &type_, arena)?;
+                let entry = Entry {
+                    name,
+                    type_: Rc::clone(&type_),
+                    value: Some(Rc::clone(&value)),
+                };
+                let expected = shift(expected, 1, 0);
+                let body = self.under(ctx, entry, |this, ctx| this.check(ctx, body, &expected, arena))?;
+
+                return Ok(located(Payload::Let(binder, type_, value, body), builder));
+            },
+
+            _ => {},
+        }
+
+        let (expr, type_) = self.infer(ctx, builder, arena)?;
+        self.constrain(ctx, expected, &type_, builder.location(), arena)?;
+
+        Ok(expr)
+    }
+
+    /// Infers the type of `builder`, the head of an application, in the context `ctx`. Also
+    /// returns the flags indicating which of its arguments are implicit, if it is a named term.
+    pub(super) fn infer_head(
+        &mut self,
+        ctx: &mut Context<'build, 'arena>,
+        builder: &'build Builder<'build>,
+        arena: &mut Arena<'arena>,
+    ) -> Result<'build, 'arena, (Expr<'build, 'arena>, Expr<'build, 'arena>, &'env [bool])> {
+        match **builder {
+            BuilderPayload::Var(name) => match ctx.iter().rposition(|entry| entry.name == name) {
+                Some(position) => {
+                    let index = ctx.len() - position;
+                    Ok((located(Payload::Var(index), builder), var_type(ctx, index), &[]))
+                },
+                None => {
+                    let names = self.scope.resolve(name, arena);
+
+                    // unknown and ambiguous names are reported by the kernel
+                    let [ref name] = *names else {
+                        let (expr, type_) = self.constant(builder, arena)?;
+                        return Ok((expr, type_, &[]));
+                    };
+
+                    let (expr, type_) = match arena.get_binding_decl(name) {
+                        // a universe-polymorphic declaration given without its universe levels
+                        Some(decl) if arena.get_binding(name).is_none() => self.instance(builder, name, decl.vars(), arena)?,
+                        _ => self.constant(builder, arena)?,
+                    };
+
+                    Ok((expr, type_, self.implicits.get(name)))
+                },
+            },
+
+            BuilderPayload::VarInstance(name, _) => {
+                let (expr, type_) = self.constant(builder, arena)?;
+
+                match *self.scope.resolve(name, arena) {
+                    [ref name] => Ok((expr, type_, self.implicits.get(name))),
+                    _ => Ok((expr, type_, &[])),
+                }
+            },
+
+            BuilderPayload::Explicit(ref builder) => {
+                let (expr, type_, _) = self.infer_head(ctx, builder, arena)?;
+                Ok((expr, type_, &[]))
+            },
+
+            _ => {
+                let (expr, type_) = self.infer(ctx, builder, arena)?;
+                Ok((expr, type_, &[]))
+            },
+        }
+    }
+
+    /// Infers the type of the application `builder` in the context `ctx`, inserting a
+    /// metavariable in place of each implicit argument.
+    fn infer_app(
+        &mut self,
+        ctx: &mut Context<'build, 'arena>,
+        builder: &'build Builder<'build>,
+        arena: &mut Arena<'arena>,
+    ) -> Result<'build, 'arena, (Expr<'build, 'arena>, Expr<'build, 'arena>)> {
+        let mut head = builder;
+        let mut args = Vec::new();
+
+        while let BuilderPayload::App(ref fun, ref arg) = **head {
+            args.push((head, &**arg));
+            head = fun;
+        }
+
+        let explicit = matches!(**head, BuilderPayload::Explicit(_));
+        let (mut expr, mut type_, implicits) = self.infer_head(ctx, head, arena)?;
+        let mut position = 0;
+
+        for (node, arg) in args.into_iter().rev() {
+            loop {
+                let whnf = self.whnf(ctx, &type_, arena);
+
+                let Payload::Prod(binder, ref arg_type, ref body) = whnf.payload else {
+                    return Err(Error::Elaboration(node.location(), ErrorKind::NotAFunction(self.show(ctx, &type_, arena))));
+                };
+
+                position += 1;
+
+                if !explicit && (binder.implicit || implicits.get(position - 1) == Some(&true)) {
+                    let meta = self.new_meta(ctx, arg_type, node.location());
+                    expr = new(Payload::App(expr, Rc::clone(&meta)));
+                    type_ = instantiate(body, &meta);
+                } else {
+                    let arg = self.check(ctx, arg, arg_type, arena)?;
+                    expr = located(Payload::App(expr, Rc::clone(&arg)), node);
+                    type_ = instantiate(body, &arg);
+                    break;
+                }
+            }
+        }
+
+        if explicit { Ok((expr, type_)) } else { self.insert_implicits(ctx, builder, expr, type_, implicits, position, arena) }
+    }
+
+    /// Applies `expr`, of type `type_`, to metavariables as long as its next argument, of
+    /// position `position`, is implicit.
+    #[allow(clippy::too_many_arguments)]
+    fn insert_implicits(
+        &mut self,
+        ctx: &Context<'build, 'arena>,
+        builder: &'build Builder<'build>,
+        mut expr: Expr<'build, 'arena>,
+        mut type_: Expr<'build, 'arena>,
+        implicits: &[bool],
+        mut position: usize,
+        arena: &mut Arena<'arena>,
+    ) -> Result<'build, 'arena, (Expr<'build, 'arena>, Expr<'build, 'arena>)> {
+        if matches!(**builder, BuilderPayload::Explicit(_)) {
+            return Ok((expr, type_));
+        }
+
+        loop {
+            let implicit = implicits.get(position) == Some(&true);
+
+            let next = if implicit { self.whnf(ctx, &type_, arena) } else { expose(self.zonk(&type_)) };
+
+            let Payload::Prod(binder, ref arg_type, ref body) = next.payload else {
+                return Ok((expr, type_));
+            };
+
+            if !implicit && !binder.implicit {
+                return Ok((expr, type_));
+            }
+
+            let meta = self.new_meta(ctx, arg_type, builder.location());
+            expr = new(Payload::App(expr, Rc::clone(&meta)));
+            type_ = instantiate(body, &meta);
+            position += 1;
+        }
+    }
+
+    /// Realises `builder`, which does not depend on the context, with the kernel, and returns it
+    /// along with its type.
+    fn constant(
+        &self,
+        builder: &'build Builder<'build>,
+        arena: &mut Arena<'arena>,
+    ) -> Result<'build, 'arena, (Expr<'build, 'arena>, Expr<'build, 'arena>)> {
+        let term = builder.as_closure(self.scope)(arena, &Environment::new(), &self.lvl_env, 0.into())
+            .map_err(|err| Error::Kernel(builder, err))?;
+        let type_ = term.infer(arena).map_err(|err| Error::Kernel(builder, err))?;
+
+        Ok((located(Payload::Atom(term), builder), read(type_)))
+    }
+
+    /// Returns a sort whose universe level is a new universe metavariable.
+    pub(super) fn new_sort(
+        &mut self,
+        builder: &'build Builder<'build>,
+        arena: &mut Arena<'arena>,
+    ) -> Result<'build, 'arena, Expr<'build, 'arena>> {
+        let level = self.new_levels(1, builder.location());
+        let sort = arena
+            .build(sort(move |arena, _| Ok(Level::var(level, arena))))
+            .map_err(|err| Error::Kernel(builder, err))?;
+
+        Ok(read(sort))
+    }
+
+    /// Creates a new metavariable of type `type_` standing for the hole `builder`, named `name`.
+    fn hole(
+        &mut self,
+        ctx: &Context<'build, 'arena>,
+        builder: &'build Builder<'build>,
+        name: Option<&'build str>,
+        type_: &Expr<'build, 'arena>,
+    ) -> Expr<'build, 'arena> {
+        self.holes.push(Hole {
+            meta: self.metas.len(),
+            name,
+        });
+
+        inherit(self.new_meta(ctx, type_, builder.location()), Some(builder))
+    }
+
+    /// Instantiates the declaration `name`, which has `vars` universe variables, with new
+    /// universe metavariables, and returns it along with its type.
+    fn instance(
+        &mut self,
+        builder: &'build Builder<'build>,
+        name: &str,
+        vars: usize,
+        arena: &mut Arena<'arena>,
+    ) -> Result<'build, 'arena, (Expr<'build, 'arena>, Expr<'build, 'arena>)> {
+        let term = self.instance_term(name, vars, builder.location(), arena).map_err(|err| Error::Kernel(builder, err))?;
+        let type_ = term.infer(arena).map_err(|err| Error::Kernel(builder, err))?;
+
+        Ok((located(Payload::Atom(term), builder), read(type_)))
+    }
+
+    /// Returns the declaration `name`, which has `vars` universe variables, instantiated with new
+    /// universe metavariables introduced at `location`.
+    pub(super) fn instance_term(
+        &mut self,
+        name: &str,
+        vars: usize,
+        location: Location,
+        arena: &mut Arena<'arena>,
+    ) -> ResultTerm<'arena> {
+        let first = self.new_levels(vars, location);
+
+        arena.build(decl(declaration::var(name, move |arena, _| {
+            Ok((first..first + vars).map(|var| Level::var(var, arena)).collect())
+        })))
+    }
+
+    /// Returns the type of `expr`, an expression built by the elaborator living in the context
+    /// `ctx`, if it can be computed.
+    pub(super) fn type_of(
+        &self,
+        ctx: &mut Context<'build, 'arena>,
+        expr: &Expr<'build, 'arena>,
+        arena: &mut Arena<'arena>,
+    ) -> Option<Expr<'build, 'arena>> {
+        match expr.payload {
+            Payload::Var(index) => Some(var_type(ctx, index)),
+
+            Payload::Atom(term) => term.infer(arena).ok().map(read),
+
+            Payload::Meta(_) | Payload::App(..) => {
+                let (head, args) = spine(expr);
+
+                let (mut type_, args) = match head.payload {
+                    Payload::Meta(id) => {
+                        let meta = &self.metas[id];
+                        let (subs, args) = args.split_at_checked(meta.context.len())?;
+
+                        (substitute(&meta.type_, subs, 0), args)
+                    },
+                    _ => (self.type_of(ctx, &head, arena)?, args.as_slice()),
+                };
+
+                for arg in args {
+                    let Payload::Prod(_, _, ref body) = self.whnf(ctx, &type_, arena).payload else {
+                        return None;
+                    };
+
+                    type_ = instantiate(body, arg);
+                }
+
+                Some(type_)
+            },
+
+            Payload::Abs(binder, ref arg_type, ref body) => {
+                ctx.push(Entry {
+                    name: binder.name,
+                    type_: Rc::clone(arg_type),
+                    value: None,
+                });
+                let body_type = self.type_of(ctx, body, arena);
+                ctx.pop();
+
+                Some(new(Payload::Prod(binder, Rc::clone(arg_type), body_type?)))
+            },
+
+            Payload::Prod(binder, ref arg_type, ref body) => {
+                let arg_sort = self.type_of(ctx, arg_type, arena)?;
+                let arg_sort = self.whnf(ctx, &arg_sort, arena);
+
+                ctx.push(Entry {
+                    name: binder.name,
+                    type_: Rc::clone(arg_type),
+                    value: None,
+                });
+                let body_sort = self.type_of(ctx, body, arena).map(|body_sort| self.whnf(ctx, &body_sort, arena));
+                ctx.pop();
+
+                match (&arg_sort.payload, &body_sort?.payload) {
+                    (&Payload::Atom(arg_sort), &Payload::Atom(body_sort)) => {
+                        kernel_product_sort(arg_sort, body_sort, arena).ok().map(read)
+                    },
+                    _ => None,
+                }
+            },
+
+            Payload::Let(binder, ref type_, ref value, ref body) => {
+                ctx.push(Entry {
+                    name: binder.name,
+                    type_: Rc::clone(type_),
+                    value: Some(Rc::clone(value)),
+                });
+                let body_type = self.type_of(ctx, body, arena);
+                ctx.pop();
+
+                Some(instantiate(&body_type?, value))
+            },
+        }
+    }
+}
+
+/// Returns the sort of a product whose argument type has sort `arg_sort` and whose body has
+/// sort `body_sort`.
+fn product_sort<'build, 'arena>(
+    builder: &'build Builder<'build>,
+    arg_sort: &Expr<'build, 'arena>,
+    body_sort: &Expr<'build, 'arena>,
+    arena: &mut Arena<'arena>,
+) -> Result<'build, 'arena, Expr<'build, 'arena>> {
+    let (Payload::Atom(arg_sort), Payload::Atom(body_sort)) = (&arg_sort.payload, &body_sort.payload) else {
+        return Err(Error::Elaboration(builder.location(), ErrorKind::NotAType(builder.to_string())));
+    };
+
+    kernel_product_sort(*arg_sort, *body_sort, arena).map(read).map_err(|err| Error::Kernel(builder, err))
+}
+
+/// Returns the sort of a product whose argument type has the kernel sort `arg_sort` and whose
+/// body has the kernel sort `body_sort`.
+fn kernel_product_sort<'arena>(arg_sort: Term<'arena>, body_sort: Term<'arena>, arena: &mut Arena<'arena>) -> ResultTerm<'arena> {
+    let mut ctx = KernelContext::new();
+
+    ctx.push("#a", arg_sort, arena)?;
+    ctx.push("#b", body_sort, arena)?;
+
+    let product = arena.build_in(&ctx, prod("_", var("#a"), var("#b")))?;
+    product.infer_in(&ctx, arena)
+}
@@ -0,0 +1,788 @@
+//! Elaboration of builders into fully explicit terms.
+//!
+//! Builders written by users may omit the arguments of functions declared with implicit binders
+//! (`{x: A}`). The elaborator inserts a *metavariable* in place of each of these arguments, and
+//! solves them by unification against the expected types: it first tries to solve problems of the
+//! form `?m x1 ... xn = t` with distinct variables `xi` (higher-order patterns), and postpones
+//! the other ones until more metavariables are solved. The resulting term is fully explicit, and
+//! is then realised and type checked by the kernel as usual: the elaborator is not trusted.
+//!
+//! Holes (`_` and `?name`) also stand for metavariables. Instead of an opaque error, the terms
+//! where some of them are left are reported along with the [goal](Goal) of each hole: its expected
+//! type and its local context. Anonymous holes solved by unification are not reported, while named
+//! ones always are.
+//!
+//! Tactic blocks (`by intro x; exact x`) stand for metavariables as well: their tactics are run
+//! by the elaborator, which solves these metavariables step by step (see [`tactic`]).
+//!
+//! Likewise, the universe levels of a universe-polymorphic declaration may be omitted: the elaborator
+//! then instantiates the declaration with *universe metavariables*, which are solved from the
+//! equations between levels arising from unification.
+//!
+//! Kernel terms do not record which arguments are implicit. This information is kept in a table
+//! of [`Implicits`], filled as declarations are elaborated.
+
+mod expr;
+mod infer;
+pub mod output;
+mod tactic;
+mod unify;
+mod universe;
+
+use alloc::rc::Rc;
+use core::fmt;
+use std::collections::HashMap;
+
+use derive_more::Display;
+use kernel::memory::arena::Arena;
+use kernel::memory::level::builder as level;
+use kernel::memory::level::Level;
+
+use self::expr::{expose, write, Expr, Payload};
+use self::output::Elaborated;
+use self::unify::elaborated;
+use crate::builder::term::{self, Builder};
+use crate::location::Location;
+use crate::scope::Scope;
+
+/// The kind of errors that can occur during elaboration.
+#[non_exhaustive]
+#[derive(Clone, Debug, Display, Eq, PartialEq)]
+pub enum ErrorKind {
+    /// A term does not have the expected type.
+    #[display(fmt = "expected {_0}, got {_1}")]
+    TypeMismatch(String, String),
+
+    /// A term which is not a function is applied to some argument.
+    #[display(fmt = "a term of type {_0} is not a function")]
+    NotAFunction(String),
+
+    /// A term which is not a type is used as one.
+    #[display(fmt = "{_0} is not a type")]
+    NotAType(String),
+
+    /// An implicit argument could not be inferred.
+    #[display(fmt = "cannot infer an implicit argument of type {_0}")]
+    UnsolvedMeta(String),
+
+    /// A universe level could not be inferred.
+    #[display(fmt = "cannot infer a universe level")]
+    UnsolvedLevel,
+
+    /// Two universe levels which must be equal cannot be made so.
+    #[display(fmt = "universe constraint {_0} = {_1} cannot be satisfied")]
+    InconsistentLevels(String, String),
+
+    /// Some holes are left in the term.
+    #[display(fmt = "holes are left in the term\n{}", "show_goals(_0)")]
+    Holes(Vec<Goal>),
+
+    /// A tactic is run while no goal is left.
+    #[display(fmt = "no goals left")]
+    NoGoals,
+
+    /// A tactic cannot be run on the goal, for the given reason.
+    #[display(fmt = "tactic failed: {_0}")]
+    TacticFailed(String),
+
+    /// Some goals are left at the end of a tactic block.
+    #[display(fmt = "unsolved goals\n{}", "show_goals(_0)")]
+    UnsolvedGoals(Vec<Goal>),
+}
+
+/// A hypothesis of the local context of a goal.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Hypothesis {
+    /// The name of the hypothesis.
+    pub name: String,
+
+    /// The type of the hypothesis.
+    pub type_: String,
+
+    /// The value of the hypothesis, if it is bound by a local definition.
+    pub value: Option<String>,
+}
+
+/// A goal: the expected type of a hole, along with its local context.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Goal {
+    /// The name of the hole, if any.
+    pub name: Option<String>,
+
+    /// The location of the hole.
+    pub location: Location,
+
+    /// The local context of the hole, from the outermost to the innermost hypothesis.
+    pub hypotheses: Vec<Hypothesis>,
+
+    /// The expected type of the hole.
+    pub type_: String,
+}
+
+impl fmt::Display for Goal {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.name {
+            Some(ref name) => writeln!(f, "?{name} at {}:", self.location.start)?,
+            None => writeln!(f, "_ at {}:", self.location.start)?,
+        }
+
+        for hypothesis in &self.hypotheses {
+            match hypothesis.value {
+                Some(ref value) => writeln!(f, "{}: {} := {value}", hypothesis.name, hypothesis.type_)?,
+                None => writeln!(f, "{}: {}", hypothesis.name, hypothesis.type_)?,
+            }
+        }
+
+        write!(f, "\u{22A2} {}", self.type_)
+    }
+}
+
+/// Returns a textual representation of the goals `goals`.
+fn show_goals(goals: &[Goal]) -> String {
+    goals.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n")
+}
+
+/// The type of errors raised during elaboration.
+#[derive(Clone, Debug, Display, Eq, PartialEq)]
+pub enum Error<'build, 'arena> {
+    /// An error raised by the kernel on some part of the input.
+    #[display(fmt = "{_1}")]
+    Kernel(&'build Builder<'build>, kernel::error::Error<'arena>),
+
+    /// An error raised by the elaborator, at the given location.
+    #[display(fmt = "{_1}")]
+    Elaboration(Location, ErrorKind),
+}
+
+/// The type of results yielded by the elaborator.
+pub type Result<'build, 'arena, T> = core::result::Result<T, Error<'build, 'arena>>;
+
+/// The table of implicit arguments of named terms and declarations.
+///
+/// Each full name is associated to a list of flags, indicating which of its first arguments are
+/// implicit. Names not appearing in the table have no implicit argument.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Implicits(HashMap<String, Vec<bool>>);
+
+impl Implicits {
+    /// Creates an empty table.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the implicit arguments of the full name `name`.
+    #[inline]
+    pub fn insert(&mut self, name: &str, flags: Vec<bool>) {
+        if flags.contains(&true) {
+            self.0.insert(name.to_owned(), flags);
+        } else {
+            self.0.remove(name);
+        }
+    }
+
+    /// Records that the full name `name` has been abstracted over new leading arguments, the
+    /// implicit ones being given by `flags`.
+    #[inline]
+    pub fn generalise(&mut self, name: &str, mut flags: Vec<bool>) {
+        flags.extend(self.0.get(name).into_iter().flatten());
+        self.insert(name, flags);
+    }
+
+    /// Returns the flags indicating which arguments of the full name `name` are implicit.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, name: &str) -> &[bool] {
+        self.0.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    /// Indicates whether `builder` needs to be elaborated, that is, whether it has implicit
+    /// binders, holes or tactic blocks, refers to a name with implicit arguments or to a universe-polymorphic declaration
+    /// of `arena` without its universe levels, names being resolved in `scope`. Other builders are
+    /// already fully explicit and can be realised directly.
+    #[inline]
+    #[must_use]
+    pub fn involve(&self, builder: &Builder, scope: &Scope, arena: &Arena) -> bool {
+        use term::Payload::{
+            Abs, App, By, Decl, Explicit, Hole, ImplicitAbs, ImplicitProd, Let, NatLit, Prod, Prop, Sort, Type, Var, VarInstance,
+        };
+
+        match **builder {
+            ImplicitAbs(..) | ImplicitProd(..) | Explicit(_) | Hole(_) | By(_) => true,
+            Var(name) => match *scope.resolve(name, arena) {
+                [ref name] => {
+                    !self.get(name).is_empty() || (arena.get_binding(name).is_none() && arena.get_binding_decl(name).is_some())
+                },
+                _ => false,
+            },
+            VarInstance(name, _) => match *scope.resolve(name, arena) {
+                [ref name] => !self.get(name).is_empty(),
+                _ => false,
+            },
+            App(ref lhs, ref rhs) | Abs(_, ref lhs, ref rhs) | Prod(_, ref lhs, ref rhs) => {
+                self.involve(lhs, scope, arena) || self.involve(rhs, scope, arena)
+            },
+            Let(_, ref type_, ref value, ref body) => {
+                self.involve(type_, scope, arena) || self.involve(value, scope, arena) || self.involve(body, scope, arena)
+            },
+            Prop | Type(_) | Sort(_) | Decl(_) | NatLit(_) => false,
+        }
+    }
+}
+
+/// The result of the elaboration of a term, possibly along with its type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Output<'build> {
+    /// The elaborated term.
+    pub term: Elaborated<'build>,
+
+    /// The elaborated type, if one was provided.
+    pub type_: Option<Elaborated<'build>>,
+
+    /// The flags indicating which arguments of the term are implicit.
+    pub implicits: Vec<bool>,
+}
+
+/// A local hypothesis, possibly with a value if it is bound by a local definition.
+#[derive(Clone, Debug)]
+struct Entry<'build, 'arena> {
+    /// The name of the hypothesis.
+    name: &'build str,
+
+    /// The type of the hypothesis, living in the context made of the hypotheses preceding it.
+    type_: Expr<'build, 'arena>,
+
+    /// The value of the hypothesis, living in the same context as its type.
+    value: Option<Expr<'build, 'arena>>,
+}
+
+/// A list of hypotheses, from the outermost to the innermost one.
+type Context<'build, 'arena> = Vec<Entry<'build, 'arena>>;
+
+/// A metavariable.
+#[derive(Clone, Debug)]
+struct Meta<'build, 'arena> {
+    /// The context the metavariable was created in. The metavariable stands for a function of all
+    /// the variables of this context.
+    context: Context<'build, 'arena>,
+
+    /// The type of the metavariable, in its context.
+    type_: Expr<'build, 'arena>,
+
+    /// The location of the term whose elaboration introduced the metavariable.
+    location: Location,
+
+    /// The solution of the metavariable, if any, living in its context.
+    solution: Option<Expr<'build, 'arena>>,
+}
+
+/// A hole of the input.
+#[derive(Clone, Copy, Debug)]
+struct Hole<'build> {
+    /// The metavariable standing for the hole.
+    meta: usize,
+
+    /// The name of the hole, if any.
+    name: Option<&'build str>,
+}
+
+/// A unification problem postponed until more metavariables are solved.
+#[derive(Clone, Debug)]
+struct Constraint<'build, 'arena> {
+    /// The context of the problem.
+    context: Context<'build, 'arena>,
+
+    /// The expressions to unify.
+    lhs: Expr<'build, 'arena>,
+
+    /// The expressions to unify.
+    rhs: Expr<'build, 'arena>,
+
+    /// The location of the term whose elaboration raised the problem.
+    location: Location,
+}
+
+/// A universe metavariable.
+///
+/// The `n`-th universe metavariable is represented by the level variable `k + n`, where `k` is the
+/// number of universe variables of the declaration being elaborated.
+#[derive(Clone, Debug)]
+struct LevelMeta<'arena> {
+    /// The location of the term whose elaboration introduced the metavariable.
+    location: Location,
+
+    /// The solution of the metavariable, if any, in which no solved metavariable appears.
+    solution: Option<Level<'arena>>,
+}
+
+/// An equation between universe levels postponed until more metavariables are solved.
+#[derive(Clone, Debug)]
+struct LevelConstraint<'arena> {
+    /// The levels to make equal.
+    lhs: Level<'arena>,
+
+    /// The levels to make equal.
+    rhs: Level<'arena>,
+
+    /// The location of the term whose elaboration raised the problem.
+    location: Location,
+}
+
+/// An elaborator, turning builders into fully explicit terms.
+pub struct Elaborator<'build, 'env, 'arena> {
+    /// The implicit arguments of named terms.
+    implicits: &'env Implicits,
+
+    /// The scope in which names are resolved.
+    scope: &'env Scope,
+
+    /// The universe variables of the declaration being elaborated.
+    lvl_env: level::Environment<'build>,
+
+    /// The metavariables created so far.
+    metas: Vec<Meta<'build, 'arena>>,
+
+    /// The postponed unification problems.
+    postponed: Vec<Constraint<'build, 'arena>>,
+
+    /// The holes met so far.
+    holes: Vec<Hole<'build>>,
+
+    /// The universe metavariables created so far.
+    level_metas: Vec<LevelMeta<'arena>>,
+
+    /// The postponed equations between universe levels.
+    level_constraints: Vec<LevelConstraint<'arena>>,
+
+    /// Whether unification problems out of the pattern fragment can be postponed.
+    postponing: bool,
+
+    /// The location of the term being checked, used when postponing unification problems.
+    location: Location,
+}
+
+impl<'build, 'env, 'arena> Elaborator<'build, 'env, 'arena> {
+    /// Creates a new elaborator, relying on the table `implicits` and the universe variables
+    /// `vars`, names being resolved in `scope`.
+    #[inline]
+    #[must_use]
+    pub fn new(implicits: &'env Implicits, scope: &'env Scope, vars: &[&'build str]) -> Self {
+        Self {
+            implicits,
+            scope,
+            lvl_env: vars.iter().enumerate().map(|(n, name)| (*name, n)).collect(),
+            metas: Vec::new(),
+            postponed: Vec::new(),
+            holes: Vec::new(),
+            level_metas: Vec::new(),
+            level_constraints: Vec::new(),
+            postponing: true,
+            location: Location::default(),
+        }
+    }
+
+    /// Elaborates the term `term`, checking that it has type `type_` if one is provided.
+    ///
+    /// # Errors
+    /// If the term is ill-typed, or if some implicit argument cannot be inferred, yields an error
+    /// indicating the reason.
+    #[inline]
+    pub fn elaborate(
+        &mut self,
+        term: &'build Builder<'build>,
+        type_: Option<&'build Builder<'build>>,
+        arena: &mut Arena<'arena>,
+    ) -> Result<'build, 'arena, Output<'build>> {
+        let mut ctx = Context::new();
+
+        let mut implicits = Vec::new();
+
+        let (expr, type_, expected) = match type_ {
+            Some(type_) => {
+                let (type_, _) = self.infer_type(&mut ctx, type_, arena)?;
+                (self.check(&mut ctx, term, &type_, arena)?, Rc::clone(&type_), Some(type_))
+            },
+            // a named term on its own is elaborated as is, without inserting its implicit arguments
+            None if matches!(**term, term::Payload::Var(_) | term::Payload::VarInstance(..)) => {
+                let (expr, type_, flags) = self.infer_head(&mut ctx, term, arena)?;
+                implicits.extend_from_slice(flags);
+                (expr, type_, None)
+            },
+            None => {
+                let (expr, type_) = self.infer(&mut ctx, term, arena)?;
+                (expr, type_, None)
+            },
+        };
+
+        self.finish(arena)?;
+
+        if implicits.is_empty() {
+            let mut type_ = expose(self.zonk(&type_));
+
+            while let Payload::Prod(binder, _, ref body) = type_.payload {
+                implicits.push(binder.implicit);
+                type_ = expose(Rc::clone(body));
+            }
+        }
+
+        let unsolved = || Error::Elaboration(term.location(), ErrorKind::UnsolvedMeta(String::from("?")));
+
+        Ok(Output {
+            term: elaborated(&self.zonk_levels(&self.zonk(&expr), arena), 0, arena).ok_or_else(unsolved)?,
+            type_: expected
+                .map(|type_| elaborated(&self.zonk_levels(&self.zonk(&type_), arena), 0, arena).ok_or_else(unsolved))
+                .transpose()?,
+            implicits,
+        })
+    }
+
+    /// Indicates whether the term `term`, once applied to its implicit arguments, has a type
+    /// matching `pattern`, whose holes may remain unsolved.
+    #[inline]
+    pub fn matches(&mut self, term: &'build Builder<'build>, pattern: &'build Builder<'build>, arena: &mut Arena<'arena>) -> bool {
+        let mut ctx = Context::new();
+
+        let Ok((type_, _)) = self.infer_type(&mut ctx, pattern, arena) else {
+            return false;
+        };
+
+        if self.check(&mut ctx, term, &type_, arena).is_err() {
+            return false;
+        }
+
+        matches!(
+            self.finish(arena),
+            Ok(()) | Err(Error::Elaboration(_, ErrorKind::Holes(_) | ErrorKind::UnsolvedMeta(_) | ErrorKind::UnsolvedLevel))
+        )
+    }
+
+    /// Returns the goals of the holes to report: the unsolved anonymous ones and the named ones.
+    fn goals(&self, arena: &mut Arena<'arena>) -> Vec<Goal> {
+        self.holes
+            .iter()
+            .filter(|hole| hole.name.is_some() || self.metas[hole.meta].solution.is_none())
+            .map(|&hole| self.goal(hole, arena))
+            .collect()
+    }
+
+    /// Returns the goal of the hole `hole`.
+    fn goal(&self, hole: Hole, arena: &mut Arena<'arena>) -> Goal {
+        let meta = &self.metas[hole.meta];
+
+        let hypotheses = meta
+            .context
+            .iter()
+            .enumerate()
+            .map(|(depth, entry)| Hypothesis {
+                name: entry.name.to_owned(),
+                type_: self.show(&meta.context[..depth], &entry.type_, arena),
+                value: entry.value.as_ref().map(|value| self.show(&meta.context[..depth], value, arena)),
+            })
+            .collect();
+
+        Goal {
+            name: hole.name.map(ToOwned::to_owned),
+            location: meta.location,
+            hypotheses,
+            type_: self.show(&meta.context, &meta.type_, arena),
+        }
+    }
+
+    /// Returns a textual representation of `expr`, living in the context `ctx`.
+    fn show(&self, ctx: &[Entry<'build, 'arena>], expr: &Expr<'build, 'arena>, arena: &mut Arena<'arena>) -> String {
+        let mut names = ctx.iter().map(|entry| entry.name.to_owned()).collect();
+        let mut out = String::new();
+
+        write(&mut out, &mut names, &self.zonk_levels(&self.zonk(expr), arena))
+            .map(|()| out)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use kernel::memory::arena::use_arena_with_axioms;
+    use kernel::memory::declaration::builder::declaration;
+    use kernel::memory::level::builder as kernel_level;
+    use kernel::memory::term::builder as kernel_builder;
+
+    use super::*;
+    use crate::builder::{tactic, Buildable};
+
+    /// Returns a builder located at column `column` of the first line.
+    fn at<'build>(column: usize, payload: term::Payload<'build>) -> Builder<'build> {
+        Builder::new(Location::new((1, column), (1, column)), payload)
+    }
+
+    /// Returns the application of `fun` to `arg`.
+    fn app<'build>(column: usize, fun: Builder<'build>, arg: Builder<'build>) -> Builder<'build> {
+        at(column, term::Payload::App(Box::new(fun), Box::new(arg)))
+    }
+
+    /// Binds `id := fun (A: Type) (x: A) => x`, whose first argument is implicit.
+    fn bind_id(arena: &mut Arena, implicits: &mut Implicits) {
+        let id = arena
+            .build(kernel_builder::abs(
+                "A",
+                kernel_builder::type_usize(0),
+                kernel_builder::abs("x", kernel_builder::var("A"), kernel_builder::var("x")),
+            ))
+            .unwrap();
+
+        arena.bind("id", id);
+        implicits.insert("id", vec![true]);
+    }
+
+    /// Binds `poly.{u} := fun (A: Sort u) (x: A) => x`.
+    fn bind_poly(arena: &mut Arena) {
+        let poly = arena
+            .build_declaration(declaration(
+                kernel_builder::abs(
+                    "A",
+                    kernel_builder::sort(kernel_level::var("u")),
+                    kernel_builder::abs("x", kernel_builder::var("A"), kernel_builder::var("x")),
+                ),
+                &["u"],
+            ))
+            .unwrap();
+
+        arena.bind_decl("poly", poly);
+    }
+
+    #[test]
+    fn implicit_arguments() {
+        use_arena_with_axioms(|arena| {
+            let scope = Scope::new();
+            let mut implicits = Implicits::new();
+            bind_id(arena, &mut implicits);
+
+            // id Zero
+            let builder = app(1, at(1, term::Payload::Var("id")), at(4, term::Payload::Var("Zero")));
+            assert!(implicits.involve(&builder, &scope, arena));
+
+            let output = Elaborator::new(&implicits, &scope, &[]).elaborate(&builder, None, arena).unwrap();
+            let expected = arena
+                .build(kernel_builder::app(
+                    kernel_builder::app(kernel_builder::var("id"), kernel_builder::var("Nat")),
+                    kernel_builder::var("Zero"),
+                ))
+                .unwrap();
+
+            assert_eq!(output.term.realise(arena, &scope), Ok(expected));
+            assert!(output.implicits.is_empty());
+
+            // @id Nat Zero
+            let builder = app(
+                1,
+                app(1, at(1, term::Payload::Explicit(Box::new(at(2, term::Payload::Var("id"))))), at(5, term::Payload::Var("Nat"))),
+                at(9, term::Payload::Var("Zero")),
+            );
+
+            let output = Elaborator::new(&implicits, &scope, &[]).elaborate(&builder, None, arena).unwrap();
+            assert_eq!(output.term.realise(arena, &scope), Ok(expected));
+        });
+    }
+
+    #[test]
+    fn implicit_binders() {
+        use_arena_with_axioms(|arena| {
+            let scope = Scope::new();
+            let implicits = Implicits::new();
+
+            // fun {A: Type} (x: A) => x
+            let builder = at(
+                1,
+                term::Payload::ImplicitAbs(
+                    "A",
+                    Box::new(at(2, term::Payload::Type(Box::new(crate::builder::level::Builder::Const(0))))),
+                    Box::new(at(
+                        3,
+                        term::Payload::Abs("x", Box::new(at(4, term::Payload::Var("A"))), Box::new(at(5, term::Payload::Var("x")))),
+                    )),
+                ),
+            );
+
+            let output = Elaborator::new(&implicits, &scope, &[]).elaborate(&builder, None, arena).unwrap();
+
+            assert_eq!(output.implicits, vec![true, false]);
+            assert!(output.term.realise(arena, &scope).is_ok());
+        });
+    }
+
+    #[test]
+    fn unsolved_metavariable() {
+        use_arena_with_axioms(|arena| {
+            let scope = Scope::new();
+            let mut implicits = Implicits::new();
+            bind_id(arena, &mut implicits);
+
+            // fun x: Prop => id
+            let builder =
+                at(1, term::Payload::Abs("x", Box::new(at(2, term::Payload::Prop)), Box::new(at(3, term::Payload::Var("id")))));
+
+            assert_eq!(
+                Elaborator::new(&implicits, &scope, &[]).elaborate(&builder, None, arena),
+                Err(Error::Elaboration(Location::new((1, 3), (1, 3)), ErrorKind::UnsolvedMeta("Type".to_owned())))
+            );
+        });
+    }
+
+    #[test]
+    fn type_patterns() {
+        use_arena_with_axioms(|arena| {
+            let scope = Scope::new();
+            let mut implicits = Implicits::new();
+            bind_id(arena, &mut implicits);
+            bind_poly(arena);
+
+            let id = at(1, term::Payload::Var("id"));
+            let poly = at(1, term::Payload::Var("poly"));
+
+            // Nat -> Nat
+            let nat = at(
+                1,
+                term::Payload::Prod("_", Box::new(at(1, term::Payload::Var("Nat"))), Box::new(at(8, term::Payload::Var("Nat")))),
+            );
+
+            // _ -> _
+            let any = at(
+                1,
+                term::Payload::Prod("_", Box::new(at(1, term::Payload::Hole(None))), Box::new(at(6, term::Payload::Hole(None)))),
+            );
+
+            assert!(Elaborator::new(&implicits, &scope, &[]).matches(&id, &nat, arena));
+            assert!(Elaborator::new(&implicits, &scope, &[]).matches(&id, &any, arena));
+            assert!(Elaborator::new(&implicits, &scope, &[]).matches(&poly, &any, arena));
+            assert!(!Elaborator::new(&implicits, &scope, &[]).matches(&at(1, term::Payload::Var("Zero")), &any, arena));
+        });
+    }
+
+    #[test]
+    fn type_mismatch() {
+        use_arena_with_axioms(|arena| {
+            let scope = Scope::new();
+            let mut implicits = Implicits::new();
+            bind_id(arena, &mut implicits);
+
+            // id Zero : Prop
+            let builder = app(1, at(1, term::Payload::Var("id")), at(4, term::Payload::Var("Zero")));
+            let type_ = at(10, term::Payload::Prop);
+
+            assert_eq!(
+                Elaborator::new(&implicits, &scope, &[]).elaborate(&builder, Some(&type_), arena),
+                Err(Error::Elaboration(
+                    Location::new((1, 1), (1, 1)),
+                    ErrorKind::TypeMismatch("Prop".to_owned(), "Nat".to_owned())
+                ))
+            );
+        });
+    }
+
+    #[test]
+    fn universe_levels() {
+        use_arena_with_axioms(|arena| {
+            let scope = Scope::new();
+            let implicits = Implicits::new();
+            bind_poly(arena);
+
+            // poly Nat Zero
+            let builder = app(
+                1,
+                app(1, at(1, term::Payload::Var("poly")), at(6, term::Payload::Var("Nat"))),
+                at(10, term::Payload::Var("Zero")),
+            );
+            assert!(implicits.involve(&builder, &scope, arena));
+
+            let output = Elaborator::new(&implicits, &scope, &[]).elaborate(&builder, None, arena).unwrap();
+
+            // poly.{1} Nat Zero
+            let instance = at(1, term::Payload::VarInstance("poly", vec![crate::builder::level::Builder::Const(1)]));
+            let expected = app(1, app(1, instance, at(6, term::Payload::Var("Nat"))), at(10, term::Payload::Var("Zero")));
+
+            assert_eq!(output.term.realise(arena, &scope), expected.realise(arena, &scope));
+        });
+    }
+
+    #[test]
+    fn unsolved_level() {
+        use_arena_with_axioms(|arena| {
+            let scope = Scope::new();
+            let implicits = Implicits::new();
+            bind_poly(arena);
+
+            // fun x: Prop => poly
+            let builder =
+                at(1, term::Payload::Abs("x", Box::new(at(2, term::Payload::Prop)), Box::new(at(3, term::Payload::Var("poly")))));
+
+            assert_eq!(
+                Elaborator::new(&implicits, &scope, &[]).elaborate(&builder, None, arena),
+                Err(Error::Elaboration(Location::new((1, 3), (1, 3)), ErrorKind::UnsolvedLevel))
+            );
+        });
+    }
+
+    #[test]
+    fn holes() {
+        use_arena_with_axioms(|arena| {
+            let scope = Scope::new();
+            let implicits = Implicits::new();
+
+            // fun x: Prop => ?goal
+            let builder = at(
+                1,
+                term::Payload::Abs("x", Box::new(at(2, term::Payload::Prop)), Box::new(at(3, term::Payload::Hole(Some("goal"))))),
+            );
+
+            let Err(Error::Elaboration(location, ErrorKind::Holes(goals))) =
+                Elaborator::new(&implicits, &scope, &[]).elaborate(&builder, None, arena)
+            else {
+                panic!("the hole should be reported");
+            };
+
+            assert_eq!(location, Location::new((1, 3), (1, 3)));
+            assert_eq!(goals.len(), 1);
+            assert_eq!(goals[0].name, Some("goal".to_owned()));
+            assert_eq!(goals[0].hypotheses, vec![Hypothesis {
+                name: "x".to_owned(),
+                type_: "Prop".to_owned(),
+                value: None,
+            }]);
+        });
+    }
+
+    #[test]
+    fn tactics() {
+        use_arena_with_axioms(|arena| {
+            let scope = Scope::new();
+            let implicits = Implicits::new();
+
+            // Prop -> Prop
+            let type_ = at(1, term::Payload::Prod("_", Box::new(at(1, term::Payload::Prop)), Box::new(at(9, term::Payload::Prop))));
+
+            // by intro x; exact x
+            let intro = tactic::Builder::new(Location::new((1, 4), (1, 11)), tactic::Payload::Intro(vec!["x"]));
+            let exact =
+                tactic::Builder::new(Location::new((1, 13), (1, 20)), tactic::Payload::Exact(Box::new(at(19, term::Payload::Var("x")))));
+            let builder = at(1, term::Payload::By(vec![intro.clone(), exact]));
+
+            let output = Elaborator::new(&implicits, &scope, &[]).elaborate(&builder, Some(&type_), arena).unwrap();
+            let expected = arena.build(kernel_builder::abs("x", kernel_builder::prop(), kernel_builder::var("x"))).unwrap();
+            assert_eq!(output.term.realise(arena, &scope), Ok(expected));
+
+            // by intro x
+            let builder = at(1, term::Payload::By(vec![intro]));
+
+            let Err(Error::Elaboration(_, ErrorKind::UnsolvedGoals(goals))) =
+                Elaborator::new(&implicits, &scope, &[]).elaborate(&builder, Some(&type_), arena)
+            else {
+                panic!("the goal should be reported");
+            };
+
+            assert_eq!(goals.len(), 1);
+            assert_eq!(goals[0].type_, "Prop");
+            assert_eq!(goals[0].hypotheses.len(), 1);
+        });
+    }
+}
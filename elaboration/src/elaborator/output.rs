@@ -0,0 +1,123 @@
+//! Fully elaborated terms, which can be realised by the kernel.
+//!
+//! An elaborated term is fully explicit: its metavariables are all solved, and the terms the
+//! elaborator obtained from the kernel are embedded as [pinned](kernel::memory::term::builder::Pin)
+//! terms. Every node remembers the part of the input it was elaborated from, so that errors
+//! raised by the kernel can still be located.
+
+use kernel::error::ResultTerm;
+use kernel::memory::arena::Arena;
+use kernel::memory::term::builder::{abs, app, let_, pinned, prod, var, BuilderTrait, Pin};
+use kernel::memory::term::Term;
+use kernel::trace::Trace;
+
+use crate::builder::term::Builder;
+use crate::builder::Buildable;
+use crate::scope::Scope;
+
+/// A fully elaborated term.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Elaborated<'build> {
+    /// The part of the input the term was elaborated from, if any.
+    origin: Option<&'build Builder<'build>>,
+
+    /// The content of the term.
+    payload: Payload<'build>,
+}
+
+/// The content of an elaborated term.
+///
+/// Variables are named as in the input where possible, and otherwise after the depth at which
+/// they are bound, which cannot clash with any identifier of the input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[allow(clippy::missing_docs_in_private_items)]
+pub(super) enum Payload<'build> {
+    Var(String),
+    Pinned(Pin),
+    App(Box<Elaborated<'build>>, Box<Elaborated<'build>>),
+    Abs(String, Box<Elaborated<'build>>, Box<Elaborated<'build>>),
+    Prod(String, Box<Elaborated<'build>>, Box<Elaborated<'build>>),
+    Let(String, Box<Elaborated<'build>>, Box<Elaborated<'build>>, Box<Elaborated<'build>>),
+}
+
+/// Returns the name of the variable bound at depth `depth`, for variables which cannot be named
+/// as in the input.
+pub(super) fn name(depth: usize) -> String {
+    format!("#{depth}")
+}
+
+impl<'build> Elaborated<'build> {
+    /// Creates a new elaborated term.
+    pub(super) const fn new(origin: Option<&'build Builder<'build>>, payload: Payload<'build>) -> Self {
+        Self { origin, payload }
+    }
+
+    /// Returns the part of the input the subterm designated by `trace` was elaborated from.
+    ///
+    /// Subterms introduced by the elaborator are attributed to the closest enclosing part of the
+    /// input, and ultimately to `default`.
+    #[inline]
+    #[must_use]
+    pub fn origin(&self, trace: &[Trace], default: &'build Builder<'build>) -> &'build Builder<'build> {
+        let mut term = self;
+        let mut origin = self.origin.unwrap_or(default);
+
+        for trace in trace.iter().rev() {
+            let next = match (trace, &term.payload) {
+                (
+                    Trace::Left,
+                    Payload::App(lhs, _) | Payload::Abs(_, lhs, _) | Payload::Prod(_, lhs, _) | Payload::Let(_, lhs, _, _),
+                ) => lhs,
+                (
+                    Trace::Right,
+                    Payload::App(_, rhs) | Payload::Abs(_, _, rhs) | Payload::Prod(_, _, rhs) | Payload::Let(_, _, _, rhs),
+                ) => rhs,
+                (Trace::Middle, Payload::Let(_, _, value, _)) => value,
+                _ => break,
+            };
+
+            term = next;
+            origin = term.origin.unwrap_or(origin);
+        }
+
+        origin
+    }
+
+    /// Associates an elaborated term to a builder trait, whose names are all local.
+    fn local_closure(&'build self) -> impl BuilderTrait<'build> {
+        move |arena, env, lvl_env, depth| match self.payload {
+            Payload::Var(ref name) => var(name)(arena, env, lvl_env, depth),
+            Payload::Pinned(pin) => pinned(pin)(arena, env, lvl_env, depth),
+            Payload::App(ref l, ref r) => app(l.local_closure(), r.local_closure())(arena, env, lvl_env, depth),
+            Payload::Abs(ref s, ref arg, ref body) => abs(s, arg.local_closure(), body.local_closure())(arena, env, lvl_env, depth),
+            Payload::Prod(ref s, ref arg, ref body) => {
+                prod(s, arg.local_closure(), body.local_closure())(arena, env, lvl_env, depth)
+            },
+            Payload::Let(ref s, ref type_, ref value, ref body) => {
+                let_(s, type_.local_closure(), value.local_closure(), body.local_closure())(arena, env, lvl_env, depth)
+            },
+        }
+    }
+}
+
+impl<'build> Buildable<'build> for Elaborated<'build> {
+    type Output<'arena> = Term<'arena>;
+
+    type Closure = impl BuilderTrait<'build>;
+
+    /// Realises an elaborated term into a [`Term`].
+    ///
+    /// # Errors
+    /// If the term could not be built, yields an error indicating the reason.
+    #[inline]
+    fn realise<'arena>(&self, arena: &mut Arena<'arena>, scope: &Scope) -> ResultTerm<'arena> {
+        arena.build(self.as_closure(scope))
+    }
+
+    /// Associates an elaborated term to a builder trait. Its names are all local, and are not
+    /// resolved in the scope.
+    #[inline]
+    fn as_closure(&'build self, _: &'build Scope) -> Self::Closure {
+        self.local_closure()
+    }
+}
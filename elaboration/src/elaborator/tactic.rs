@@ -0,0 +1,428 @@
+//! Tactics.
+//!
+//! A tactic block stands for a metavariable, its first *goal*. The tactics of the block are run
+//! in order on the first unsolved goal: each of them solves it with a term which may involve new
+//! metavariables, which become the next goals. The block is elaborated into the solution of its
+//! first goal, once no goal is left.
+//!
+//! Tactics only build terms: these are checked by the kernel along with the rest of the
+//! declaration, as usual.
+
+use alloc::rc::Rc;
+
+use kernel::memory::arena::Arena;
+
+use super::expr::{any, apply, generalise, inherit, instantiate, new, read, shift, spine, Binder, Expr, Payload};
+use super::unify::var_type;
+use super::{Context, Elaborator, Entry, Error, ErrorKind, Hole, Result};
+use crate::builder::tactic::{Builder as Tactic, Payload as TacticPayload};
+use crate::builder::term::Builder;
+use crate::location::Location;
+
+/// Returns the error raised by a tactic located at `location` which fails for the reason `reason`.
+fn failure<'build, 'arena>(location: Location, reason: String) -> Error<'build, 'arena> {
+    Error::Elaboration(location, ErrorKind::TacticFailed(reason))
+}
+
+impl<'build, 'env, 'arena> Elaborator<'build, 'env, 'arena> {
+    /// Elaborates the tactic block `builder`, made of the tactics `tactics`, in the context `ctx`,
+    /// checking that it has type `expected`.
+    ///
+    /// # Errors
+    /// If a tactic fails, or if goals are left once all the tactics are run, yields an error
+    /// indicating the reason.
+    pub(super) fn by(
+        &mut self,
+        ctx: &Context<'build, 'arena>,
+        builder: &'build Builder<'build>,
+        tactics: &'build [Tactic<'build>],
+        expected: &Expr<'build, 'arena>,
+        arena: &mut Arena<'arena>,
+    ) -> Result<'build, 'arena, Expr<'build, 'arena>> {
+        let first = Hole {
+            meta: self.metas.len(),
+            name: None,
+        };
+        let term = self.new_meta(ctx, expected, builder.location());
+        let mut goals = vec![first];
+
+        for tactic in tactics {
+            goals.retain(|goal| self.metas[goal.meta].solution.is_none());
+
+            let Some((&goal, rest)) = goals.split_first() else {
+                return Err(Error::Elaboration(tactic.location(), ErrorKind::NoGoals));
+            };
+
+            let mut next = self.run(goal, tactic, arena)?;
+            next.extend_from_slice(rest);
+            goals = next;
+        }
+
+        goals.retain(|goal| self.metas[goal.meta].solution.is_none());
+
+        if !goals.is_empty() {
+            let goals = goals.into_iter().map(|goal| self.goal(goal, arena)).collect();
+            return Err(Error::Elaboration(builder.location(), ErrorKind::UnsolvedGoals(goals)));
+        }
+
+        Ok(inherit(term, Some(builder)))
+    }
+
+    /// Runs `tactic` on the goal `goal`, and returns the new goals, to be solved first.
+    ///
+    /// The new goals are the metavariables created by the tactic and left unsolved, those which
+    /// do not appear in the type of the others coming first.
+    fn run(
+        &mut self,
+        goal: Hole<'build>,
+        tactic: &'build Tactic<'build>,
+        arena: &mut Arena<'arena>,
+    ) -> Result<'build, 'arena, Vec<Hole<'build>>> {
+        let mut ctx = self.metas[goal.meta].context.clone();
+        let target = self.zonk(&self.metas[goal.meta].type_);
+        let location = tactic.location();
+
+        let first = self.metas.len();
+        let holes = self.holes.len();
+        self.location = location;
+
+        let solution = match **tactic {
+            TacticPayload::Intro(ref names) => self.intro(&mut ctx, &target, names, location, arena)?,
+            TacticPayload::Exact(ref term) => {
+                let solution = self.check(&mut ctx, term, &target, arena)?;
+                self.metas[goal.meta].solution = Some(solution);
+
+                return Ok(Vec::new());
+            },
+            TacticPayload::Apply(ref term) => self.apply_term(&mut ctx, term, &target, arena)?,
+            TacticPayload::Refine(ref term) => self.check(&mut ctx, term, &target, arena)?,
+            TacticPayload::Rewrite(reverse, ref term) => self.rewrite(&mut ctx, term, reverse, &target, arena)?,
+            TacticPayload::Induction(name) => self.induction(&mut ctx, name, &target, location, arena)?,
+            TacticPayload::Exfalso => self.exfalso(&mut ctx, &target, location, arena)?,
+            TacticPayload::Assumption => self.assumption(&mut ctx, &target, location, arena)?,
+        };
+
+        self.metas[goal.meta].solution = Some(solution);
+
+        // the holes of the term given to the tactic are now goals
+        let named: Vec<_> = self.holes.drain(holes..).collect();
+
+        let (independent, dependent): (Vec<_>, Vec<_>) = (first..self.metas.len())
+            .filter(|&meta| self.metas[meta].solution.is_none())
+            .map(|meta| Hole {
+                meta,
+                name: named.iter().find(|hole| hole.meta == meta).and_then(|hole| hole.name),
+            })
+            .partition(|goal| {
+                (first..self.metas.len()).all(|other| {
+                    other == goal.meta
+                        || self.metas[other].solution.is_some()
+                        || !any(&self.zonk(&self.metas[other].type_), &|payload| *payload == Payload::Meta(goal.meta))
+                })
+            });
+
+        Ok(independent.into_iter().chain(dependent).collect())
+    }
+
+    /// Introduces the arguments of `target`, living in the context `ctx`, with the names `names`,
+    /// and returns a term of type `target` made of a new goal under abstractions.
+    fn intro(
+        &mut self,
+        ctx: &mut Context<'build, 'arena>,
+        target: &Expr<'build, 'arena>,
+        names: &'build [&'build str],
+        location: Location,
+        arena: &mut Arena<'arena>,
+    ) -> Result<'build, 'arena, Expr<'build, 'arena>> {
+        let whnf = self.whnf(ctx, target, arena);
+
+        let Payload::Prod(binder, ref arg_type, ref body) = whnf.payload else {
+            return Err(failure(location, format!("{} is not a product", self.show(ctx, target, arena))));
+        };
+
+        let binder = Binder {
+            name: names.first().copied().unwrap_or(binder.name),
+            implicit: binder.implicit,
+        };
+
+        ctx.push(Entry {
+            name: binder.name,
+            type_: Rc::clone(arg_type),
+            value: None,
+        });
+
+        let body = match names.get(1..) {
+            Some(names) if !names.is_empty() => self.intro(ctx, body, names, location, arena),
+            _ => Ok(self.new_meta(ctx, body, location)),
+        };
+
+        ctx.pop();
+        Ok(new(Payload::Abs(binder, Rc::clone(arg_type), body?)))
+    }
+
+    /// Returns the term `term`, applied to as many new goals as needed for it to have type
+    /// `target`, living in the context `ctx`.
+    fn apply_term(
+        &mut self,
+        ctx: &mut Context<'build, 'arena>,
+        term: &'build Builder<'build>,
+        target: &Expr<'build, 'arena>,
+        arena: &mut Arena<'arena>,
+    ) -> Result<'build, 'arena, Expr<'build, 'arena>> {
+        let (mut expr, type_) = self.infer(ctx, term, arena)?;
+        let mut remaining = Rc::clone(&type_);
+
+        loop {
+            if self.try_unify(ctx, target, &remaining, arena) {
+                return Ok(expr);
+            }
+
+            let whnf = self.whnf(ctx, &remaining, arena);
+
+            let Payload::Prod(_, ref arg_type, ref body) = whnf.payload else {
+                let reason =
+                    format!("cannot apply a term of type {} to the goal {}", self.show(ctx, &type_, arena), self.show(ctx, target, arena));
+
+                return Err(failure(term.location(), reason));
+            };
+
+            let meta = self.new_meta(ctx, arg_type, term.location());
+            expr = new(Payload::App(expr, Rc::clone(&meta)));
+            remaining = instantiate(body, &meta);
+        }
+    }
+
+    /// Returns a term of type `target`, living in the context `ctx`, made of a new goal where the
+    /// left-hand side of the equality `term` is replaced with its right-hand side, or the converse
+    /// if `reverse` is set.
+    ///
+    /// The term is built with the recursor `Eq_rec`: given `e: Eq A a b` and a goal `G a`, the new
+    /// goal is `G b`, and `Eq_rec A a (fun z _ => G z -> G a) (fun g => g) b e` turns a proof of
+    /// the latter into one of the former.
+    fn rewrite(
+        &mut self,
+        ctx: &mut Context<'build, 'arena>,
+        term: &'build Builder<'build>,
+        reverse: bool,
+        target: &Expr<'build, 'arena>,
+        arena: &mut Arena<'arena>,
+    ) -> Result<'build, 'arena, Expr<'build, 'arena>> {
+        let location = term.location();
+        let (proof, type_) = self.infer(ctx, term, arena)?;
+
+        let sort = self.new_sort(term, arena)?;
+        let carrier = self.new_meta(ctx, &sort, location);
+        let lhs = self.new_meta(ctx, &carrier, location);
+        let rhs = self.new_meta(ctx, &carrier, location);
+        let eq = self.app_global(ctx, "Eq", &[Rc::clone(&carrier), Rc::clone(&lhs), Rc::clone(&rhs)], location, arena)?;
+
+        if !self.try_unify(ctx, &eq, &type_, arena) {
+            return Err(failure(location, format!("{} is not an equality", self.show(ctx, &type_, arena))));
+        }
+
+        let (carrier, lhs, rhs) = (self.zonk(&carrier), self.canonical(ctx, &lhs, arena), self.canonical(ctx, &rhs, arena));
+        let from = if reverse { &rhs } else { &lhs };
+
+        // the goal, as a function of the rewritten term
+        let target = self.canonical(ctx, target, arena);
+        let motive = generalise(&target, from, 0);
+
+        if motive == shift(&target, 1, 0) {
+            return Err(failure(location, format!("{} does not appear in the goal", self.show(ctx, from, arena))));
+        }
+
+        let at_lhs = instantiate(&motive, &lhs);
+
+        // in the context extended with `z: A` and `_: Eq A a z`
+        let (premise, conclusion) = if reverse {
+            (shift(&at_lhs, 2, 0), shift(&motive, 2, 0))
+        } else {
+            (shift(&motive, 1, 0), shift(&at_lhs, 3, 0))
+        };
+
+        let eq_head = spine(&eq).0;
+        let eq_type = apply(eq_head, &[shift(&carrier, 1, 0), shift(&lhs, 1, 0), new(Payload::Var(1))]);
+
+        let motive_fun = new(Payload::Abs(
+            Binder::ANONYMOUS,
+            Rc::clone(&carrier),
+            new(Payload::Abs(Binder::ANONYMOUS, eq_type, new(Payload::Prod(Binder::ANONYMOUS, premise, conclusion)))),
+        ));
+        let identity = new(Payload::Abs(Binder::ANONYMOUS, Rc::clone(&at_lhs), new(Payload::Var(1))));
+
+        let goal_type = if reverse { at_lhs } else { instantiate(&motive, &rhs) };
+        let goal = self.new_meta(ctx, &goal_type, location);
+
+        let cast = self.app_global(ctx, "Eq_rec", &[carrier, lhs, motive_fun, identity, rhs, proof], location, arena)?;
+        Ok(new(Payload::App(cast, goal)))
+    }
+
+    /// Returns a term of type `target`, living in the context `ctx`, which proves it by induction
+    /// on the hypothesis `name`, a natural number, with the recursor `Nat_rec`. The new goals are
+    /// the base case and the inductive step.
+    fn induction(
+        &mut self,
+        ctx: &mut Context<'build, 'arena>,
+        name: &'build str,
+        target: &Expr<'build, 'arena>,
+        location: Location,
+        arena: &mut Arena<'arena>,
+    ) -> Result<'build, 'arena, Expr<'build, 'arena>> {
+        let Some(position) = ctx.iter().rposition(|entry| entry.name == name) else {
+            return Err(failure(location, format!("unknown hypothesis {name}")));
+        };
+
+        let index = ctx.len() - position;
+        let (nat, _) = self.global("Nat", location, arena)?;
+
+        if !self.try_unify(ctx, &var_type(ctx, index), &nat, arena) {
+            return Err(failure(location, format!("{name} is not a natural number")));
+        }
+
+        let (zero, _) = self.global("Zero", location, arena)?;
+        let (succ, _) = self.global("Succ", location, arena)?;
+
+        // the goal, as a function of the natural number
+        let motive = generalise(target, &new(Payload::Var(index)), 0);
+        let motive_fun = new(Payload::Abs(
+            Binder {
+                name,
+                implicit: false,
+            },
+            Rc::clone(&nat),
+            Rc::clone(&motive),
+        ));
+
+        let base = self.new_meta(ctx, &instantiate(&motive, &zero), location);
+
+        // in the context extended with `n: Nat` and `ih: P n`
+        let succ_n = new(Payload::App(succ, new(Payload::Var(1))));
+        let step_type = shift(&instantiate(&shift(&motive, 1, 1), &succ_n), 1, 0);
+
+        ctx.push(Entry {
+            name,
+            type_: Rc::clone(&nat),
+            value: None,
+        });
+        ctx.push(Entry {
+            name: "ih",
+            type_: Rc::clone(&motive),
+            value: None,
+        });
+
+        let step = self.new_meta(ctx, &step_type, location);
+
+        ctx.truncate(ctx.len() - 2);
+
+        let step_fun = new(Payload::Abs(
+            Binder {
+                name,
+                implicit: false,
+            },
+            Rc::clone(&nat),
+            new(Payload::Abs(
+                Binder {
+                    name: "ih",
+                    implicit: false,
+                },
+                motive,
+                step,
+            )),
+        ));
+
+        self.app_global(ctx, "Nat_rec", &[motive_fun, base, step_fun, new(Payload::Var(index))], location, arena)
+    }
+
+    /// Returns a term of type `target`, living in the context `ctx`, made of a new goal of type
+    /// `False`, with the recursor `False_rec`.
+    fn exfalso(
+        &mut self,
+        ctx: &mut Context<'build, 'arena>,
+        target: &Expr<'build, 'arena>,
+        location: Location,
+        arena: &mut Arena<'arena>,
+    ) -> Result<'build, 'arena, Expr<'build, 'arena>> {
+        let (false_, _) = self.global("False", location, arena)?;
+        let goal = self.new_meta(ctx, &false_, location);
+        let motive = new(Payload::Abs(Binder::ANONYMOUS, false_, shift(target, 1, 0)));
+
+        self.app_global(ctx, "False_rec", &[motive, goal], location, arena)
+    }
+
+    /// Returns the innermost hypothesis of the context `ctx` whose type is `target`.
+    fn assumption(
+        &mut self,
+        ctx: &mut Context<'build, 'arena>,
+        target: &Expr<'build, 'arena>,
+        location: Location,
+        arena: &mut Arena<'arena>,
+    ) -> Result<'build, 'arena, Expr<'build, 'arena>> {
+        for index in 1..=ctx.len() {
+            if self.try_unify(ctx, target, &var_type(ctx, index), arena) {
+                return Ok(new(Payload::Var(index)));
+            }
+        }
+
+        Err(failure(location, format!("no hypothesis has type {}", self.show(ctx, target, arena))))
+    }
+
+    /// Returns the global constant `name`, instantiated with new universe metavariables if it is
+    /// a universe-polymorphic declaration, along with its type.
+    fn global(
+        &mut self,
+        name: &str,
+        location: Location,
+        arena: &mut Arena<'arena>,
+    ) -> Result<'build, 'arena, (Expr<'build, 'arena>, Expr<'build, 'arena>)> {
+        let term = match arena.get_binding_decl(name) {
+            Some(decl) => self.instance_term(name, decl.vars(), location, arena).ok(),
+            None => arena.get_binding(name),
+        };
+
+        let type_ = term.and_then(|term| term.infer(arena).ok());
+
+        match (term, type_) {
+            (Some(term), Some(type_)) => Ok((new(Payload::Atom(term)), read(type_))),
+            _ => Err(failure(location, format!("{name} is not defined"))),
+        }
+    }
+
+    /// Returns the application of the global constant `name` to `args`, living in the context
+    /// `ctx`, solving the universe metavariables of the constant along the way.
+    fn app_global(
+        &mut self,
+        ctx: &mut Context<'build, 'arena>,
+        name: &str,
+        args: &[Expr<'build, 'arena>],
+        location: Location,
+        arena: &mut Arena<'arena>,
+    ) -> Result<'build, 'arena, Expr<'build, 'arena>> {
+        let (mut expr, mut type_) = self.global(name, location, arena)?;
+
+        for arg in args {
+            let whnf = self.whnf(ctx, &type_, arena);
+
+            let (Payload::Prod(_, ref arg_type, ref body), Some(actual)) = (&whnf.payload, self.type_of(ctx, arg, arena)) else {
+                return Err(failure(location, format!("cannot build an application of {name}")));
+            };
+
+            self.constrain(ctx, arg_type, &actual, location, arena)?;
+
+            expr = new(Payload::App(expr, Rc::clone(arg)));
+            type_ = instantiate(body, arg);
+        }
+
+        Ok(expr)
+    }
+
+    /// Returns `expr`, living in the context `ctx`, as given back by the kernel if it has no
+    /// metavariable, so that its subterms can be compared syntactically.
+    fn canonical(
+        &self,
+        ctx: &Context<'build, 'arena>,
+        expr: &Expr<'build, 'arena>,
+        arena: &mut Arena<'arena>,
+    ) -> Expr<'build, 'arena> {
+        self.to_kernel(ctx, expr, arena).map_or_else(|| self.zonk(expr), read)
+    }
+}
@@ -0,0 +1,663 @@
+//! Metavariables and unification.
+//!
+//! Unification problems `?m x1 ... xn = t`, where the `xi` are distinct variables, are solved by
+//! abstracting `t` over these variables. Problems involving a metavariable outside of this
+//! fragment are postponed, while problems without metavariables are handed over to the kernel.
+//! Atoms which only differ by their universe levels are unified by solving equations between
+//! these levels (see [`super::universe`]).
+
+use alloc::rc::Rc;
+use core::mem;
+
+use kernel::memory::arena::Arena;
+use kernel::memory::context::Context as KernelContext;
+use kernel::memory::term::builder::prop;
+use kernel::memory::term::{Payload as TermPayload, Term};
+
+use super::expr::{
+    any, apply, expose, has_meta, inherit, instantiate, new, read, rename, replace, shift, spine, substitute, view, Binder, Expr,
+    Payload,
+};
+use super::output::{self, Elaborated};
+use super::{Constraint, Context, Elaborator, Entry, Error, ErrorKind, LevelConstraint, LevelMeta, Meta, Result};
+use crate::builder::Buildable;
+use crate::location::Location;
+
+use Payload::{Abs, App, Atom, Let, Prod, Var};
+
+/// Returns the type of the variable of de Bruijn index `index` in the context `ctx`.
+pub(super) fn var_type<'build, 'arena>(ctx: &Context<'build, 'arena>, index: usize) -> Expr<'build, 'arena> {
+    shift(&ctx[ctx.len() - index].type_, index, 0)
+}
+
+/// Returns the expression `expr`, living in the context `ctx`, where the variables bound to a
+/// value in `ctx` are replaced by this value.
+fn unfold_lets<'build, 'arena>(ctx: &[Entry<'build, 'arena>], expr: &Expr<'build, 'arena>) -> Expr<'build, 'arena> {
+    if ctx.iter().all(|entry| entry.value.is_none()) {
+        return Rc::clone(expr);
+    }
+
+    replace(
+        expr,
+        &|index| {
+            let position = ctx.len().checked_sub(index)?;
+            let value = ctx[position].value.as_ref()?;
+
+            Some(shift(&unfold_lets(&ctx[..position], value), index, 0))
+        },
+        0,
+    )
+}
+
+/// Returns the solution `solution` of a metavariable of arity `arity`, applied to `args`.
+///
+/// Returns `None` if there are not enough arguments to instantiate the solution.
+fn instantiate_meta<'build, 'arena>(
+    solution: &Expr<'build, 'arena>,
+    arity: usize,
+    args: &[Expr<'build, 'arena>],
+) -> Option<Expr<'build, 'arena>> {
+    let (subs, rest) = args.split_at_checked(arity)?;
+
+    Some(rest.iter().fold(substitute(solution, subs, 0), |acc, arg| match acc.payload {
+        Abs(_, _, ref body) => instantiate(body, arg),
+        _ => new(App(acc, Rc::clone(arg))),
+    }))
+}
+
+/// Returns the elaborated term corresponding to `expr`, living at depth `depth`, if it has no
+/// metavariable.
+///
+/// The variables of the context are named after their depth (see [`output::name`]), while the
+/// binders of `expr` keep the name given in the input, unless it is anonymous or already bound
+/// by an enclosing binder.
+pub(super) fn elaborated<'build, 'arena>(
+    expr: &Expr<'build, 'arena>,
+    depth: usize,
+    arena: &mut Arena<'arena>,
+) -> Option<Elaborated<'build>> {
+    let mut names = (0..depth).map(output::name).collect();
+
+    elaborated_under(expr, &mut names, arena)
+}
+
+/// Returns the elaborated term corresponding to `expr`, under binders whose variables are named
+/// `names`, if it has no metavariable.
+fn elaborated_under<'build, 'arena>(
+    expr: &Expr<'build, 'arena>,
+    names: &mut Vec<String>,
+    arena: &mut Arena<'arena>,
+) -> Option<Elaborated<'build>> {
+    let payload = match expr.payload {
+        Var(index) => output::Payload::Var(names.get(names.len().checked_sub(index)?)?.clone()),
+        Payload::Meta(_) => return None,
+        Atom(term) => output::Payload::Pinned(arena.pin(term)?),
+        App(ref fun, ref arg) => output::Payload::App(
+            Box::new(elaborated_under(fun, names, arena)?),
+            Box::new(elaborated_under(arg, names, arena)?),
+        ),
+        Abs(binder, ref arg_type, ref body) => {
+            let arg_type = elaborated_under(arg_type, names, arena)?;
+            let (name, body) = elaborated_binder(binder, body, names, arena)?;
+
+            output::Payload::Abs(name, Box::new(arg_type), Box::new(body))
+        },
+        Prod(binder, ref arg_type, ref body) => {
+            let arg_type = elaborated_under(arg_type, names, arena)?;
+            let (name, body) = elaborated_binder(binder, body, names, arena)?;
+
+            output::Payload::Prod(name, Box::new(arg_type), Box::new(body))
+        },
+        Let(binder, ref type_, ref value, ref body) => {
+            let type_ = elaborated_under(type_, names, arena)?;
+            let value = elaborated_under(value, names, arena)?;
+            let (name, body) = elaborated_binder(binder, body, names, arena)?;
+
+            output::Payload::Let(name, Box::new(type_), Box::new(value), Box::new(body))
+        },
+    };
+
+    Some(Elaborated::new(expr.origin, payload))
+}
+
+/// Returns the name of the variable bound by `binder`, along with the elaborated term
+/// corresponding to `body`, which lives under it.
+fn elaborated_binder<'build, 'arena>(
+    binder: Binder<'build>,
+    body: &Expr<'build, 'arena>,
+    names: &mut Vec<String>,
+    arena: &mut Arena<'arena>,
+) -> Option<(String, Elaborated<'build>)> {
+    let name = if binder.name == "_" || names.iter().any(|name| name == binder.name) {
+        output::name(names.len())
+    } else {
+        binder.name.to_owned()
+    };
+
+    names.push(name);
+    let body = elaborated_under(body, names, arena);
+    let name = names.pop()?;
+
+    Some((name, body?))
+}
+
+/// The state of the metavariables at some point of the elaboration, to backtrack to.
+pub(super) struct Snapshot<'build, 'arena> {
+    /// The solutions of the metavariables.
+    solutions: Vec<Option<Expr<'build, 'arena>>>,
+
+    /// The number of postponed unification problems.
+    postponed: usize,
+
+    /// The number of holes.
+    holes: usize,
+
+    /// The universe metavariables.
+    level_metas: Vec<LevelMeta<'arena>>,
+
+    /// The number of postponed equations between universe levels.
+    level_constraints: usize,
+}
+
+impl<'build, 'env, 'arena> Elaborator<'build, 'env, 'arena> {
+    /// Creates a new metavariable of type `type_` in the context `ctx`, and returns it, applied to
+    /// all the variables of the context.
+    pub(super) fn new_meta(
+        &mut self,
+        ctx: &Context<'build, 'arena>,
+        type_: &Expr<'build, 'arena>,
+        location: Location,
+    ) -> Expr<'build, 'arena> {
+        let id = self.metas.len();
+
+        self.metas.push(Meta {
+            context: ctx.clone(),
+            type_: Rc::clone(type_),
+            location,
+            solution: None,
+        });
+
+        let args: Vec<_> = (1..=ctx.len()).rev().map(|index| new(Var(index))).collect();
+        apply(new(Payload::Meta(id)), &args)
+    }
+
+    /// Returns the instantiation of the head of `expr` if it is a solved metavariable.
+    fn solved_head(&self, expr: &Expr<'build, 'arena>) -> Option<Expr<'build, 'arena>> {
+        let (head, args) = spine(expr);
+
+        let Payload::Meta(id) = head.payload else {
+            return None;
+        };
+
+        let meta = &self.metas[id];
+        instantiate_meta(meta.solution.as_ref()?, meta.context.len(), &args)
+    }
+
+    /// Returns the number of solved metavariables, universe metavariables included.
+    fn solved(&self) -> usize {
+        self.metas.iter().filter(|meta| meta.solution.is_some()).count()
+            + self.level_metas.iter().filter(|meta| meta.solution.is_some()).count()
+    }
+
+    /// Indicates whether `expr` is an unsolved metavariable, possibly applied to some arguments.
+    pub(super) fn is_flex(&self, expr: &Expr<'build, 'arena>) -> bool {
+        matches!(spine(expr).0.payload, Payload::Meta(id) if self.metas[id].solution.is_none())
+    }
+
+    /// Returns `expr`, where all the solved metavariables are replaced by their solution.
+    pub(super) fn zonk(&self, expr: &Expr<'build, 'arena>) -> Expr<'build, 'arena> {
+        if !has_meta(expr) {
+            return Rc::clone(expr);
+        }
+
+        if let Some(solution) = self.solved_head(expr) {
+            return inherit(self.zonk(&solution), expr.origin);
+        }
+
+        let payload = match expr.payload {
+            App(ref fun, ref arg) => App(self.zonk(fun), self.zonk(arg)),
+            Abs(binder, ref arg_type, ref body) => Abs(binder, self.zonk(arg_type), self.zonk(body)),
+            Prod(binder, ref arg_type, ref body) => Prod(binder, self.zonk(arg_type), self.zonk(body)),
+            Let(binder, ref type_, ref value, ref body) => Let(binder, self.zonk(type_), self.zonk(value), self.zonk(body)),
+            _ => return Rc::clone(expr),
+        };
+
+        inherit(new(payload), expr.origin)
+    }
+
+    /// Returns the weak head normal form of `expr`, living in the context `ctx`.
+    ///
+    /// The head of the result is exposed, if it is an atom.
+    pub(super) fn whnf(
+        &self,
+        ctx: &Context<'build, 'arena>,
+        expr: &Expr<'build, 'arena>,
+        arena: &mut Arena<'arena>,
+    ) -> Expr<'build, 'arena> {
+        let mut expr = Rc::clone(expr);
+
+        loop {
+            let (head, args) = spine(&expr);
+
+            let next = match head.payload {
+                Payload::Meta(_) => self.solved_head(&expr),
+                Abs(_, _, ref body) => args.split_first().map(|(arg, rest)| apply(instantiate(body, arg), rest)),
+                Let(_, _, ref value, ref body) => Some(apply(instantiate(body, value), &args)),
+                Var(index) => ctx
+                    .len()
+                    .checked_sub(index)
+                    .and_then(|position| ctx.get(position))
+                    .and_then(|entry| entry.value.as_ref())
+                    .map(|value| apply(shift(value, index, 0), &args)),
+                Atom(term) => self.whnf_atom(ctx, &expr, term, &args, arena),
+                _ => None,
+            };
+
+            match next {
+                Some(next) => expr = next,
+                None => return expose(expr),
+            }
+        }
+    }
+
+    /// Performs one reduction step on `expr`, living in the context `ctx`, whose head is the atom
+    /// `head`, applied to `args`.
+    fn whnf_atom(
+        &self,
+        ctx: &Context<'build, 'arena>,
+        expr: &Expr<'build, 'arena>,
+        head: Term<'arena>,
+        args: &[Expr<'build, 'arena>],
+        arena: &mut Arena<'arena>,
+    ) -> Option<Expr<'build, 'arena>> {
+        match *head {
+            TermPayload::Decl(decl) if !decl.decl().is_opaque() => Some(apply(read(decl.get_term(arena)), args)),
+            TermPayload::Let(..) => view(head).map(|view| apply(view, args)),
+            TermPayload::Abs(..) if !args.is_empty() => view(head).map(|view| apply(view, args)),
+            _ if args.is_empty() => None,
+            _ => {
+                let term = self.to_kernel(ctx, expr, arena)?;
+                let whnf = term.whnf(arena);
+
+                (whnf != term).then(|| read(whnf))
+            },
+        }
+    }
+
+    /// Returns the kernel context corresponding to `ctx`.
+    ///
+    /// The variable bound at depth `n` is named after it (see [`output::name`]). Hypotheses whose
+    /// type cannot be given to the kernel yet are given a placeholder type.
+    fn kernel_context(&self, ctx: &Context<'build, 'arena>, arena: &mut Arena<'arena>) -> KernelContext<'arena> {
+        let mut kernel_ctx = KernelContext::new();
+
+        for (depth, entry) in ctx.iter().enumerate() {
+            let type_ = self.zonk_levels(&self.zonk(&entry.type_), arena);
+
+            let type_ = (!has_meta(&type_))
+                .then(|| unfold_lets(&ctx[..depth], &type_))
+                .and_then(|type_| elaborated(&type_, depth, arena))
+                .and_then(|type_| arena.build_in(&kernel_ctx, type_.as_closure(self.scope)).ok());
+
+            let pushed = type_.is_some_and(|type_| kernel_ctx.push(&output::name(depth), type_, arena).is_ok());
+
+            if !pushed {
+                if let Ok(prop) = arena.build(prop()) {
+                    kernel_ctx.push(&output::name(depth), prop, arena).unwrap_or_default();
+                }
+            }
+        }
+
+        kernel_ctx
+    }
+
+    /// Returns the kernel term corresponding to `expr`, living in the context `ctx`, if it has no
+    /// unsolved metavariable.
+    pub(super) fn to_kernel(
+        &self,
+        ctx: &Context<'build, 'arena>,
+        expr: &Expr<'build, 'arena>,
+        arena: &mut Arena<'arena>,
+    ) -> Option<Term<'arena>> {
+        let expr = self.zonk_levels(&self.zonk(expr), arena);
+
+        if has_meta(&expr) {
+            return None;
+        }
+
+        let expr = elaborated(&unfold_lets(ctx, &expr), ctx.len(), arena)?;
+        let kernel_ctx = self.kernel_context(ctx, arena);
+
+        arena.build_in(&kernel_ctx, expr.as_closure(self.scope)).ok()
+    }
+
+    /// Returns the type of `expr`, living in the context `ctx`, as inferred by the kernel, if
+    /// neither `expr` nor the types of its context have unsolved metavariables.
+    fn kernel_type(
+        &self,
+        ctx: &Context<'build, 'arena>,
+        expr: &Expr<'build, 'arena>,
+        arena: &mut Arena<'arena>,
+    ) -> Option<Expr<'build, 'arena>> {
+        if ctx.iter().any(|entry| has_meta(&self.zonk(&entry.type_))) {
+            return None;
+        }
+
+        let term = self.to_kernel(ctx, expr, arena)?;
+        let kernel_ctx = self.kernel_context(ctx, arena);
+
+        term.infer_in(&kernel_ctx, arena).ok().map(read)
+    }
+
+    /// Checks that `lhs` and `rhs`, living in the context `ctx`, can be unified.
+    ///
+    /// # Errors
+    /// If they cannot, yields an error located at `location`.
+    pub(super) fn constrain(
+        &mut self,
+        ctx: &mut Context<'build, 'arena>,
+        lhs: &Expr<'build, 'arena>,
+        rhs: &Expr<'build, 'arena>,
+        location: Location,
+        arena: &mut Arena<'arena>,
+    ) -> Result<'build, 'arena, ()> {
+        self.location = location;
+
+        if self.unify(ctx, lhs, rhs, arena) {
+            Ok(())
+        } else {
+            Err(Error::Elaboration(location, ErrorKind::TypeMismatch(self.show(ctx, lhs, arena), self.show(ctx, rhs, arena))))
+        }
+    }
+
+    /// Tries to unify `lhs` and `rhs`, living in the context `ctx`, solving metavariables in the
+    /// process.
+    pub(super) fn unify(
+        &mut self,
+        ctx: &mut Context<'build, 'arena>,
+        lhs: &Expr<'build, 'arena>,
+        rhs: &Expr<'build, 'arena>,
+        arena: &mut Arena<'arena>,
+    ) -> bool {
+        let lhs = expose(self.zonk_levels(&self.zonk(lhs), arena));
+        let rhs = expose(self.zonk_levels(&self.zonk(rhs), arena));
+
+        if lhs == rhs {
+            return true;
+        }
+
+        if !has_meta(&lhs) && !has_meta(&rhs) {
+            let def_eq = self.is_def_eq(ctx, &lhs, &rhs, arena);
+
+            // the kernel sees unsolved universe metavariables as universe variables, so that
+            // it cannot solve them
+            if def_eq || !self.has_unsolved_levels() {
+                return def_eq;
+            }
+        }
+
+        if let Some(solved) = self.solve(ctx, &lhs, &rhs, arena).or_else(|| self.solve(ctx, &rhs, &lhs, arena)) {
+            return solved;
+        }
+
+        if self.is_flex(&lhs) || self.is_flex(&rhs) {
+            if self.postponing {
+                self.postponed.push(Constraint {
+                    context: ctx.clone(),
+                    lhs,
+                    rhs,
+                    location: self.location,
+                });
+            }
+
+            return self.postponing;
+        }
+
+        match (&lhs.payload, &rhs.payload) {
+            (&Abs(_, ref lhs_type, ref lhs_body), &Abs(_, ref rhs_type, ref rhs_body))
+            | (&Prod(_, ref lhs_type, ref lhs_body), &Prod(_, ref rhs_type, ref rhs_body)) => {
+                return self.unify(ctx, lhs_type, rhs_type, arena) && self.unify_under(ctx, lhs_type, lhs_body, rhs_body, arena);
+            },
+            _ => {},
+        }
+
+        let (lhs_head, lhs_args) = spine(&lhs);
+        let (rhs_head, rhs_args) = spine(&rhs);
+
+        if lhs_args.len() == rhs_args.len() && (!lhs_args.is_empty() || matches!(lhs_head.payload, Atom(_))) {
+            let snapshot = self.snapshot();
+
+            if self.unify_heads(&lhs_head, &rhs_head, arena)
+                && lhs_args.iter().zip(&rhs_args).all(|(lhs, rhs)| self.unify(ctx, lhs, rhs, arena))
+            {
+                return true;
+            }
+
+            self.restore(snapshot);
+        }
+
+        let lhs_whnf = self.whnf(ctx, &lhs, arena);
+        let rhs_whnf = self.whnf(ctx, &rhs, arena);
+
+        if lhs_whnf != lhs || rhs_whnf != rhs {
+            return self.unify(ctx, &lhs_whnf, &rhs_whnf, arena);
+        }
+
+        match (&lhs.payload, &rhs.payload) {
+            (&Abs(_, ref arg_type, ref body), _) => {
+                let other = new(App(shift(&rhs, 1, 0), new(Var(1))));
+                self.unify_under(ctx, arg_type, body, &other, arena)
+            },
+            (_, &Abs(_, ref arg_type, ref body)) => {
+                let other = new(App(shift(&lhs, 1, 0), new(Var(1))));
+                self.unify_under(ctx, arg_type, &other, body, arena)
+            },
+            _ => false,
+        }
+    }
+
+    /// Tries to unify `lhs` and `rhs`, living in the context `ctx`, leaving the metavariables as
+    /// they were if they cannot be unified.
+    pub(super) fn try_unify(
+        &mut self,
+        ctx: &mut Context<'build, 'arena>,
+        lhs: &Expr<'build, 'arena>,
+        rhs: &Expr<'build, 'arena>,
+        arena: &mut Arena<'arena>,
+    ) -> bool {
+        let snapshot = self.snapshot();
+        let unified = self.unify(ctx, lhs, rhs, arena);
+
+        if !unified {
+            self.restore(snapshot);
+        }
+
+        unified
+    }
+
+    /// Returns the current state of the metavariables.
+    pub(super) fn snapshot(&self) -> Snapshot<'build, 'arena> {
+        Snapshot {
+            solutions: self.metas.iter().map(|meta| meta.solution.clone()).collect(),
+            postponed: self.postponed.len(),
+            holes: self.holes.len(),
+            level_metas: self.level_metas.clone(),
+            level_constraints: self.level_constraints.len(),
+        }
+    }
+
+    /// Restores the state `snapshot` of the metavariables, forgetting about the ones created
+    /// since.
+    pub(super) fn restore(&mut self, snapshot: Snapshot<'build, 'arena>) {
+        self.metas.truncate(snapshot.solutions.len());
+
+        for (meta, solution) in self.metas.iter_mut().zip(snapshot.solutions) {
+            meta.solution = solution;
+        }
+
+        self.postponed.truncate(snapshot.postponed);
+        self.holes.truncate(snapshot.holes);
+        self.level_metas = snapshot.level_metas;
+        self.level_constraints.truncate(snapshot.level_constraints);
+    }
+
+    /// Tries to unify the heads `lhs` and `rhs` of two applications, which are not unfolded.
+    fn unify_heads(&mut self, lhs: &Expr<'build, 'arena>, rhs: &Expr<'build, 'arena>, arena: &mut Arena<'arena>) -> bool {
+        match (&lhs.payload, &rhs.payload) {
+            (&Atom(lhs), &Atom(rhs)) => self.unify_atoms(lhs, rhs, arena),
+            _ => lhs == rhs,
+        }
+    }
+
+    /// Tries to unify `lhs` and `rhs`, living in the context `ctx` extended with an hypothesis of
+    /// type `arg_type`.
+    fn unify_under(
+        &mut self,
+        ctx: &mut Context<'build, 'arena>,
+        arg_type: &Expr<'build, 'arena>,
+        lhs: &Expr<'build, 'arena>,
+        rhs: &Expr<'build, 'arena>,
+        arena: &mut Arena<'arena>,
+    ) -> bool {
+        ctx.push(Entry {
+            name: Binder::ANONYMOUS.name,
+            type_: Rc::clone(arg_type),
+            value: None,
+        });
+
+        let unified = self.unify(ctx, lhs, rhs, arena);
+
+        ctx.pop();
+        unified
+    }
+
+    /// Asks the kernel whether `lhs` and `rhs`, living in the context `ctx` and without
+    /// metavariables, are definitionally equal.
+    fn is_def_eq(
+        &self,
+        ctx: &Context<'build, 'arena>,
+        lhs: &Expr<'build, 'arena>,
+        rhs: &Expr<'build, 'arena>,
+        arena: &mut Arena<'arena>,
+    ) -> bool {
+        let (Some(lhs), Some(rhs)) = (self.to_kernel(ctx, lhs, arena), self.to_kernel(ctx, rhs, arena)) else {
+            return false;
+        };
+
+        lhs.is_def_eq(rhs, arena).is_ok()
+    }
+
+    /// Tries to solve the problem `flex = other`, where `flex` is a metavariable applied to
+    /// distinct variables.
+    ///
+    /// Returns `None` if the problem is out of this fragment, and whether it could be solved
+    /// otherwise.
+    fn solve(
+        &mut self,
+        ctx: &mut Context<'build, 'arena>,
+        flex: &Expr<'build, 'arena>,
+        other: &Expr<'build, 'arena>,
+        arena: &mut Arena<'arena>,
+    ) -> Option<bool> {
+        let (head, args) = spine(flex);
+
+        let Payload::Meta(id) = head.payload else {
+            return None;
+        };
+
+        let arity = self.metas[id].context.len();
+
+        let vars = args
+            .iter()
+            .map(|arg| match arg.payload {
+                Var(index) => Some(index),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        if vars.len() < arity || vars.iter().enumerate().any(|(position, var)| vars[..position].contains(var)) {
+            return None;
+        }
+
+        if any(other, &|payload| *payload == Payload::Meta(id)) {
+            return Some(false);
+        }
+
+        let Some(mut solution) = rename(other, &vars, 0).or_else(|| rename(&unfold_lets(ctx, other), &vars, 0)) else {
+            return Some(false);
+        };
+
+        for position in (arity..vars.len()).rev() {
+            let Some(arg_type) = rename(&var_type(ctx, vars[position]), &vars[..position], 0) else {
+                return Some(false);
+            };
+
+            solution = new(Abs(Binder::ANONYMOUS, arg_type, solution));
+        }
+
+        self.metas[id].solution = Some(solution);
+
+        // unification never looks at the type of the solution, which may be all that determines
+        // the universe metavariables of the type of the metavariable
+        if vars.len() == arity && self.has_unsolved_levels() {
+            if let Some(type_) = self.kernel_type(ctx, other, arena) {
+                let expected = substitute(&self.metas[id].type_, &args, 0);
+
+                return Some(self.unify(ctx, &expected, &type_, arena));
+            }
+        }
+
+        Some(true)
+    }
+
+    /// Solves the postponed unification problems, and checks that all metavariables are solved,
+    /// universe metavariables included.
+    ///
+    /// # Errors
+    /// If a problem has no solution, or if a metavariable is left unsolved, yields an error
+    /// located where it was introduced.
+    pub(super) fn finish(&mut self, arena: &mut Arena<'arena>) -> Result<'build, 'arena, ()> {
+        while !self.postponed.is_empty() || !self.level_constraints.is_empty() {
+            let solved = self.solved();
+            let constraints = mem::take(&mut self.postponed);
+            let level_constraints = mem::take(&mut self.level_constraints);
+            let count = constraints.len() + level_constraints.len();
+
+            for Constraint {
+                mut context,
+                lhs,
+                rhs,
+                location,
+            } in constraints
+            {
+                self.constrain(&mut context, &lhs, &rhs, location, arena)?;
+            }
+
+            for LevelConstraint { lhs, rhs, location } in level_constraints {
+                self.constrain_levels(lhs, rhs, location, arena)?;
+            }
+
+            // without progress, the remaining problems are considered unsolvable
+            if self.postponed.len() + self.level_constraints.len() >= count && self.solved() == solved {
+                self.postponing = false;
+            }
+        }
+
+        self.postponing = true;
+
+        let goals = self.goals(arena);
+
+        if let Some(goal) = goals.first() {
+            return Err(Error::Elaboration(goal.location, ErrorKind::Holes(goals)));
+        }
+
+        if let Some(meta) = self.metas.iter().find(|meta| meta.solution.is_none()) {
+            return Err(Error::Elaboration(meta.location, ErrorKind::UnsolvedMeta(self.show(&meta.context, &meta.type_, arena))));
+        }
+
+        match self.level_metas.iter().find(|meta| meta.solution.is_none()) {
+            Some(meta) => Err(Error::Elaboration(meta.location, ErrorKind::UnsolvedLevel)),
+            None => Ok(()),
+        }
+    }
+}
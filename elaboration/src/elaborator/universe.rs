@@ -0,0 +1,221 @@
+//! Universe metavariables.
+//!
+//! Equations `?u + n = l` between universe levels are solved by assigning `l - n` to `?u`, when
+//! this difference can be computed syntactically. The other equations are postponed until their
+//! metavariables are solved, and are then decided by [`Level::is_eq`].
+
+use alloc::rc::Rc;
+
+use kernel::memory::arena::Arena;
+use kernel::memory::level::Level;
+use kernel::memory::level::Payload::{Add, IMax, Max, Var, Zero};
+use kernel::memory::term::{Payload as TermPayload, Term};
+
+use super::expr::{map_atoms, Expr};
+use super::{Elaborator, Error, ErrorKind, LevelConstraint, LevelMeta, Result};
+use crate::location::Location;
+
+/// Splits `level` into a level and a constant offset, such that `level = base + offset`.
+fn split(level: Level) -> (Level, u32) {
+    match *level {
+        Add(base, offset) => (base, offset),
+        _ => (level, 0),
+    }
+}
+
+/// Indicates whether the predicate `pred` holds for some variable of `level`.
+fn any_var<P>(level: Level, pred: &P) -> bool
+where
+    P: Fn(usize) -> bool,
+{
+    match *level {
+        Zero => false,
+        Add(level, _) => any_var(level, pred),
+        Max(lhs, rhs) | IMax(lhs, rhs) => any_var(lhs, pred) || any_var(rhs, pred),
+        Var(var) => pred(var),
+    }
+}
+
+impl<'build, 'env, 'arena> Elaborator<'build, 'env, 'arena> {
+    /// Creates `count` new universe metavariables, and returns the level variable representing
+    /// the first one.
+    pub(super) fn new_levels(&mut self, count: usize, location: Location) -> usize {
+        let first = self.lvl_env.len() + self.level_metas.len();
+
+        self.level_metas.resize(self.level_metas.len() + count, LevelMeta {
+            location,
+            solution: None,
+        });
+        first
+    }
+
+    /// Returns the levels substituted to the level variables: universe variables stand for
+    /// themselves, and universe metavariables for their solution, if any.
+    fn univs(&self, arena: &mut Arena<'arena>) -> Vec<Level<'arena>> {
+        let vars = self.lvl_env.len();
+
+        (0..vars + self.level_metas.len())
+            .map(|var| {
+                var.checked_sub(vars)
+                    .and_then(|id| self.level_metas[id].solution)
+                    .unwrap_or_else(|| Level::var(var, arena))
+            })
+            .collect()
+    }
+
+    /// Returns `level`, where all the solved universe metavariables are replaced by their solution.
+    fn zonk_level(&self, level: Level<'arena>, arena: &mut Arena<'arena>) -> Level<'arena> {
+        level.substitute(&self.univs(arena), arena)
+    }
+
+    /// Returns `expr`, where all the solved universe metavariables are replaced by their solution.
+    pub(super) fn zonk_levels(&self, expr: &Expr<'build, 'arena>, arena: &mut Arena<'arena>) -> Expr<'build, 'arena> {
+        if self.level_metas.iter().all(|meta| meta.solution.is_none()) {
+            return Rc::clone(expr);
+        }
+
+        let univs = self.univs(arena);
+        map_atoms(expr, &mut |term| term.substitute_univs(&univs, arena))
+    }
+
+    /// Indicates whether some universe metavariable appears in `level`.
+    fn has_level_meta(&self, level: Level) -> bool {
+        any_var(level, &|var| var >= self.lvl_env.len())
+    }
+
+    /// Indicates whether some universe metavariable is unsolved.
+    pub(super) fn has_unsolved_levels(&self) -> bool {
+        self.level_metas.iter().any(|meta| meta.solution.is_none())
+    }
+
+    /// Tries to unify the atoms `lhs` and `rhs`, which may only differ by their universe levels.
+    pub(super) fn unify_atoms(&mut self, lhs: Term<'arena>, rhs: Term<'arena>, arena: &mut Arena<'arena>) -> bool {
+        match (&*lhs, &*rhs) {
+            (&TermPayload::Sort(lhs), &TermPayload::Sort(rhs)) => self.unify_levels(lhs, rhs, arena),
+            (&TermPayload::Decl(lhs), &TermPayload::Decl(rhs)) if lhs.decl() == rhs.decl() => lhs
+                .params()
+                .iter()
+                .zip(rhs.params())
+                .all(|(&lhs, &rhs)| self.unify_levels(lhs, rhs, arena)),
+            (&TermPayload::Axiom(lhs, lhs_levels), &TermPayload::Axiom(rhs, rhs_levels)) if lhs == rhs => {
+                lhs_levels.iter().zip(rhs_levels).all(|(&lhs, &rhs)| self.unify_levels(lhs, rhs, arena))
+            },
+            _ => lhs == rhs,
+        }
+    }
+
+    /// Tries to make the levels `lhs` and `rhs` equal, solving universe metavariables in the
+    /// process.
+    fn unify_levels(&mut self, lhs: Level<'arena>, rhs: Level<'arena>, arena: &mut Arena<'arena>) -> bool {
+        let lhs = self.zonk_level(lhs, arena);
+        let rhs = self.zonk_level(rhs, arena);
+
+        if lhs == rhs {
+            return true;
+        }
+
+        if let Some(solved) = self.solve_level(lhs, rhs, arena).or_else(|| self.solve_level(rhs, lhs, arena)) {
+            return solved;
+        }
+
+        if !self.has_level_meta(lhs) && !self.has_level_meta(rhs) {
+            return lhs.is_eq(rhs, arena);
+        }
+
+        if self.postponing {
+            self.level_constraints.push(LevelConstraint {
+                lhs,
+                rhs,
+                location: self.location,
+            });
+        }
+
+        self.postponing
+    }
+
+    /// Tries to solve the equation `flex = other`, where `flex` is an unsolved universe
+    /// metavariable, plus some constant.
+    ///
+    /// Returns `None` if the equation cannot be solved this way, and whether it could be solved
+    /// otherwise.
+    fn solve_level(&mut self, flex: Level<'arena>, other: Level<'arena>, arena: &mut Arena<'arena>) -> Option<bool> {
+        let (base, offset) = split(flex);
+
+        let Var(var) = *base else {
+            return None;
+        };
+
+        let id = var.checked_sub(self.lvl_env.len())?;
+        let (other_base, other_offset) = split(other);
+
+        if any_var(other, &|other| other == var) {
+            return (other_base == base).then_some(false);
+        }
+
+        let Some(difference) = other_offset.checked_sub(offset) else {
+            // `other` must be at least `offset` for the equation to have a solution
+            return (!self.has_level_meta(other) && !Level::from(offset, arena).geq(other, 0, arena)).then_some(false);
+        };
+
+        let solution = other_base.add(difference, arena);
+        self.level_metas[id].solution = Some(solution);
+
+        let univs = self.univs(arena);
+
+        for meta in &mut self.level_metas {
+            meta.solution = meta.solution.map(|solution| solution.substitute(&univs, arena));
+        }
+
+        Some(true)
+    }
+
+    /// Checks that the levels `lhs` and `rhs` can be made equal.
+    ///
+    /// # Errors
+    /// If they cannot, yields an error located at `location`.
+    pub(super) fn constrain_levels(
+        &mut self,
+        lhs: Level<'arena>,
+        rhs: Level<'arena>,
+        location: Location,
+        arena: &mut Arena<'arena>,
+    ) -> Result<'build, 'arena, ()> {
+        self.location = location;
+
+        if self.unify_levels(lhs, rhs, arena) {
+            return Ok(());
+        }
+
+        let lhs = self.zonk_level(lhs, arena);
+        let rhs = self.zonk_level(rhs, arena);
+
+        if self.has_level_meta(lhs) || self.has_level_meta(rhs) {
+            Err(Error::Elaboration(location, ErrorKind::UnsolvedLevel))
+        } else {
+            Err(Error::Elaboration(location, ErrorKind::InconsistentLevels(self.show_level(lhs), self.show_level(rhs))))
+        }
+    }
+
+    /// Returns a textual representation of `level`, where universe variables are designated by
+    /// their name.
+    fn show_level(&self, level: Level) -> String {
+        if let Some(n) = level.to_numeral() {
+            return n.to_string();
+        }
+
+        match *level {
+            Zero => String::from("0"),
+            Add(level, n) => format!("({} + {n})", self.show_level(level)),
+            Max(lhs, rhs) => format!("(max {} {})", self.show_level(lhs), self.show_level(rhs)),
+            IMax(lhs, rhs) => format!("(imax {} {})", self.show_level(lhs), self.show_level(rhs)),
+            Var(var) => match var.checked_sub(self.lvl_env.len()) {
+                Some(id) => format!("?u{id}"),
+                None => self
+                    .lvl_env
+                    .iter()
+                    .find_map(|(name, &index)| (index == var).then(|| (*name).to_owned()))
+                    .unwrap_or_default(),
+            },
+        }
+    }
+}
@@ -57,5 +57,9 @@
 )]
 #![feature(type_alias_impl_trait)]
 
+extern crate alloc;
+
 pub mod builder;
+pub mod elaborator;
 pub mod location;
+pub mod scope;
@@ -0,0 +1,164 @@
+//! Scopes, in which names are declared and looked up.
+//!
+//! The kernel only knows about *full names*, like `Foo.Bar.x`. Names declared in a namespace are
+//! prefixed by the full name of the namespace, while names used in a term are resolved into the
+//! full names they may refer to, according to the namespaces entered and opened so far.
+
+use std::collections::HashSet;
+
+use kernel::memory::arena::Arena;
+
+/// The namespaces entered and opened so far.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Scope {
+    /// The namespaces currently entered, from the outermost one.
+    namespaces: Vec<Namespace>,
+
+    /// The full names of the namespaces opened so far.
+    opened: Vec<String>,
+
+    /// The full names of the namespaces entered so far.
+    entered: HashSet<String>,
+}
+
+/// A namespace entered with [`Scope::enter`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Namespace {
+    /// The name under which the namespace has been entered.
+    name: String,
+
+    /// The full name of the namespace, including the enclosing namespaces.
+    full_name: String,
+
+    /// The number of namespaces opened before entering this one, which are the only ones still
+    /// opened once it is exited.
+    opened: usize,
+}
+
+impl Scope {
+    /// Creates a scope where no namespace is entered.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of namespaces currently entered.
+    #[inline]
+    #[must_use]
+    pub const fn depth(&self) -> usize {
+        self.namespaces.len()
+    }
+
+    /// Returns the full name of `name` in the current namespace.
+    #[inline]
+    #[must_use]
+    pub fn qualify(&self, name: &str) -> String {
+        self.namespaces
+            .last()
+            .map_or_else(|| name.to_owned(), |namespace| format!("{}.{name}", namespace.full_name))
+    }
+
+    /// Returns the full names bound in `arena` that `name` may refer to.
+    ///
+    /// The enclosing namespaces are looked up first, from the innermost one, the first name found
+    /// there shadowing the others. Otherwise, `name` may refer to a name bound outside of any
+    /// namespace, or in any opened namespace: it is ambiguous if several such names exist.
+    #[inline]
+    #[must_use]
+    pub fn resolve(&self, name: &str, arena: &Arena) -> Vec<String> {
+        self.resolve_with(name, |name| arena.is_bound(name))
+    }
+
+    /// Returns the full names `name` may refer to, as in [`Scope::resolve`], a full name being
+    /// bound whenever `is_bound` holds.
+    pub(crate) fn resolve_with<F>(&self, name: &str, is_bound: F) -> Vec<String>
+    where
+        F: Fn(&str) -> bool,
+    {
+        let enclosing = self
+            .namespaces
+            .iter()
+            .rev()
+            .map(|namespace| format!("{}.{name}", namespace.full_name))
+            .find(|name| is_bound(name));
+
+        if let Some(name) = enclosing {
+            return vec![name];
+        }
+
+        let mut names: Vec<_> = is_bound(name).then(|| name.to_owned()).into_iter().collect();
+
+        for opened in &self.opened {
+            let name = format!("{opened}.{name}");
+
+            if is_bound(&name) && !names.contains(&name) {
+                names.push(name);
+            }
+        }
+
+        names
+    }
+
+    /// Enters the namespace `name`, nested in the current one.
+    #[inline]
+    pub fn enter(&mut self, name: &str) {
+        let full_name = self.qualify(name);
+        self.entered.insert(full_name.clone());
+
+        self.namespaces.push(Namespace {
+            name: name.to_owned(),
+            full_name,
+            opened: self.opened.len(),
+        });
+    }
+
+    /// Exits the current namespace, closing the namespaces opened in it.
+    ///
+    /// Returns `false`, without exiting anything, if the current namespace has not been entered
+    /// under the name `name`.
+    #[inline]
+    pub fn exit(&mut self, name: &str) -> bool {
+        match self.namespaces.last() {
+            Some(namespace) if namespace.name == name => {
+                self.opened.truncate(namespace.opened);
+                self.namespaces.pop();
+                true
+            },
+            _ => false,
+        }
+    }
+
+    /// Opens the namespace `name`, whose names can then be used without their prefix, until the
+    /// current namespace is exited.
+    ///
+    /// Like other names, `name` is looked up in the enclosing namespaces first, from the innermost
+    /// one. A namespace exists if it has been entered, or if some name of `arena` belongs to it.
+    /// Returns `false`, without opening anything, if no such namespace exists.
+    #[inline]
+    pub fn open(&mut self, name: &str, arena: &Arena) -> bool {
+        let names = arena.names();
+        let exists = |namespace: &str| {
+            self.entered.contains(namespace)
+                || names.iter().any(|name| name.strip_prefix(namespace).is_some_and(|name| name.starts_with('.')))
+        };
+
+        let full_name = self
+            .namespaces
+            .iter()
+            .rev()
+            .map(|namespace| format!("{}.{name}", namespace.full_name))
+            .chain(core::iter::once(name.to_owned()))
+            .find(|full_name| exists(full_name));
+
+        let Some(full_name) = full_name else {
+            return false;
+        };
+
+        if !self.opened.contains(&full_name) {
+            self.opened.push(full_name);
+        }
+
+        true
+    }
+}
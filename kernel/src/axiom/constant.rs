@@ -0,0 +1,43 @@
+//! Constants, standing for arbitrary inhabitants of their type.
+//!
+//! Unlike the other axioms, constants are not hardcoded: they are generated for the
+//! [axioms](crate::memory::arena::Arena::declare_axiom) declared by the user, and for the
+//! [variables](crate::memory::arena::Arena::declare_variable) of sections, over which the terms
+//! using them are later [abstracted](crate::memory::term::Term::abs_constant).
+
+use derive_more::Display;
+
+use crate::memory::term::Term;
+
+/// A constant, standing for an arbitrary inhabitant of its type.
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq, Hash)]
+#[display(fmt = "{name}")]
+pub struct Constant<'arena> {
+    /// The name of the constant.
+    name: &'arena str,
+
+    /// The type of the constant.
+    type_: Term<'arena>,
+
+    /// The number of constants created before this one, which tells apart constants with the
+    /// same name and type.
+    index: usize,
+}
+
+impl<'arena> Constant<'arena> {
+    /// Creates a new constant named `name` of type `type_`, which must be the `index`-th constant
+    /// created in its arena.
+    pub(crate) const fn new(name: &'arena str, type_: Term<'arena>, index: usize) -> Self {
+        Self { name, type_, index }
+    }
+
+    /// Returns the name of the constant.
+    pub(crate) const fn name(self) -> &'arena str {
+        self.name
+    }
+
+    /// Returns the type of the constant.
+    pub(crate) const fn get_type(self) -> Term<'arena> {
+        self.type_
+    }
+}
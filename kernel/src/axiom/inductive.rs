@@ -0,0 +1,440 @@
+//! Axioms, typing and reduction rules generated from user-declared inductive types.
+//!
+//! Every [inductive type](crate::memory::inductive::Inductive) gives rise to three families of
+//! axioms: the type former itself, its constructors, and its recursor. For an inductive type `I`
+//! with parameters `(p: P)` and indices `(i: J)`, the recursor has the type
+//! ```text
+//! I_rec : (p: P) -> (motive: (i: J) -> I p i -> Sort v) -> (minor premises) -> (i: J) -> (t: I p i) -> motive i t
+//! ```
+//! where, for every constructor `c : (p: P) -> (f: F) -> I p e`, the minor premise is
+//! ```text
+//! (f: F) -> (induction hypotheses over the recursive fields of f) -> motive e (c p f)
+//! ```
+//! The recursor computes through the ι-reduction rule, which replaces the recursor applied to a
+//! constructor with the corresponding minor premise, applied to the fields of the constructor and
+//! to the recursive calls on its recursive fields.
+
+use core::fmt;
+
+use crate::memory::arena::Arena;
+use crate::memory::inductive;
+use crate::memory::level::Level;
+use crate::memory::term::Payload::{App, Axiom, Prod};
+use crate::memory::term::Term;
+
+/// Axioms generated from an inductive type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Inductive<'arena> {
+    /// The inductive type itself.
+    Type(inductive::Inductive<'arena>),
+
+    /// The constructor of the given index.
+    Constructor(inductive::Inductive<'arena>, usize),
+
+    /// The recursor over the inductive type.
+    Recursor(inductive::Inductive<'arena>),
+}
+
+impl<'arena> fmt::Display for Inductive<'arena> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Self::Type(inductive) => write!(f, "{inductive}"),
+            Self::Constructor(inductive, index) => write!(f, "{}", inductive.constructor(index).name()),
+            Self::Recursor(inductive) => write!(f, "{inductive}_rec"),
+        }
+    }
+}
+
+/// A typed local context, used to build terms under binders.
+///
+/// Binders are stored from the outermost to the innermost one. The type of each binder lives in
+/// the context formed by the binders preceding it.
+pub(crate) struct Telescope<'arena>(Vec<Term<'arena>>);
+
+impl<'arena> Telescope<'arena> {
+    /// Creates an empty telescope.
+    pub(crate) const fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Returns the number of binders of the telescope.
+    pub(crate) fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Adds a binder of type `type_` to the telescope.
+    pub(crate) fn push(&mut self, type_: Term<'arena>) {
+        self.0.push(type_);
+    }
+
+    /// Returns the type of the binder at position `position`, living in the current context.
+    pub(crate) fn type_at(&self, position: usize, arena: &mut Arena<'arena>) -> Term<'arena> {
+        let type_ = *self.0.get(position).unwrap_or_else(|| unreachable!("position out of the telescope"));
+        self.lift(type_, position, arena)
+    }
+
+    /// Returns the variable bound at position `position`, living in the current context.
+    pub(crate) fn var(&self, position: usize, arena: &mut Arena<'arena>) -> Term<'arena> {
+        let type_ = self.type_at(position, arena);
+        Term::var((self.len() - position).into(), type_, arena)
+    }
+
+    /// Weakens a term living in the context formed by the first `len` binders, so that it lives
+    /// in the current context.
+    pub(crate) fn lift(&self, term: Term<'arena>, len: usize, arena: &mut Arena<'arena>) -> Term<'arena> {
+        term.shift(self.len() - len, 0, arena)
+    }
+
+    /// Applies `term` to the variables bound at the given `positions`.
+    pub(crate) fn apply<I>(&self, term: Term<'arena>, positions: I, arena: &mut Arena<'arena>) -> Term<'arena>
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        positions.into_iter().fold(term, |term, position| {
+            let var = self.var(position, arena);
+            term.app(var, arena)
+        })
+    }
+
+    /// Removes the binders from position `from`, and abstracts `body` over them with products.
+    pub(crate) fn close_prods(&mut self, from: usize, body: Term<'arena>, arena: &mut Arena<'arena>) -> Term<'arena> {
+        self.0.drain(from..).rev().fold(body, |body, type_| type_.prod(body, arena))
+    }
+
+    /// Removes the binders from position `from`, and abstracts `body` over them with
+    /// lambda-abstractions.
+    pub(crate) fn close_abs(&mut self, from: usize, body: Term<'arena>, arena: &mut Arena<'arena>) -> Term<'arena> {
+        self.0.drain(from..).rev().fold(body, |body, type_| type_.abs(body, arena))
+    }
+}
+
+impl<'arena> Term<'arena> {
+    /// Returns the weak-head normal form of a term, where declarations in head position are also
+    /// unfolded.
+    pub(crate) fn whnf_unfolded(self, arena: &mut Arena<'arena>) -> Self {
+        let mut term = self;
+
+        loop {
+            let next = term.unfold(arena).whnf(arena);
+
+            if next == term {
+                return term;
+            }
+
+            term = next;
+        }
+    }
+
+    /// Decomposes an application into its head and its arguments, in order.
+    ///
+    /// Function parts are put in weak-head normal form on the way.
+    pub(crate) fn spine(self, arena: &mut Arena<'arena>) -> (Self, Vec<Self>) {
+        let mut args = Vec::new();
+        let mut term = self;
+
+        while let App(f, arg) = *term {
+            args.push(arg);
+            term = f.whnf_unfolded(arena);
+        }
+
+        args.reverse();
+        (term, args)
+    }
+
+    /// Decomposes a product `(x1: A1) -> ... -> (xn: An) -> B` into `[A1, ..., An]` and `B`.
+    ///
+    /// Each type is put in weak-head normal form (with unfolding) before being inspected.
+    pub(crate) fn telescope(self, arena: &mut Arena<'arena>) -> (Vec<Self>, Self) {
+        let mut binders = Vec::new();
+        let mut term = self.whnf_unfolded(arena);
+
+        while let Prod(arg_type, body) = *term {
+            binders.push(arg_type);
+            term = body.whnf_unfolded(arena);
+        }
+
+        (binders, term)
+    }
+
+    /// Indicates whether `self` is the inductive type `inductive`, applied to some arguments.
+    pub(crate) fn is_instance_of(self, inductive: inductive::Inductive<'arena>, arena: &mut Arena<'arena>) -> bool {
+        let (head, _) = self.spine(arena);
+
+        matches!(*head, Axiom(super::Axiom::Inductive(Inductive::Type(ind)), _) if ind == inductive)
+    }
+}
+
+impl<'arena> inductive::Inductive<'arena> {
+    /// Returns the constructor of the given index.
+    pub(crate) fn constructor(self, index: usize) -> inductive::Constructor<'arena> {
+        *self.constructors().get(index).unwrap_or_else(|| unreachable!("constructor out of bounds"))
+    }
+
+    /// Returns the type former of the inductive type, instantiated with its own universe variables.
+    pub(crate) fn type_former(self, arena: &mut Arena<'arena>) -> Term<'arena> {
+        let levels = self.identity_levels(arena);
+        Term::axiom(super::Axiom::Inductive(Inductive::Type(self)), &levels, arena)
+    }
+
+    /// Returns the number of indices of the inductive type.
+    pub(crate) fn indices(self, arena: &mut Arena<'arena>) -> usize {
+        self.arity().telescope(arena).0.len().saturating_sub(self.params())
+    }
+}
+
+impl<'arena> Inductive<'arena> {
+    /// Returns the type of a given axiom.
+    ///
+    /// Because of memoisation, this is only computed once per axiom.
+    #[inline]
+    pub fn get_type(self, arena: &mut Arena<'arena>) -> Term<'arena> {
+        match self {
+            Self::Type(inductive) => inductive.arity(),
+            Self::Constructor(inductive, index) => {
+                let type_former = inductive.type_former(arena);
+                inductive.constructor(index).get_type().substitute(type_former, 1, arena)
+            },
+            Self::Recursor(inductive) => inductive.get_recursor_type_or_init(|| Self::type_recursor(inductive, arena)),
+        }
+    }
+
+    /// Type of the recursor over the inductive type.
+    fn type_recursor(inductive: inductive::Inductive<'arena>, arena: &mut Arena<'arena>) -> Term<'arena> {
+        let params = inductive.params();
+        let type_former = inductive.type_former(arena);
+
+        let motive_sort = if inductive.is_large_eliminating() {
+            Term::sort(Level::var(inductive.univs(), arena), arena)
+        } else {
+            Term::prop(arena)
+        };
+
+        let (arity, _) = inductive.arity().telescope(arena);
+        let (param_types, index_types) = arity.split_at(params);
+
+        let mut ctx = Telescope::new();
+        param_types.iter().for_each(|type_| ctx.push(*type_));
+
+        // motive : (i: J) -> I p i -> Sort v
+        index_types.iter().for_each(|type_| ctx.push(*type_));
+        let major = ctx.apply(type_former, 0..ctx.len(), arena);
+        ctx.push(major);
+        let motive = ctx.close_prods(params, motive_sort, arena);
+        ctx.push(motive);
+
+        // minor premises
+        (0..inductive.constructors().len()).for_each(|index| {
+            let minor = Self::type_minor_premise(inductive, index, &mut ctx, arena);
+            ctx.push(minor);
+        });
+
+        // (i: J) -> (t: I p i) -> motive i t
+        let start = ctx.len();
+        let gap = start - params;
+        index_types.iter().enumerate().for_each(|(depth, type_)| {
+            let type_ = type_.shift(gap, depth, arena);
+            ctx.push(type_);
+        });
+
+        let major = ctx.apply(type_former, (0..params).chain(start..ctx.len()), arena);
+        ctx.push(major);
+
+        let motive = ctx.var(params, arena);
+        let conclusion = ctx.apply(motive, start..ctx.len(), arena);
+
+        ctx.close_prods(0, conclusion, arena)
+    }
+
+    /// Type of the minor premise associated to the constructor of index `index`, in a context
+    /// made of the parameters, the motive, and the previous minor premises.
+    fn type_minor_premise(
+        inductive: inductive::Inductive<'arena>,
+        index: usize,
+        ctx: &mut Telescope<'arena>,
+        arena: &mut Arena<'arena>,
+    ) -> Term<'arena> {
+        let params = inductive.params();
+        let motive_position = params;
+
+        let levels = inductive.identity_levels(arena);
+        let constructor = Term::axiom(super::Axiom::Inductive(Self::Constructor(inductive, index)), &levels, arena);
+
+        // skip the parameters, which are already bound in the context
+        let mut type_ = Self::Constructor(inductive, index).get_type(arena);
+        for _ in 0..params {
+            let Prod(_, body) = *type_.whnf_unfolded(arena) else {
+                unreachable!("constructor types start with the parameters");
+            };
+            type_ = body;
+        }
+
+        let type_ = ctx.lift(type_, params, arena);
+        let fields_start = ctx.len();
+
+        let (fields, conclusion) = type_.telescope(arena);
+        fields.iter().for_each(|field| ctx.push(*field));
+
+        let fields_end = ctx.len();
+        let (_, conclusion_args) = conclusion.spine(arena);
+
+        // induction hypotheses
+        for position in fields_start..fields_end {
+            let field_type = ctx.type_at(position, arena);
+            let start = ctx.len();
+
+            let (args, conclusion) = field_type.telescope(arena);
+
+            if !conclusion.is_instance_of(inductive, arena) {
+                continue;
+            }
+
+            args.iter().for_each(|arg| ctx.push(*arg));
+
+            let (_, conclusion_args) = conclusion.spine(arena);
+            let motive = ctx.var(motive_position, arena);
+            let motive = conclusion_args.iter().skip(params).fold(motive, |motive, arg| motive.app(*arg, arena));
+
+            let field = ctx.var(position, arena);
+            let field = ctx.apply(field, start..ctx.len(), arena);
+
+            let hypothesis = motive.app(field, arena);
+            let hypothesis = ctx.close_prods(start, hypothesis, arena);
+
+            ctx.push(hypothesis);
+        }
+
+        // motive e (c p f)
+        let motive = ctx.var(motive_position, arena);
+        let motive = conclusion_args.into_iter().skip(params).fold(motive, |motive, arg| {
+            let arg = ctx.lift(arg, fields_end, arena);
+            motive.app(arg, arena)
+        });
+
+        let constructor = ctx.apply(constructor, (0..params).chain(fields_start..fields_end), arena);
+        let conclusion = motive.app(constructor, arena);
+
+        ctx.close_prods(fields_start, conclusion, arena)
+    }
+
+    /// Reduces a [`Term`] if it is a recursor applied to a constructor; returns `None` otherwise.
+    pub(crate) fn reduce(term: Term<'arena>, arena: &mut Arena<'arena>) -> Option<Term<'arena>> {
+        if !matches!(*term, App(..)) {
+            return None;
+        }
+
+        let (head, args) = term.spine(arena);
+
+        let Axiom(super::Axiom::Inductive(Self::Recursor(inductive)), levels) = *head else {
+            return None;
+        };
+
+        let params = inductive.params();
+        let minors = inductive.constructors().len();
+        let major_position = params + 1 + minors + inductive.indices(arena);
+
+        if args.len() != major_position + 1 {
+            return None;
+        }
+
+        let major = args.get(major_position).unwrap_or_else(|| unreachable!()).whnf_unfolded(arena);
+        let (constructor, constructor_args) = major.spine(arena);
+
+        let Axiom(super::Axiom::Inductive(Self::Constructor(ind, index)), _) = *constructor else {
+            return None;
+        };
+
+        if ind != inductive {
+            return None;
+        }
+
+        // instantiate the type of the constructor with the actual universes and parameters
+        let levels = levels.get(..inductive.univs()).unwrap_or_else(|| unreachable!());
+        let mut type_ = Self::Constructor(inductive, index).get_type(arena).substitute_univs(levels, arena);
+
+        for param in args.iter().take(params) {
+            let Prod(_, body) = *type_.whnf_unfolded(arena) else {
+                return None;
+            };
+            type_ = body.substitute(*param, 1, arena);
+        }
+
+        let (recursor_args, _) = args.split_at(major_position - inductive.indices(arena));
+        let mut hypotheses = Vec::new();
+
+        for field in constructor_args.iter().skip(params) {
+            let Prod(field_type, body) = *type_.whnf_unfolded(arena) else {
+                return None;
+            };
+
+            if let Some(hypothesis) = Self::induction_hypothesis(inductive, head, recursor_args, *field, field_type, arena) {
+                hypotheses.push(hypothesis);
+            }
+
+            type_ = body.substitute(*field, 1, arena);
+        }
+
+        if matches!(*type_.whnf_unfolded(arena), Prod(..)) {
+            return None;
+        }
+
+        let minor = *args.get(params + 1 + index).unwrap_or_else(|| unreachable!());
+        let fields = constructor_args.into_iter().skip(params);
+
+        Some(fields.chain(hypotheses).fold(minor, |minor, arg| minor.app(arg, arena)))
+    }
+
+    /// Indicates whether `type_` is an instance of an inductive type without indices, which has a
+    /// single constructor without any field.
+    pub(crate) fn is_unit_like(type_: Term<'arena>, arena: &mut Arena<'arena>) -> bool {
+        let (head, _) = type_.spine(arena);
+
+        let Axiom(super::Axiom::Inductive(Self::Type(inductive)), _) = *head else {
+            return false;
+        };
+
+        let &[constructor] = inductive.constructors() else {
+            return false;
+        };
+
+        inductive.indices(arena) == 0 && constructor.get_type().telescope(arena).0.len() == inductive.params()
+    }
+
+    /// Returns the recursive call on `field` when it is a recursive field of type `field_type`;
+    /// returns `None` otherwise.
+    ///
+    /// The recursive call is `fun (x: X) => I_rec p motive minors e (field x)` when the field
+    /// has type `(x: X) -> I p e`.
+    fn induction_hypothesis(
+        inductive: inductive::Inductive<'arena>,
+        recursor: Term<'arena>,
+        recursor_args: &[Term<'arena>],
+        field: Term<'arena>,
+        field_type: Term<'arena>,
+        arena: &mut Arena<'arena>,
+    ) -> Option<Term<'arena>> {
+        let (args, conclusion) = field_type.telescope(arena);
+
+        if !conclusion.is_instance_of(inductive, arena) {
+            return None;
+        }
+
+        let mut ctx = Telescope::new();
+        args.iter().for_each(|arg| ctx.push(*arg));
+
+        let (_, conclusion_args) = conclusion.spine(arena);
+        let indices = conclusion_args.into_iter().skip(inductive.params());
+
+        let call = recursor_args.iter().fold(recursor, |call, arg| {
+            let arg = ctx.lift(*arg, 0, arena);
+            call.app(arg, arena)
+        });
+        let call = indices.fold(call, |call, index| call.app(index, arena));
+
+        let field = ctx.lift(field, 0, arena);
+        let field = ctx.apply(field, 0..ctx.len(), arena);
+
+        let call = call.app(field, arena);
+        Some(ctx.close_abs(0, call, arena))
+    }
+}
@@ -1,67 +1,110 @@
 //! A set of axioms hardcoded in the kernel.
 //!
-//! This is mostly used in order to provide inductive types to the user.
-//! For now, no new axiom can be dynamically added by the user.
+//! This is mostly used in order to provide inductive types to the user. Most of them, like `Eq`,
+//! `True`, `False` and `Bool`, are declared in the [prelude] as
+//! [user-declared inductive types](crate::memory::inductive) are, and their axioms are
+//! [generated](inductive) from their declaration. Axioms are also generated as
+//! [constants](constant) for the axioms declared by the user and the variables of sections.
+//!
+//! The remaining types are hardcoded because they need computation rules beyond the generic
+//! ι-reduction:
+//! - the recursor over `PUnit` computes on any of its inhabitants, not only on `Star`, by eta;
+//! - dependent pairs have primitive projections and an eta rule;
+//! - natural numbers are accelerated through [literals](crate::memory::term::Payload::NatLit).
 
 use derive_more::Display;
 
 use crate::memory::arena::Arena;
 use crate::memory::term::Term;
 
-pub mod equality;
-pub mod false_;
+pub mod constant;
+pub mod inductive;
 pub mod natural;
-pub mod true_;
+mod prelude;
+pub mod sigma;
+pub mod unit;
 
-/// Enumeration type over all hardcoded axioms.
+/// Enumeration type over all axioms.
 #[derive(Copy, Clone, Debug, Display, Eq, PartialEq, Hash)]
-pub enum Axiom {
-    /// Axioms to describe Equality.
+pub enum Axiom<'arena> {
+    /// Axioms to describe the unit type.
     #[display(fmt = "{_0}")]
-    Equality(equality::Equality),
-
-    /// Axioms to describe True.
-    True(true_::True),
+    Unit(unit::Unit),
 
-    /// Axioms to describe False.
+    /// Axioms to describe dependent pairs.
     #[display(fmt = "{_0}")]
-    False(false_::False),
+    Sigma(sigma::Sigma),
 
     /// Axioms to describe Natural numbers.
     #[display(fmt = "{_0}")]
     Natural(natural::Natural),
+
+    /// Axioms generated from a user-declared inductive type.
+    #[display(fmt = "{_0}")]
+    Inductive(inductive::Inductive<'arena>),
+
+    /// A constant, generated from a user-declared axiom or from the variable of a section.
+    #[display(fmt = "{_0}")]
+    Constant(constant::Constant<'arena>),
 }
 
-impl Axiom {
+impl<'arena> Axiom<'arena> {
     /// Adds all the axioms to a given `arena`.
     #[inline]
     pub fn add_named_axioms(arena: &mut Arena<'_>) {
-        self::equality::Equality::append_to_named_axioms(arena);
-        self::true_::True::append_to_named_axioms(arena);
-        self::false_::False::append_to_named_axioms(arena);
+        self::prelude::declare(arena);
+        self::unit::Unit::append_to_named_axioms(arena);
+        self::sigma::Sigma::append_to_named_axioms(arena);
         self::natural::Natural::append_to_named_axioms(arena);
     }
 
     /// Gets the type of a given axiom.
     #[inline]
-    pub fn get_type<'arena>(self, arena: &mut Arena<'arena>) -> Term<'arena> {
-        use Axiom::{Equality, False, Natural, True};
+    pub fn get_type(self, arena: &mut Arena<'arena>) -> Term<'arena> {
+        use Axiom::{Constant, Inductive, Natural, Sigma, Unit};
 
         match self {
-            Equality(axiom) => axiom.get_type(arena),
-            True(axiom) => axiom.get_type(arena),
-            False(axiom) => axiom.get_type(arena),
+            Unit(axiom) => axiom.get_type(arena),
+            Sigma(axiom) => axiom.get_type(arena),
             Natural(axiom) => axiom.get_type(arena),
+            Inductive(axiom) => axiom.get_type(arena),
+            Constant(axiom) => axiom.get_type(),
         }
     }
 
+    /// Indicates whether the axiom is a constructor of some inductive type.
+    pub(crate) const fn is_constructor(self) -> bool {
+        matches!(
+            self,
+            Self::Unit(unit::Unit::Star)
+                | Self::Sigma(sigma::Sigma::SigmaMk)
+                | Self::Natural(natural::Natural::Zero | natural::Natural::Succ)
+                | Self::Inductive(inductive::Inductive::Constructor(..))
+        )
+    }
+
     /// Reduces a term, should any reduction be possible; returns `None` otherwise.
     #[inline]
-    pub fn reduce_recursor<'arena>(term: Term<'arena>, arena: &mut Arena<'arena>) -> Option<Term<'arena>> {
-        let recursors = [equality::Equality::reduce, natural::Natural::reduce];
+    pub fn reduce_recursor(term: Term<'arena>, arena: &mut Arena<'arena>) -> Option<Term<'arena>> {
+        let recursors = [
+            unit::Unit::reduce,
+            sigma::Sigma::reduce,
+            natural::Natural::reduce,
+            inductive::Inductive::reduce,
+        ];
 
         recursors.into_iter().find_map(|f| f(term, arena))
     }
+
+    /// Indicates whether a type, in weak-head normal form, is unit-like, that is, whether it has a
+    /// single constructor without any field. Any two inhabitants of such a type are definitionally
+    /// equal.
+    #[inline]
+    pub fn is_unit_like(type_: Term<'arena>, arena: &mut Arena<'arena>) -> bool {
+        let unit_likes = [unit::Unit::is_unit_like, inductive::Inductive::is_unit_like];
+
+        unit_likes.into_iter().any(|f| f(type_, arena))
+    }
 }
 
 /// Trait that defines the kind of axioms hardcoded in the kernel.
@@ -79,4 +122,10 @@ trait AxiomKind<'arena> {
     fn reduce(term: Term<'arena>, arena: &mut Arena<'arena>) -> Option<Term<'arena>> {
         None
     }
+
+    /// Indicates whether a type, in weak-head normal form, is a unit-like instance of the axioms.
+    #[allow(unused_variables)]
+    fn is_unit_like(type_: Term<'arena>, arena: &mut Arena<'arena>) -> bool {
+        false
+    }
 }
@@ -1,11 +1,19 @@
 //! Set of axioms, typing and reduction rules for the Natural numbers.
+//!
+//! Natural numbers can either be built from `Zero` and `Succ`, or be written as
+//! [literals](crate::memory::term::Payload::NatLit), which are equated with their unary
+//! representation. Arithmetic operations reduce structurally over the unary representation, and
+//! are computed directly over arbitrary-precision integers when both arguments are literals.
 
 use derive_more::Display;
+use num_bigint::BigUint;
 
+use super::prelude::{bool_term, bool_type};
 use super::{Axiom, AxiomKind};
 use crate::memory::arena::Arena;
 use crate::memory::declaration::Declaration;
 use crate::memory::level::Level;
+use crate::memory::term::Payload::App;
 use crate::memory::term::Term;
 
 #[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Hash)]
@@ -15,6 +23,7 @@ pub enum Natural {
     Nat,
 
     /// The recursor over natural numbers.
+    #[display(fmt = "Nat_rec")]
     NatRec,
 
     /// Zero (in the natural numbers).
@@ -22,6 +31,46 @@ pub enum Natural {
 
     /// The successor function in the natural numbers.
     Succ,
+
+    /// The addition of natural numbers.
+    #[display(fmt = "Nat_add")]
+    NatAdd,
+
+    /// The multiplication of natural numbers.
+    #[display(fmt = "Nat_mul")]
+    NatMul,
+
+    /// The truncated subtraction of natural numbers.
+    #[display(fmt = "Nat_sub")]
+    NatSub,
+
+    /// The euclidean division of natural numbers, where division by zero yields zero.
+    #[display(fmt = "Nat_div")]
+    NatDiv,
+
+    /// The remainder of the euclidean division of natural numbers, where `n % 0` is `n`.
+    #[display(fmt = "Nat_mod")]
+    NatMod,
+
+    /// The boolean equality test over natural numbers.
+    #[display(fmt = "Nat_beq")]
+    NatBeq,
+
+    /// The boolean less-or-equal test over natural numbers.
+    #[display(fmt = "Nat_ble")]
+    NatBle,
+}
+
+/// The shape of a natural number in weak-head normal form.
+enum Shape<'arena> {
+    /// Zero.
+    Zero,
+
+    /// The successor of the given natural number.
+    Succ(Term<'arena>),
+
+    /// Any other term, for which no reduction can happen.
+    Stuck,
 }
 
 impl<'arena> AxiomKind<'arena> for Natural {
@@ -31,7 +80,7 @@ impl<'arena> AxiomKind<'arena> for Natural {
         let decl = Term::axiom(Axiom::Natural(Self::Nat), &[], arena);
         arena.bind("Nat", decl);
 
-        let decl = Declaration(Term::axiom(Axiom::Natural(Self::NatRec), &[var0], arena), 1);
+        let decl = Declaration::new(Term::axiom(Axiom::Natural(Self::NatRec), &[var0], arena), 1);
         arena.bind_decl("Nat_rec", decl);
 
         let decl = Term::axiom(Axiom::Natural(Self::Zero), &[], arena);
@@ -39,6 +88,21 @@ impl<'arena> AxiomKind<'arena> for Natural {
 
         let decl = Term::axiom(Axiom::Natural(Self::Succ), &[], arena);
         arena.bind("Succ", decl);
+
+        let operations = [
+            (Self::NatAdd, "Nat_add"),
+            (Self::NatMul, "Nat_mul"),
+            (Self::NatSub, "Nat_sub"),
+            (Self::NatDiv, "Nat_div"),
+            (Self::NatMod, "Nat_mod"),
+            (Self::NatBeq, "Nat_beq"),
+            (Self::NatBle, "Nat_ble"),
+        ];
+
+        for (operation, name) in operations {
+            let decl = operation.term(arena);
+            arena.bind(name, decl);
+        }
     }
 
     fn get_type(self, arena: &mut Arena<'arena>) -> Term<'arena> {
@@ -51,11 +115,179 @@ impl<'arena> AxiomKind<'arena> for Natural {
                 Term::axiom(Axiom::Natural(Self::Nat), &[], arena),
                 arena,
             ),
+            Self::NatAdd | Self::NatMul | Self::NatSub | Self::NatDiv | Self::NatMod => {
+                let term_nat = Self::Nat.term(arena);
+                Term::prod(term_nat, Term::prod(term_nat, term_nat, arena), arena)
+            },
+            Self::NatBeq | Self::NatBle => {
+                let term_nat = Self::Nat.term(arena);
+                let term_bool = bool_type(arena);
+                Term::prod(term_nat, Term::prod(term_nat, term_bool, arena), arena)
+            },
         }
     }
 
     fn reduce(term: Term<'arena>, arena: &mut Arena<'arena>) -> Option<Term<'arena>> {
-        use crate::memory::term::Payload::{App, Axiom};
+        Self::reduce_rec(term, arena)
+            .or_else(|| Self::reduce_operation(term, arena))
+            .or_else(|| Self::reduce_succ(term, arena))
+    }
+}
+
+impl<'arena> Term<'arena> {
+    /// Expands a natural number literal into its unary representation: `0` becomes `Zero`, and
+    /// `n + 1` becomes `Succ n`. Other terms are left unchanged.
+    pub(crate) fn expand_nat_lit(self, arena: &mut Arena<'arena>) -> Self {
+        let Some(n) = self.to_nat_lit() else {
+            return self;
+        };
+
+        if n == BigUint::default() {
+            Natural::Zero.term(arena)
+        } else {
+            let pred = Term::nat_lit(&(n - 1_u32), arena);
+            Natural::Succ.term(arena).app(pred, arena)
+        }
+    }
+}
+
+impl Natural {
+    /// Returns the term associated to a universe-monomorphic axiom.
+    pub(crate) fn term<'arena>(self, arena: &mut Arena<'arena>) -> Term<'arena> {
+        Term::axiom(Axiom::Natural(self), &[], arena)
+    }
+
+    /// Returns the value of a term in weak-head normal form, if it is a literal or `Zero`.
+    fn literal(term: Term<'_>) -> Option<BigUint> {
+        use crate::memory::term::Payload::Axiom;
+
+        match *term {
+            Axiom(super::Axiom::Natural(Self::Zero), _) => Some(BigUint::default()),
+            _ => term.to_nat_lit(),
+        }
+    }
+
+    /// Returns the shape of a term in weak-head normal form.
+    fn shape<'arena>(term: Term<'arena>, arena: &mut Arena<'arena>) -> Shape<'arena> {
+        use crate::memory::term::Payload::Axiom;
+
+        match *term.expand_nat_lit(arena) {
+            Axiom(super::Axiom::Natural(Self::Zero), _) => Shape::Zero,
+            App(f, n) if matches!(*f.unfold(arena).whnf(arena), Axiom(super::Axiom::Natural(Self::Succ), _)) => Shape::Succ(n),
+            _ => Shape::Stuck,
+        }
+    }
+
+    /// Computes the result of an operation over two literals.
+    fn compute<'arena>(self, m: BigUint, n: BigUint, arena: &mut Arena<'arena>) -> Term<'arena> {
+        let zero = BigUint::default();
+
+        let result = match self {
+            Self::NatAdd => m + n,
+            Self::NatMul => m * n,
+            Self::NatSub => {
+                if m < n {
+                    zero
+                } else {
+                    m - n
+                }
+            },
+            Self::NatDiv => {
+                if n == zero {
+                    zero
+                } else {
+                    m / n
+                }
+            },
+            Self::NatMod => {
+                if n == zero {
+                    m
+                } else {
+                    m % n
+                }
+            },
+            Self::NatBeq => return bool_term(m == n, arena),
+            Self::NatBle => return bool_term(m <= n, arena),
+            Self::Nat | Self::NatRec | Self::Zero | Self::Succ => unreachable!("not an operation"),
+        };
+
+        Term::nat_lit(&result, arena)
+    }
+
+    /// Reduces an operation over natural numbers applied to two arguments.
+    ///
+    /// When both arguments are literals, the result is computed directly. Otherwise, the
+    /// operation reduces structurally, following the unary representation of its arguments.
+    fn reduce_operation<'arena>(term: Term<'arena>, arena: &mut Arena<'arena>) -> Option<Term<'arena>> {
+        use crate::memory::term::Payload::Axiom;
+
+        let App(f, n) = *term else {
+            return None;
+        };
+        let App(f, m) = *f.whnf(arena) else {
+            return None;
+        };
+        let Axiom(super::Axiom::Natural(operation), _) = *f.unfold(arena).whnf(arena) else {
+            return None;
+        };
+
+        if matches!(operation, Self::Nat | Self::NatRec | Self::Zero | Self::Succ) {
+            return None;
+        }
+
+        let m = m.whnf(arena);
+        let n = n.whnf(arena);
+
+        if let (Some(m), Some(n)) = (Self::literal(m), Self::literal(n)) {
+            return Some(operation.compute(m, n, arena));
+        }
+
+        let apply = |operation: Self, m, n, arena: &mut Arena<'arena>| operation.term(arena).app(m, arena).app(n, arena);
+
+        match (operation, Self::shape(m, arena), Self::shape(n, arena)) {
+            (Self::NatAdd | Self::NatSub, _, Shape::Zero) => Some(m),
+            (Self::NatAdd, _, Shape::Succ(n)) => {
+                let add = apply(Self::NatAdd, m, n, arena);
+                Some(Self::Succ.term(arena).app(add, arena))
+            },
+
+            (Self::NatMul, _, Shape::Zero) | (Self::NatSub, Shape::Zero, Shape::Succ(_)) => Some(Self::Zero.term(arena)),
+            (Self::NatMul, _, Shape::Succ(n)) => {
+                let mul = apply(Self::NatMul, m, n, arena);
+                Some(apply(Self::NatAdd, mul, m, arena))
+            },
+
+            (Self::NatSub, Shape::Succ(m), Shape::Succ(n)) => Some(apply(Self::NatSub, m, n, arena)),
+
+            (Self::NatBeq, Shape::Zero, Shape::Zero) | (Self::NatBle, Shape::Zero, _) => Some(bool_term(true, arena)),
+            (Self::NatBeq, Shape::Zero, Shape::Succ(_)) | (Self::NatBeq | Self::NatBle, Shape::Succ(_), Shape::Zero) => {
+                Some(bool_term(false, arena))
+            },
+            (Self::NatBeq | Self::NatBle, Shape::Succ(m), Shape::Succ(n)) => Some(apply(operation, m, n, arena)),
+
+            _ => None,
+        }
+    }
+
+    /// Reduces the successor of a literal into a literal.
+    fn reduce_succ<'arena>(term: Term<'arena>, arena: &mut Arena<'arena>) -> Option<Term<'arena>> {
+        use crate::memory::term::Payload::Axiom;
+
+        let App(f, n) = *term else {
+            return None;
+        };
+        let n = n.to_nat_lit()?;
+        let Axiom(super::Axiom::Natural(Self::Succ), _) = *f.unfold(arena).whnf(arena) else {
+            return None;
+        };
+
+        Some(Term::nat_lit(&(n + 1_u32), arena))
+    }
+
+    /// Reduces the recursor over natural numbers, applied to a natural number in constructor
+    /// form or to a literal.
+    fn reduce_rec<'arena>(term: Term<'arena>, arena: &mut Arena<'arena>) -> Option<Term<'arena>> {
+        use crate::memory::term::Payload::Axiom;
 
         // The multiple `let` statements can be easily rewritten as a pattern match
         // if https://github.com/rust-lang/rfcs/issues/2099 is solved.
@@ -75,7 +307,7 @@ impl<'arena> AxiomKind<'arena> for Natural {
             return None;
         };
 
-        match *n.whnf(arena) {
+        match *n.whnf(arena).expand_nat_lit(arena) {
             Axiom(super::Axiom::Natural(Self::Zero), _) => Some(motive_0),
             App(f, n) => {
                 let Axiom(super::Axiom::Natural(Self::Succ), _) = *f.unfold(arena).whnf(arena) else {
@@ -101,9 +333,7 @@ impl<'arena> AxiomKind<'arena> for Natural {
             _ => None,
         }
     }
-}
 
-impl Natural {
     /// Type of the recursor over natural numbers
     fn type_nat_rec<'arena>(arena: &mut Arena<'arena>) -> Term<'arena> {
         // Nat
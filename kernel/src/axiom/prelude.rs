@@ -0,0 +1,94 @@
+//! Inductive types provided to the user, declared through the same machinery as
+//! [user-declared inductive types](crate::memory::inductive).
+//!
+//! Their constructors and recursors are thus derived by the kernel, and they compute through the
+//! generic ι-reduction rule of [inductive axioms](super::inductive).
+
+use super::inductive::Inductive::{Constructor, Type};
+use super::Axiom;
+use crate::memory::arena::Arena;
+use crate::memory::inductive::builder::{inductive, BuilderTrait};
+use crate::memory::inductive::Inductive;
+use crate::memory::level::builder as level;
+use crate::memory::term::builder::{self as term, app, prod, prop, sort, type_usize, var};
+use crate::memory::term::Term;
+
+/// A boxed term builder, so that constructors of different shapes may be built together.
+type Boxed<'build> = Box<dyn term::BuilderTrait<'build> + 'build>;
+
+/// Boxes the term builder `f`.
+fn boxed<'build, F: term::BuilderTrait<'build> + 'build>(f: F) -> Boxed<'build> {
+    Box::new(f)
+}
+
+/// Declares all the inductive types of the prelude in the given `arena`.
+pub(super) fn declare(arena: &mut Arena<'_>) {
+    declare_inductive(arena, equality());
+    declare_inductive(arena, true_());
+    declare_inductive(arena, false_());
+    declare_inductive(arena, bool_());
+}
+
+/// Builds, checks and binds the given inductive type, which is known to be well-formed.
+fn declare_inductive<'build, F: BuilderTrait<'build>>(arena: &mut Arena<'_>, f: F) {
+    arena
+        .build_inductive(f)
+        .and_then(|inductive| arena.bind_inductive(inductive))
+        .unwrap_or_else(|err| unreachable!("ill-formed inductive type of the prelude: {err}"));
+}
+
+/// The equality type `Eq.{u} (A : Sort u) (a : A) : A -> Prop`, whose only constructor is
+/// `Refl.{u} A a : Eq.{u} A a a`.
+fn equality<'build>() -> impl BuilderTrait<'build> {
+    inductive(
+        "Eq",
+        &["u"],
+        2,
+        boxed(prod("A", sort(level::var("u")), prod("a", var("A"), prod("_", var("A"), prop())))),
+        vec![(
+            "Refl",
+            boxed(prod(
+                "A",
+                sort(level::var("u")),
+                prod("a", var("A"), app(app(app(var("Eq"), var("A")), var("a")), var("a"))),
+            )),
+        )],
+    )
+}
+
+/// The proposition `True`, whose only proof is `Tt`.
+fn true_<'build>() -> impl BuilderTrait<'build> {
+    inductive("True", &[], 0, boxed(prop()), vec![("Tt", boxed(var("True")))])
+}
+
+/// The proposition `False`, which has no proof.
+fn false_<'build>() -> impl BuilderTrait<'build> {
+    inductive("False", &[], 0, boxed(prop()), vec![])
+}
+
+/// The booleans `Bool : Type`, whose constructors are `BTrue` and `BFalse`.
+///
+/// Booleans are mostly used as the result of the comparison functions over [natural
+/// numbers](super::natural).
+fn bool_<'build>() -> impl BuilderTrait<'build> {
+    inductive("Bool", &[], 0, boxed(type_usize(0)), vec![("BTrue", boxed(var("Bool"))), ("BFalse", boxed(var("Bool")))])
+}
+
+/// Returns the inductive type of booleans.
+fn booleans<'arena>(arena: &Arena<'arena>) -> Inductive<'arena> {
+    arena.get_inductive("Bool").unwrap_or_else(|| unreachable!("booleans are declared along with natural numbers"))
+}
+
+/// Returns the type of booleans.
+pub(super) fn bool_type<'arena>(arena: &mut Arena<'arena>) -> Term<'arena> {
+    let booleans = booleans(arena);
+
+    Term::axiom(Axiom::Inductive(Type(booleans)), &[], arena)
+}
+
+/// Returns the boolean term corresponding to `b`.
+pub(super) fn bool_term<'arena>(b: bool, arena: &mut Arena<'arena>) -> Term<'arena> {
+    let booleans = booleans(arena);
+
+    Term::axiom(Axiom::Inductive(Constructor(booleans, usize::from(!b))), &[], arena)
+}
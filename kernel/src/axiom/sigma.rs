@@ -0,0 +1,245 @@
+//! Set of axioms, typing and reduction rules for dependent pairs.
+//!
+//! `Sigma.{u, v} A B` is the type of pairs made of some `a: A` and some `b: B a`. Pairs are built
+//! with `Sigma_mk` and destructed with the primitive projections `Sigma_fst` and `Sigma_snd`.
+//! Conversely, any term of a `Sigma` type is definitionally equal to the pair of its projections.
+
+use derive_more::Display;
+
+use super::{Axiom, AxiomKind};
+use crate::memory::arena::Arena;
+use crate::memory::declaration::Declaration;
+use crate::memory::level::Level;
+use crate::memory::term::Term;
+
+/// Axioms regarding dependent pairs.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Sigma {
+    /// The type of dependent pairs.
+    Sigma,
+
+    /// The constructor of dependent pairs.
+    #[display(fmt = "Sigma_mk")]
+    SigmaMk,
+
+    /// The first projection of a dependent pair.
+    #[display(fmt = "Sigma_fst")]
+    SigmaFst,
+
+    /// The second projection of a dependent pair.
+    #[display(fmt = "Sigma_snd")]
+    SigmaSnd,
+}
+
+/// A dependent pair `Sigma_mk.{u, v} A B a b`, in weak-head normal form.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct Pair<'arena> {
+    /// The universe levels `u` and `v`.
+    levels: &'arena [Level<'arena>],
+
+    /// The type `A` of the first component.
+    type_fst: Term<'arena>,
+
+    /// The family `B` of types of the second component.
+    type_snd: Term<'arena>,
+
+    /// The first component.
+    fst: Term<'arena>,
+
+    /// The second component.
+    snd: Term<'arena>,
+}
+
+impl<'arena> Pair<'arena> {
+    /// Returns the first component of the pair.
+    pub(crate) const fn fst(self) -> Term<'arena> {
+        self.fst
+    }
+
+    /// Returns the second component of the pair.
+    pub(crate) const fn snd(self) -> Term<'arena> {
+        self.snd
+    }
+}
+
+impl<'arena> AxiomKind<'arena> for Sigma {
+    fn append_to_named_axioms(arena: &mut Arena<'arena>) {
+        let var0 = Level::var(0, arena);
+        let var1 = Level::var(1, arena);
+
+        let decl = Declaration::new(Term::axiom(Axiom::Sigma(Self::Sigma), &[var0, var1], arena), 2);
+        arena.bind_decl("Sigma", decl);
+
+        let decl = Declaration::new(Term::axiom(Axiom::Sigma(Self::SigmaMk), &[var0, var1], arena), 2);
+        arena.bind_decl("Sigma_mk", decl);
+
+        let decl = Declaration::new(Term::axiom(Axiom::Sigma(Self::SigmaFst), &[var0, var1], arena), 2);
+        arena.bind_decl("Sigma_fst", decl);
+
+        let decl = Declaration::new(Term::axiom(Axiom::Sigma(Self::SigmaSnd), &[var0, var1], arena), 2);
+        arena.bind_decl("Sigma_snd", decl);
+    }
+
+    fn get_type(self, arena: &mut Arena<'arena>) -> Term<'arena> {
+        match self {
+            Self::Sigma => Self::type_sigma(arena),
+            Self::SigmaMk => Self::type_sigma_mk(arena),
+            Self::SigmaFst => Self::type_sigma_fst(arena),
+            Self::SigmaSnd => Self::type_sigma_snd(arena),
+        }
+    }
+
+    fn reduce(term: Term<'arena>, arena: &mut Arena<'arena>) -> Option<Term<'arena>> {
+        use crate::memory::term::Payload::{App, Axiom};
+
+        let App(f, pair) = *term else {
+            return None;
+        };
+        let App(f, _) = *f.whnf(arena) else {
+            return None;
+        };
+        let App(f, _) = *f.whnf(arena) else {
+            return None;
+        };
+        let Axiom(super::Axiom::Sigma(projection @ (Self::SigmaFst | Self::SigmaSnd)), _) = *f.unfold(arena).whnf(arena) else {
+            return None;
+        };
+
+        let pair = Self::to_pair(pair, arena)?;
+
+        Some(if projection == Self::SigmaFst { pair.fst } else { pair.snd })
+    }
+}
+
+impl Sigma {
+    /// Returns `term` as a dependent pair, should its weak-head normal form be one.
+    pub(crate) fn to_pair<'arena>(term: Term<'arena>, arena: &mut Arena<'arena>) -> Option<Pair<'arena>> {
+        use crate::memory::term::Payload::Axiom;
+
+        let (head, args) = term.whnf_unfolded(arena).spine(arena);
+
+        let Axiom(super::Axiom::Sigma(Self::SigmaMk), levels) = *head else {
+            return None;
+        };
+        let &[type_fst, type_snd, fst, snd] = args.as_slice() else {
+            return None;
+        };
+
+        Some(Pair {
+            levels,
+            type_fst,
+            type_snd,
+            fst,
+            snd,
+        })
+    }
+
+    /// Returns the projections of `term`, seen as an inhabitant of the same type as `pair`.
+    pub(crate) fn projections<'arena>(
+        pair: Pair<'arena>,
+        term: Term<'arena>,
+        arena: &mut Arena<'arena>,
+    ) -> (Term<'arena>, Term<'arena>) {
+        let fst = Term::axiom(Axiom::Sigma(Self::SigmaFst), pair.levels, arena);
+        let fst = fst.app(pair.type_fst, arena).app(pair.type_snd, arena).app(term, arena);
+
+        let snd = Term::axiom(Axiom::Sigma(Self::SigmaSnd), pair.levels, arena);
+        let snd = snd.app(pair.type_fst, arena).app(pair.type_snd, arena).app(term, arena);
+
+        (fst, snd)
+    }
+
+    /// Returns `A -> Sort v`, the type of the family `B` whose de Bruijn index is `index - 1`, in a
+    /// context where `A: Sort u` has index `index`.
+    fn type_family<'arena>(index: usize, arena: &mut Arena<'arena>) -> Term<'arena> {
+        let sort_u = Term::sort(Level::var(0, arena), arena);
+        let sort_v = Term::sort(Level::var(1, arena), arena);
+
+        Term::prod(Term::var(index.into(), sort_u, arena), sort_v, arena)
+    }
+
+    /// Returns `Sigma.{u, v} A B`, in a context where `A` has index `index` and `B` has index
+    /// `index - 1`.
+    fn sigma_a_b<'arena>(index: usize, arena: &mut Arena<'arena>) -> Term<'arena> {
+        let var0 = Level::var(0, arena);
+        let var1 = Level::var(1, arena);
+        let sort_u = Term::sort(var0, arena);
+
+        let sigma = Term::axiom(Axiom::Sigma(Self::Sigma), &[var0, var1], arena);
+        let type_b = Self::type_family(index, arena);
+
+        sigma
+            .app(Term::var(index.into(), sort_u, arena), arena)
+            .app(Term::var((index - 1).into(), type_b, arena), arena)
+    }
+
+    /// Type of the type of dependent pairs:
+    /// `Sigma.{u, v} : (A: Sort u) -> (B: A -> Sort v) -> Sort (max 1 u v)`.
+    fn type_sigma<'arena>(arena: &mut Arena<'arena>) -> Term<'arena> {
+        let var0 = Level::var(0, arena);
+        let var1 = Level::var(1, arena);
+        let sort_u = Term::sort(var0, arena);
+
+        let one = Level::zero(arena).succ(arena);
+        let max = var0.max(var1, arena);
+        let sort = Term::sort(one.max(max, arena), arena);
+
+        let type_b = Self::type_family(1, arena);
+
+        sort_u.prod(type_b.prod(sort, arena), arena)
+    }
+
+    /// Type of the constructor of dependent pairs:
+    /// `Sigma_mk.{u, v} : (A: Sort u) -> (B: A -> Sort v) -> (a: A) -> B a -> Sigma A B`.
+    fn type_sigma_mk<'arena>(arena: &mut Arena<'arena>) -> Term<'arena> {
+        let sort_u = Term::sort(Level::var(0, arena), arena);
+        let type_b = Self::type_family(1, arena);
+
+        // B a
+        let b_a = Term::var(2.into(), Self::type_family(3, arena), arena);
+        let a = Term::var(1.into(), Term::var(3.into(), sort_u, arena), arena);
+        let b_a = b_a.app(a, arena);
+
+        let sigma_a_b = Self::sigma_a_b(4, arena);
+
+        let body = b_a.prod(sigma_a_b, arena);
+        let body = Term::var(2.into(), sort_u, arena).prod(body, arena);
+
+        sort_u.prod(type_b.prod(body, arena), arena)
+    }
+
+    /// Type of the first projection:
+    /// `Sigma_fst.{u, v} : (A: Sort u) -> (B: A -> Sort v) -> Sigma A B -> A`.
+    fn type_sigma_fst<'arena>(arena: &mut Arena<'arena>) -> Term<'arena> {
+        let sort_u = Term::sort(Level::var(0, arena), arena);
+        let type_b = Self::type_family(1, arena);
+
+        let sigma_a_b = Self::sigma_a_b(2, arena);
+        let a = Term::var(3.into(), sort_u, arena);
+
+        sort_u.prod(type_b.prod(sigma_a_b.prod(a, arena), arena), arena)
+    }
+
+    /// Type of the second projection:
+    /// `Sigma_snd.{u, v} : (A: Sort u) -> (B: A -> Sort v) -> (p: Sigma A B) -> B (Sigma_fst A B p)`.
+    fn type_sigma_snd<'arena>(arena: &mut Arena<'arena>) -> Term<'arena> {
+        let var0 = Level::var(0, arena);
+        let var1 = Level::var(1, arena);
+        let sort_u = Term::sort(var0, arena);
+        let type_b = Self::type_family(1, arena);
+
+        let sigma_a_b = Self::sigma_a_b(2, arena);
+
+        // Sigma_fst A B p
+        let fst = Term::axiom(Axiom::Sigma(Self::SigmaFst), &[var0, var1], arena);
+        let a = Term::var(3.into(), sort_u, arena);
+        let b = Term::var(2.into(), Self::type_family(3, arena), arena);
+        let p = Term::var(1.into(), Self::sigma_a_b(3, arena), arena);
+        let fst_p = fst.app(a, arena).app(b, arena).app(p, arena);
+
+        // B (Sigma_fst A B p)
+        let b_fst_p = b.app(fst_p, arena);
+
+        sort_u.prod(type_b.prod(sigma_a_b.prod(b_fst_p, arena), arena), arena)
+    }
+}
@@ -0,0 +1,114 @@
+//! Set of axioms, typing and reduction rules for the universe-polymorphic `PUnit` type.
+//!
+//! `PUnit.{u}` lives in `Sort u` and has a single inhabitant `Star.{u}`. Because of eta for
+//! unit-like types, any two of its inhabitants are definitionally equal. For convenience, `Unit`
+//! is bound to `PUnit.{1}`.
+
+use derive_more::Display;
+
+use super::{Axiom, AxiomKind};
+use crate::memory::arena::Arena;
+use crate::memory::declaration::Declaration;
+use crate::memory::level::Level;
+use crate::memory::term::Term;
+
+/// Axioms regarding `PUnit`.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Unit {
+    /// The unit type, in any universe.
+    PUnit,
+
+    /// The only inhabitant of the unit type.
+    Star,
+
+    /// The recursor over the unit type.
+    #[display(fmt = "PUnit_rec")]
+    PUnitRec,
+}
+
+impl<'arena> AxiomKind<'arena> for Unit {
+    fn append_to_named_axioms(arena: &mut Arena<'arena>) {
+        let var0 = Level::var(0, arena);
+        let var1 = Level::var(1, arena);
+
+        let decl = Declaration::new(Term::axiom(Axiom::Unit(Self::PUnit), &[var0], arena), 1);
+        arena.bind_decl("PUnit", decl);
+
+        let decl = Declaration::new(Term::axiom(Axiom::Unit(Self::Star), &[var0], arena), 1);
+        arena.bind_decl("Star", decl);
+
+        let decl = Declaration::new(Term::axiom(Axiom::Unit(Self::PUnitRec), &[var0, var1], arena), 2);
+        arena.bind_decl("PUnit_rec", decl);
+
+        let one = Level::succ(Level::zero(arena), arena);
+        let term = Term::axiom(Axiom::Unit(Self::PUnit), &[one], arena);
+        arena.bind("Unit", term);
+    }
+
+    fn get_type(self, arena: &mut Arena<'arena>) -> Term<'arena> {
+        match self {
+            Self::PUnit => Term::sort(Level::var(0, arena), arena),
+            Self::Star => {
+                let var0 = Level::var(0, arena);
+                Term::axiom(Axiom::Unit(Self::PUnit), &[var0], arena)
+            },
+            Self::PUnitRec => Self::type_punit_rec(arena),
+        }
+    }
+
+    fn reduce(term: Term<'arena>, arena: &mut Arena<'arena>) -> Option<Term<'arena>> {
+        use crate::memory::term::Payload::{App, Axiom};
+
+        // By eta, every inhabitant of the unit type is convertible to `Star`: the recursor computes
+        // without inspecting its last argument.
+        let App(f, _) = *term else {
+            return None;
+        };
+        let App(f, minor) = *f.whnf(arena) else {
+            return None;
+        };
+        let App(f, _) = *f.whnf(arena) else {
+            return None;
+        };
+        let Axiom(super::Axiom::Unit(Self::PUnitRec), _) = *f.unfold(arena).whnf(arena) else {
+            return None;
+        };
+
+        Some(minor)
+    }
+
+    fn is_unit_like(type_: Term<'arena>, _: &mut Arena<'arena>) -> bool {
+        use crate::memory::term::Payload::Axiom;
+
+        matches!(*type_, Axiom(super::Axiom::Unit(Self::PUnit), _))
+    }
+}
+
+impl Unit {
+    /// Type of the recursor over the unit type.
+    fn type_punit_rec<'arena>(arena: &mut Arena<'arena>) -> Term<'arena> {
+        let var0 = Level::var(0, arena);
+
+        // PUnit.{u}
+        let term_punit = Term::axiom(Axiom::Unit(Self::PUnit), &[var0], arena);
+
+        // Sort v
+        let sort_v = Term::sort(Level::var(1, arena), arena);
+
+        // PUnit.{u} -> Sort v
+        let motive = Term::prod(term_punit, sort_v, arena);
+
+        // motive t
+        let app_motive = Term::app(Term::var(3.into(), motive, arena), Term::var(1.into(), term_punit, arena), arena);
+
+        // (t: PUnit.{u}) -> motive t
+        let prod_app_motive = Term::prod(term_punit, app_motive, arena);
+
+        // motive Star.{u}
+        let term_star = Term::axiom(Axiom::Unit(Self::Star), &[var0], arena);
+        let motive_star = Term::app(Term::var(1.into(), motive, arena), term_star, arena);
+
+        // (motive: PUnit.{u} -> Sort v) -> motive Star.{u} -> (t: PUnit.{u}) -> motive t
+        Term::prod(motive, Term::prod(motive_star, prod_app_motive, arena), arena)
+    }
+}
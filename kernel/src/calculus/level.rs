@@ -37,9 +37,12 @@ impl<'arena> Level<'arena> {
 
     /// Substitutes all level variables in `self` according to `univs`.
     ///
-    /// This function makes no verification that `univs` has an appropriate size, which is way it
-    /// cannot be made public.
-    pub(crate) fn substitute(self, univs: &[Self], arena: &mut Arena<'arena>) -> Self {
+    /// # Panics
+    /// This function makes no verification that `univs` has an appropriate size: it panics if a
+    /// variable of `self` has no correspondence in `univs`.
+    #[inline]
+    #[must_use]
+    pub fn substitute(self, univs: &[Self], arena: &mut Arena<'arena>) -> Self {
         match *self {
             Zero => self,
             Add(n, k) => n.substitute(univs, arena).add(k, arena),
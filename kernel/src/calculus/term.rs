@@ -3,19 +3,22 @@
 //! This module consists of internal utility functions used by the type checker, and correspond to
 //! usual functions over lambda-terms. These functions interact appropriately with a given arena.
 
+use std::collections::{HashMap, HashSet};
+
+use crate::axiom::Axiom as AxiomKind;
 use crate::memory::arena::Arena;
 use crate::memory::declaration::InstantiatedDeclaration;
 use crate::memory::level::Level;
-use crate::memory::term::Payload::{Abs, App, Axiom, Decl, Prod, Sort, Var};
+use crate::memory::term::Payload::{Abs, App, Axiom, Decl, Let, NatLit, Prod, Sort, Var};
 use crate::memory::term::Term;
 
 impl<'arena> Term<'arena> {
     /// Unfolds a term.
     ///
-    /// Unfolding only happens on instantiated declarations.
+    /// Unfolding only happens on instantiated declarations which are not opaque.
     pub(crate) fn unfold(self, arena: &mut Arena<'arena>) -> Self {
         match *self {
-            Decl(decl) => decl.get_term(arena),
+            Decl(decl) if !decl.decl().is_opaque() => decl.get_term(arena),
             _ => self,
         }
     }
@@ -44,13 +47,14 @@ impl<'arena> Term<'arena> {
             },
             Abs(arg_type, body) => {
                 let body = body.beta_reduction(arena);
-                arg_type.abs(body, arena)
+                arg_type.abs(body, arena).inherit_name(self)
             },
             Prod(arg_type, body) => {
                 let body = body.beta_reduction(arena);
-                arg_type.prod(body, arena)
+                arg_type.prod(body, arena).inherit_name(self)
             },
-            Decl(decl) => decl.get_term(arena),
+            Let(_, value, body) => body.substitute(value, 1, arena),
+            Decl(decl) if !decl.decl().is_opaque() => decl.get_term(arena),
             _ => self,
         }
     }
@@ -63,7 +67,9 @@ impl<'arena> Term<'arena> {
         }
 
         let shifted_term = match *self {
-            Var(i, type_) if i > depth.into() => Term::var(i + offset.into(), type_, arena),
+            // the types annotating variables live where the variables occur, and are shifted alike
+            Var(i, type_) if i > depth.into() => Term::var(i + offset.into(), type_.shift(offset, depth, arena), arena),
+            Var(i, type_) => Term::var(i, type_.shift(offset, depth, arena), arena),
             App(t1, t2) => {
                 let t1 = t1.shift(offset, depth, arena);
                 let t2 = t2.shift(offset, depth, arena);
@@ -72,12 +78,18 @@ impl<'arena> Term<'arena> {
             Abs(arg_type, body) => {
                 let arg_type = arg_type.shift(offset, depth, arena);
                 let body = body.shift(offset, depth + 1, arena);
-                arg_type.abs(body, arena)
+                arg_type.abs(body, arena).inherit_name(self)
             },
             Prod(arg_type, body) => {
                 let arg_type = arg_type.shift(offset, depth, arena);
                 let body = body.shift(offset, depth + 1, arena);
-                arg_type.prod(body, arena)
+                arg_type.prod(body, arena).inherit_name(self)
+            },
+            Let(type_, value, body) => {
+                let type_ = type_.shift(offset, depth, arena);
+                let value = value.shift(offset, depth, arena);
+                let body = body.shift(offset, depth + 1, arena);
+                Term::let_(type_, value, body, arena).inherit_name(self)
             },
             _ => self,
         };
@@ -94,7 +106,9 @@ impl<'arena> Term<'arena> {
     pub(crate) fn substitute(self, sub: Self, depth: usize, arena: &mut Arena<'arena>) -> Self {
         arena.get_subst_or_init(&(self, sub, depth), |arena| match *self {
             Var(i, _) if i == depth.into() => sub.shift(depth - 1, 0, arena),
-            Var(i, type_) if i > depth.into() => Term::var(i - 1.into(), type_, arena),
+            // the types annotating variables live where the variables occur, and are substituted alike
+            Var(i, type_) if i > depth.into() => Term::var(i - 1.into(), type_.substitute(sub, depth, arena), arena),
+            Var(i, type_) => Term::var(i, type_.substitute(sub, depth, arena), arena),
             App(l, r) => {
                 let l = l.substitute(sub, depth, arena);
                 let r = r.substitute(sub, depth, arena);
@@ -103,24 +117,235 @@ impl<'arena> Term<'arena> {
             Abs(arg_type, body) => {
                 let arg_type = arg_type.substitute(sub, depth, arena);
                 let body = body.substitute(sub, depth + 1, arena);
-                arg_type.abs(body, arena)
+                arg_type.abs(body, arena).inherit_name(self)
             },
             Prod(arg_type, body) => {
                 let arg_type = arg_type.substitute(sub, depth, arena);
                 let body = body.substitute(sub, depth + 1, arena);
-                arg_type.prod(body, arena)
+                arg_type.prod(body, arena).inherit_name(self)
+            },
+            Let(type_, value, body) => {
+                let type_ = type_.substitute(sub, depth, arena);
+                let value = value.substitute(sub, depth, arena);
+                let body = body.substitute(sub, depth + 1, arena);
+                Term::let_(type_, value, body, arena).inherit_name(self)
             },
             _ => self,
         })
     }
 
+    /// Indicates whether the variable of de Bruijn index `index` occurs freely in `self`.
+    ///
+    /// The types annotating variables are not inspected.
+    pub(crate) fn has_free_var(self, index: usize) -> bool {
+        if self.is_certainly_closed() {
+            return false;
+        }
+
+        match *self {
+            Var(i, _) => i == index.into(),
+            App(t1, t2) => t1.has_free_var(index) || t2.has_free_var(index),
+            Abs(arg_type, body) | Prod(arg_type, body) => arg_type.has_free_var(index) || body.has_free_var(index + 1),
+            Let(type_, value, body) => type_.has_free_var(index) || value.has_free_var(index) || body.has_free_var(index + 1),
+            _ => false,
+        }
+    }
+
+    /// Indicates whether the constant `constant` occurs in `self`, possibly through the
+    /// declarations instantiated in `self`.
+    ///
+    /// The types annotating variables are not inspected.
+    #[inline]
+    #[must_use]
+    pub fn has_constant(self, constant: Self) -> bool {
+        self.has_constant_in(constant, &mut HashSet::new())
+    }
+
+    /// Indicates whether the constant `constant` occurs in `self`, the terms of `visited` being
+    /// skipped.
+    #[allow(clippy::mutable_key_type)]
+    fn has_constant_in(self, constant: Self, visited: &mut HashSet<Self>) -> bool {
+        if self == constant {
+            return true;
+        }
+
+        if !visited.insert(self) {
+            return false;
+        }
+
+        match *self {
+            App(t1, t2) | Abs(t1, t2) | Prod(t1, t2) => {
+                t1.has_constant_in(constant, visited) || t2.has_constant_in(constant, visited)
+            },
+            Let(type_, value, body) => {
+                type_.has_constant_in(constant, visited)
+                    || value.has_constant_in(constant, visited)
+                    || body.has_constant_in(constant, visited)
+            },
+            Decl(decl) => decl.decl.0.has_constant_in(constant, visited),
+            _ => false,
+        }
+    }
+
+    /// Returns the names of the axioms and opaque declarations occurring in `self`, possibly
+    /// through the transparent declarations instantiated in `self`, in no particular order.
+    ///
+    /// The types annotating variables are not inspected.
+    #[inline]
+    #[must_use]
+    pub fn constants(self) -> HashSet<String> {
+        let mut constants = HashSet::new();
+        self.constants_in(&mut constants, &mut HashSet::new());
+        constants
+    }
+
+    /// Adds the names of the axioms and opaque declarations occurring in `self` to `constants`,
+    /// the terms of `visited` being skipped.
+    #[allow(clippy::mutable_key_type)]
+    fn constants_in(self, constants: &mut HashSet<String>, visited: &mut HashSet<Self>) {
+        if !visited.insert(self) {
+            return;
+        }
+
+        match *self {
+            App(t1, t2) | Abs(t1, t2) | Prod(t1, t2) => {
+                t1.constants_in(constants, visited);
+                t2.constants_in(constants, visited);
+            },
+            Let(type_, value, body) => {
+                type_.constants_in(constants, visited);
+                value.constants_in(constants, visited);
+                body.constants_in(constants, visited);
+            },
+            Decl(decl) => match decl.decl.2 {
+                Some(name) => {
+                    constants.insert(name.to_owned());
+                },
+                None => decl.decl.0.constants_in(constants, visited),
+            },
+            Axiom(axiom, _) => {
+                constants.insert(axiom.to_string());
+            },
+            _ => (),
+        }
+    }
+
+    /// Returns the abstraction of `self` over the constant `constant`, whose occurrences are
+    /// replaced with the variable bound by the abstraction.
+    ///
+    /// The declarations instantiated in `self` which refer to `constant` are unfolded. If
+    /// `constant` is not a constant, `self` is returned as is.
+    #[inline]
+    #[must_use]
+    pub fn abs_constant(self, constant: Self, arena: &mut Arena<'arena>) -> Self {
+        let Axiom(AxiomKind::Constant(axiom), _) = *constant else {
+            return self;
+        };
+
+        let type_ = axiom.get_type();
+        let body = self.abstract_constant(constant, type_, 0, &mut HashMap::new(), arena);
+
+        type_.abs(body, arena).set_name(axiom.name(), arena)
+    }
+
+    /// Returns the term `self` where the occurrences of the constant `constant` of type `type_`
+    /// are replaced with the variable tracked by `depth`.
+    ///
+    /// The results are memoised in `memo`, for each pair of a term and a depth.
+    #[allow(clippy::mutable_key_type)]
+    fn abstract_constant(
+        self,
+        constant: Self,
+        type_: Self,
+        depth: usize,
+        memo: &mut HashMap<(Self, usize), Self>,
+        arena: &mut Arena<'arena>,
+    ) -> Self {
+        if self == constant {
+            return Term::var((depth + 1).into(), type_, arena);
+        }
+
+        if let Some(&term) = memo.get(&(self, depth)) {
+            return term;
+        }
+
+        let term = match *self {
+            Var(i, var_type) => {
+                let var_type = var_type.abstract_constant(constant, type_, depth, memo, arena);
+                Term::var(i, var_type, arena)
+            },
+            App(t1, t2) => {
+                let t1 = t1.abstract_constant(constant, type_, depth, memo, arena);
+                let t2 = t2.abstract_constant(constant, type_, depth, memo, arena);
+                t1.app(t2, arena)
+            },
+            Abs(arg_type, body) => {
+                let arg_type = arg_type.abstract_constant(constant, type_, depth, memo, arena);
+                let body = body.abstract_constant(constant, type_, depth + 1, memo, arena);
+                arg_type.abs(body, arena).inherit_name(self)
+            },
+            Prod(arg_type, body) => {
+                let arg_type = arg_type.abstract_constant(constant, type_, depth, memo, arena);
+                let body = body.abstract_constant(constant, type_, depth + 1, memo, arena);
+                arg_type.prod(body, arena).inherit_name(self)
+            },
+            Let(let_type, value, body) => {
+                let let_type = let_type.abstract_constant(constant, type_, depth, memo, arena);
+                let value = value.abstract_constant(constant, type_, depth, memo, arena);
+                let body = body.abstract_constant(constant, type_, depth + 1, memo, arena);
+                Term::let_(let_type, value, body, arena).inherit_name(self)
+            },
+            // opaque declarations are unfolded too, as the constant cannot be abstracted otherwise
+            Decl(decl) if decl.decl.0.has_constant(constant) => {
+                decl.get_term(arena).abstract_constant(constant, type_, depth, memo, arena)
+            },
+            _ => self,
+        };
+
+        memo.insert((self, depth), term);
+        term
+    }
+
+    /// Indicates whether `self` is closed, that is, whether none of its variables refers to a
+    /// binder outside of it. Should it be the case, the term is marked as such.
+    #[inline]
+    #[must_use]
+    pub fn is_closed(self) -> bool {
+        let closed = !self.has_var_above(0);
+
+        if closed {
+            self.set_as_closed();
+        }
+
+        closed
+    }
+
+    /// Indicates whether a variable of de Bruijn index larger than `depth` occurs in `self`.
+    fn has_var_above(self, depth: usize) -> bool {
+        if self.is_certainly_closed() {
+            return false;
+        }
+
+        match *self {
+            Var(i, _) => usize::from(i) > depth,
+            App(t1, t2) => t1.has_var_above(depth) || t2.has_var_above(depth),
+            Abs(arg_type, body) | Prod(arg_type, body) => arg_type.has_var_above(depth) || body.has_var_above(depth + 1),
+            Let(type_, value, body) => type_.has_var_above(depth) || value.has_var_above(depth) || body.has_var_above(depth + 1),
+            _ => false,
+        }
+    }
+
     /// Substitutes all level variables in `self` according to the correspondence given by
     /// `univs`.
     ///
-    /// This function would be safe to use from outside the kernel, but would serve no purpose as
-    /// level variables there can only appear behind a Declaration, which prevents the access to
-    /// the underlying Term.
-    pub(crate) fn substitute_univs(self, univs: &[Level<'arena>], arena: &mut Arena<'arena>) -> Self {
+    /// Outside of the kernel, this is mostly useful to elaborators, which represent the universe
+    /// levels they have to infer by level variables.
+    ///
+    /// # Panics
+    /// This function panics if a level variable of `self` has no correspondence in `univs`.
+    #[inline]
+    #[must_use]
+    pub fn substitute_univs(self, univs: &[Level<'arena>], arena: &mut Arena<'arena>) -> Self {
         match *self {
             Axiom(ax, lvl) => {
                 let lvl = lvl.iter().map(|l| l.substitute(univs, arena)).collect::<Vec<_>>();
@@ -145,13 +370,20 @@ impl<'arena> Term<'arena> {
             Abs(u1, u2) => {
                 let u1 = u1.substitute_univs(univs, arena);
                 let u2 = u2.substitute_univs(univs, arena);
-                u1.abs(u2, arena)
+                u1.abs(u2, arena).inherit_name(self)
             },
 
             Prod(u1, u2) => {
                 let u1 = u1.substitute_univs(univs, arena);
                 let u2 = u2.substitute_univs(univs, arena);
-                u1.prod(u2, arena)
+                u1.prod(u2, arena).inherit_name(self)
+            },
+
+            Let(type_, value, body) => {
+                let type_ = type_.substitute_univs(univs, arena);
+                let value = value.substitute_univs(univs, arena);
+                let body = body.substitute_univs(univs, arena);
+                Term::let_(type_, value, body, arena).inherit_name(self)
             },
 
             Decl(decl) => {
@@ -163,6 +395,8 @@ impl<'arena> Term<'arena> {
                 let inst = InstantiatedDeclaration::instantiate(decl.decl, params, arena);
                 Term::decl(inst, arena)
             },
+
+            NatLit(_) => self,
         }
     }
 
@@ -197,25 +431,26 @@ impl<'arena> Term<'arena> {
                         },
                         _ => self,
                     },
+                    Let(_, value, body) => body.substitute(value, 1, arena).whnf(arena),
                     _ => self,
                 })
         })
     }
 
     /// Tests whether a term is computationally relevant.
+    ///
+    /// A term is irrelevant when it is a proof, that is, when its type lives in `Prop`. Terms that
+    /// cannot be typed are conservatively considered relevant.
     #[inline]
     pub(crate) fn is_relevant(self, arena: &mut Arena<'arena>) -> bool {
-        self.get_relevance_or_try_init(|| match *self {
-            Var(_, ty) => ty.is_def_eq(Term::sort_usize(0, arena), arena).map_or(true, |()| false),
-            App(t, _) => t.is_relevant(arena),
-            Abs(_, t) => t.is_relevant(arena),
-            Decl(d) => d.get_term(arena).is_relevant(arena),
-            Axiom(ax, lvl) => ax
-                .get_type(arena)
-                .substitute_univs(lvl, arena)
-                .is_def_eq(Term::sort_usize(0, arena), arena)
-                .map_or(true, |()| false),
-            _ => true,
+        self.get_relevance_or_try_init(|| {
+            let Ok(sort) = self.infer(arena).and_then(|type_| type_.infer(arena)) else {
+                return true;
+            };
+
+            let zero = Level::zero(arena);
+
+            !matches!(*sort.whnf(arena), Sort(level) if level.is_eq(zero, arena))
         })
     }
 }
@@ -515,11 +750,10 @@ mod tests {
 
     #[test]
     fn relevance() {
-        use crate::axiom::false_::False::False;
-        use crate::axiom::Axiom;
+        use crate::memory::arena::use_arena_with_axioms;
 
-        use_arena(|arena| {
-            let false_ = Term::axiom(Axiom::False(False), &[], arena);
+        use_arena_with_axioms(|arena| {
+            let false_ = arena.get_binding("False").unwrap();
             let tt1 = false_.abs(Term::var(1.into(), false_, arena), arena);
             assert!(!tt1.is_relevant(arena));
         });
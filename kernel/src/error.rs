@@ -2,7 +2,7 @@
 
 use derive_more::{Display, From};
 
-use crate::memory::{declaration, level, term};
+use crate::memory::{declaration, inductive, level, term};
 use crate::trace::Trace;
 use crate::type_checker;
 
@@ -61,3 +61,6 @@ pub type ResultDecl<'arena> = Result<'arena, declaration::Declaration<'arena>>;
 
 /// The type of results yielded by the kernel (specialised to instantiated declarations).
 pub type ResultInstantiatedDecl<'arena> = Result<'arena, declaration::InstantiatedDeclaration<'arena>>;
+
+/// The type of results yielded by the kernel (specialised to inductive types).
+pub type ResultInductive<'arena> = Result<'arena, inductive::Inductive<'arena>>;
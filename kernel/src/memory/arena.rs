@@ -7,10 +7,16 @@ use std::collections::{HashMap, HashSet};
 
 use bumpalo::Bump;
 
-use super::declaration::Declaration;
+use super::declaration::{Declaration, InstantiatedDeclaration};
+use super::inductive::Inductive;
 use super::level::Level;
+use super::term::pretty::{Fixity, Notation};
+use super::term::Payload::Sort;
 use super::term::Term;
+use crate::axiom::constant::Constant;
 use crate::axiom::Axiom;
+use crate::error::{Error, Result};
+use crate::type_checker::ErrorKind;
 
 /// A comprehensive memory management unit for terms.
 ///
@@ -45,13 +51,25 @@ pub struct Arena<'arena> {
     // invariant.
     pub(super) hashcons_terms: HashSet<&'arena super::term::Node<'arena>>,
     pub(super) hashcons_decls: HashSet<&'arena super::declaration::Node<'arena>>,
+    pub(super) hashcons_inductives: HashSet<&'arena super::inductive::Node<'arena>>,
     pub(super) hashcons_levels: HashMap<&'arena super::level::Payload<'arena>, super::level::Level<'arena>>,
 
     named_decls: HashMap<&'arena str, Declaration<'arena>>,
     named_terms: HashMap<&'arena str, Term<'arena>>,
+    pub(super) named_inductives: HashMap<&'arena str, Inductive<'arena>>,
+
+    /// The notations declared so far, from the oldest one.
+    notations: Vec<Notation<'arena>>,
+
+    /// The number of constants created so far.
+    constants: usize,
 
     /// Hash maps used to speed up certain algorithms. See also `OnceCell`s in [`Term`]
     pub(super) mem_subst: HashMap<(Term<'arena>, Term<'arena>, usize), Term<'arena>>,
+
+    /// Closed terms pinned in the arena (see [`Arena::pin`]), along with their position.
+    pub(super) pinned_terms: Vec<Term<'arena>>,
+    pub(super) pins: HashMap<Term<'arena>, usize>,
 }
 
 /// Calls function `f` on a newly-created arena.
@@ -96,12 +114,21 @@ impl<'arena> Arena<'arena> {
 
             hashcons_terms: HashSet::new(),
             hashcons_decls: HashSet::new(),
+            hashcons_inductives: HashSet::new(),
             hashcons_levels: HashMap::new(),
 
             named_decls: HashMap::new(),
             named_terms: HashMap::new(),
+            named_inductives: HashMap::new(),
+
+            notations: Vec::new(),
+
+            constants: 0,
 
             mem_subst: HashMap::new(),
+
+            pinned_terms: Vec::new(),
+            pins: HashMap::new(),
         }
     }
 
@@ -112,6 +139,13 @@ impl<'arena> Arena<'arena> {
         self.alloc.alloc_slice_copy(slice)
     }
 
+    /// Stores a slice of digits in the arena.
+    ///
+    /// This is used by [natural number literals](super::term::Payload::NatLit).
+    pub(crate) fn store_digit_slice(&self, slice: &[u32]) -> &'arena [u32] {
+        self.alloc.alloc_slice_copy(slice)
+    }
+
     /// Stores a string in the arena.
     ///
     /// This is typically done to ensure strings live long enough when manipulating them.
@@ -130,12 +164,71 @@ impl<'arena> Arena<'arena> {
     #[inline]
     pub fn bind_decl(&mut self, name: &str, decl: Declaration<'arena>) {
         let name = self.store_name(name);
+        self.insert_decl(name, decl);
+    }
+
+    /// Binds a declaration to a given name, as an opaque declaration
+    /// whose instances are never unfolded. Only proofs may be opaque: their value is irrelevant.
+    ///
+    /// # Errors
+    /// Fails, without binding anything, if `decl` is not a proof (see [`Declaration::check_proof`]).
+    #[inline]
+    pub fn bind_opaque(&mut self, name: &str, decl: Declaration<'arena>) -> Result<'arena, ()> {
+        decl.check_proof(self)?;
+
+        let name = self.store_name(name);
+        self.insert_decl(name, Declaration(decl.0, decl.1, Some(name)));
+        Ok(())
+    }
+
+    /// Binds a declaration to the full name `name`. A declaration without universe variables is
+    /// also bound as a term: its own term, or its only instance if it is opaque, so that it is
+    /// never unfolded.
+    fn insert_decl(&mut self, name: &'arena str, decl: Declaration<'arena>) {
         self.named_decls.insert(name, decl);
-        if let Declaration(term, 0) = decl {
-            self.bind(name, term);
+
+        if decl.vars() == 0 {
+            let term = if decl.is_opaque() {
+                let instance = InstantiatedDeclaration::instantiate(decl, &[], self);
+                Term::decl(instance, self)
+            } else {
+                decl.0
+            };
+
+            self.named_terms.insert(name, term);
         }
     }
 
+    /// Indicates whether a term or a declaration is bound to the full name `name`.
+    #[inline]
+    #[must_use]
+    pub fn is_bound(&self, name: &str) -> bool {
+        self.named_terms.contains_key(name) || self.named_decls.contains_key(name)
+    }
+
+    /// Returns the full names of the terms and declarations bound so far, in alphabetical order.
+    #[inline]
+    #[must_use]
+    pub fn names(&self) -> Vec<&'arena str> {
+        let mut names: Vec<&str> = self.named_terms.keys().chain(self.named_decls.keys()).copied().collect();
+
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    /// Returns the type of the term or declaration bound to the full name `name`, the universe
+    /// variables of a declaration being left as is.
+    #[inline]
+    pub fn get_type(&mut self, name: &str) -> Option<Term<'arena>> {
+        let term = match self.named_terms.get(name) {
+            Some(&term) => term,
+            None => self.named_decls.get(name)?.0,
+        };
+
+        term.infer(self).ok()
+    }
+
     /// Retrieves the binding of a given name, if one exists.
     #[inline]
     #[must_use]
@@ -149,6 +242,101 @@ impl<'arena> Arena<'arena> {
     pub fn get_binding_decl(&self, name: &str) -> Option<Declaration<'arena>> {
         self.named_decls.get(name).copied()
     }
+
+    /// Binds `name` to a new constant standing for an arbitrary inhabitant of `type_`. Unlike the
+    /// constant of an axiom, it is bound as a term, over which the terms using it can later be
+    /// abstracted (see [`Term::abs_constant`]).
+    ///
+    /// # Errors
+    /// If `type_` is not a type, yields the corresponding error.
+    #[inline]
+    pub fn declare_variable(&mut self, name: &str, type_: Term<'arena>) -> Result<'arena, ()> {
+        self.check_is_type(type_)?;
+
+        let name = self.store_name(name);
+        let constant = Constant::new(name, type_, self.constants);
+        let constant = Term::axiom(Axiom::Constant(constant), &[], self);
+        self.constants += 1;
+
+        self.named_terms.insert(name, constant);
+
+        Ok(())
+    }
+
+    /// Binds `name` to a new constant standing for an arbitrary inhabitant of `type_`, whose
+    /// universe variables are those of the constant. The constant never reduces.
+    ///
+    /// # Errors
+    /// If `type_` is not a type, yields the corresponding error.
+    #[inline]
+    pub fn declare_axiom(&mut self, name: &str, type_: Declaration<'arena>) -> Result<'arena, ()> {
+        let Declaration(type_, univs, _) = type_;
+
+        self.check_is_type(type_)?;
+
+        let levels: Vec<_> = (0..univs).map(|index| Level::var(index, self)).collect();
+
+        let constant = Constant::new(self.store_name(name), type_, self.constants);
+        let constant = Term::axiom(Axiom::Constant(constant), &levels, self);
+        self.constants += 1;
+
+        self.bind_decl(name, Declaration::new(constant, univs));
+
+        Ok(())
+    }
+
+    /// Checks that `type_` is a type, that is, that its type is a universe.
+    fn check_is_type(&mut self, type_: Term<'arena>) -> Result<'arena, ()> {
+        let sort = type_.infer(self)?.whnf(self);
+
+        if !matches!(*sort, Sort(_)) {
+            return Err(Error::new(ErrorKind::NotUniverse(sort).into()));
+        }
+
+        Ok(())
+    }
+
+    /// Unbinds the term or declaration bound to the full name `name`, if any. The terms and
+    /// declarations using it are left as is.
+    #[inline]
+    pub fn unbind(&mut self, name: &str) {
+        self.named_terms.remove(name);
+        self.named_decls.remove(name);
+    }
+
+    /// Declares a notation, under which `symbol` stands for the term or declaration bound to `name`,
+    /// applied to `hidden` arguments left implicit and to the operands of the notation.
+    ///
+    /// A previous notation with the same symbol and the same number of operands is replaced.
+    /// Returns `false`, without declaring anything, if `name` is not bound.
+    #[inline]
+    pub fn add_notation(&mut self, symbol: &str, fixity: Fixity, precedence: usize, name: &str, hidden: usize) -> bool {
+        let Some(head) = self.get_binding(name).or_else(|| self.get_binding_decl(name).map(|decl| decl.0)) else {
+            return false;
+        };
+
+        let notation = Notation {
+            symbol: self.store_name(symbol),
+            fixity,
+            precedence,
+            name: self.store_name(name),
+            hidden,
+            head,
+        };
+
+        self.notations
+            .retain(|declared| declared.symbol != notation.symbol || declared.arity() != notation.arity());
+        self.notations.push(notation);
+
+        true
+    }
+
+    /// Returns the notations declared so far, from the oldest one.
+    #[inline]
+    #[must_use]
+    pub fn notations(&self) -> &[Notation<'arena>] {
+        &self.notations
+    }
 }
 
 /// This macro generates two types, $dweller and Node, parametrised by a lifetime. These types are
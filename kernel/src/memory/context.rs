@@ -0,0 +1,156 @@
+//! Typing contexts, used to manipulate open terms.
+//!
+//! A [`Context`] is a list of named hypotheses `x1: A1, ..., xn: An`, where each type `Ai` lives in
+//! the context made of the hypotheses preceding it. The free variables of a term built or checked
+//! in a context refer to these hypotheses, the last one having de Bruijn index 1.
+
+use super::arena::Arena;
+use super::term::Payload::{Abs, App, Let, Prod, Sort, Var};
+use super::term::{DeBruijnIndex, Term};
+use crate::error::{Error, Result, ResultTerm};
+use crate::trace::{Trace, TraceableError};
+use crate::type_checker::ErrorKind;
+
+/// A list of named hypotheses under which terms can be built and type checked.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Context<'arena> {
+    /// The hypotheses, from the outermost to the innermost one. Each type lives in the context
+    /// made of the hypotheses preceding it.
+    hypotheses: Vec<(&'arena str, Term<'arena>)>,
+}
+
+impl<'arena> Context<'arena> {
+    /// Creates an empty context.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            hypotheses: Vec::new(),
+        }
+    }
+
+    /// Returns the number of hypotheses in the context.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.hypotheses.len()
+    }
+
+    /// Indicates whether the context has no hypothesis.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.hypotheses.is_empty()
+    }
+
+    /// Returns an iterator over the hypotheses of the context, from the outermost to the innermost
+    /// one. Each type lives in the context made of the hypotheses preceding it.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &(&'arena str, Term<'arena>)> {
+        self.hypotheses.iter()
+    }
+
+    /// Adds the hypothesis `name: type_` at the end of the context.
+    ///
+    /// # Errors
+    /// If `type_` is not a well-formed type in the context, yields the corresponding error.
+    #[inline]
+    pub fn push(&mut self, name: &str, type_: Term<'arena>, arena: &mut Arena<'arena>) -> Result<'arena, ()> {
+        let type_ = self.bind(type_, arena)?;
+        let sort = type_.infer(arena)?.whnf(arena);
+
+        if !matches!(*sort, Sort(_)) {
+            return Err(Error::new(ErrorKind::NotUniverse(sort).into()));
+        }
+
+        self.hypotheses.push((arena.store_name(name), type_));
+        Ok(())
+    }
+
+    /// Removes the last hypothesis of the context and returns it, if any.
+    ///
+    /// The returned type lives in the remaining context.
+    #[inline]
+    pub fn pop(&mut self) -> Option<(&'arena str, Term<'arena>)> {
+        self.hypotheses.pop()
+    }
+
+    /// Returns the name and the type of the hypothesis of de Bruijn index `index`, if any.
+    ///
+    /// The returned type lives in the whole context.
+    #[inline]
+    pub fn get(&self, index: DeBruijnIndex, arena: &mut Arena<'arena>) -> Option<(&'arena str, Term<'arena>)> {
+        let (name, type_) = self.lift(index, arena)?;
+
+        Some((name, self.bind(type_, arena).ok()?))
+    }
+
+    /// Returns the variable referring to the innermost hypothesis named `name`, if any.
+    #[inline]
+    pub fn get_var(&self, name: &str, arena: &mut Arena<'arena>) -> Option<Term<'arena>> {
+        let position = self.hypotheses.iter().rposition(|&(hyp_name, _)| hyp_name == name)?;
+        let index = (self.len() - position).into();
+        let (_, type_) = self.lift(index, arena)?;
+
+        Some(Term::var(index, type_, arena))
+    }
+
+    /// Returns the name and the type of the hypothesis of de Bruijn index `index`, where the type
+    /// is shifted to live in the whole context, in the same way as the [builders](super::term::builder) do.
+    fn lift(&self, index: DeBruijnIndex, arena: &mut Arena<'arena>) -> Option<(&'arena str, Term<'arena>)> {
+        let index = usize::from(index);
+        let position = self.len().checked_sub(index)?;
+        let &(name, type_) = self.hypotheses.get(position).filter(|_| index > 0)?;
+
+        Some((name, type_.shift(index, 0, arena)))
+    }
+
+    /// Returns the term `t`, where free variables are annotated with the types of the hypotheses
+    /// they refer to.
+    ///
+    /// # Errors
+    /// If `t` has a free variable which is not bound in the context, yields an error.
+    pub(crate) fn bind(&self, t: Term<'arena>, arena: &mut Arena<'arena>) -> ResultTerm<'arena> {
+        self.bind_at(t, 0, arena)
+    }
+
+    /// Annotates the variables of `t` which are free at depth `depth`.
+    fn bind_at(&self, t: Term<'arena>, depth: usize, arena: &mut Arena<'arena>) -> ResultTerm<'arena> {
+        if t.is_certainly_closed() {
+            return Ok(t);
+        }
+
+        match *t {
+            Var(index, _) if usize::from(index) > depth => {
+                let (_, type_) = self
+                    .lift((usize::from(index) - depth).into(), arena)
+                    .ok_or_else(|| Error::new(ErrorKind::UnboundVariable(t).into()))?;
+
+                let type_ = type_.shift(depth, 0, arena);
+                Ok(Term::var(index, type_, arena))
+            },
+            App(t1, t2) => {
+                let t1 = self.bind_at(t1, depth, arena).trace_err(Trace::Left)?;
+                let t2 = self.bind_at(t2, depth, arena).trace_err(Trace::Right)?;
+                Ok(t1.app(t2, arena))
+            },
+            Abs(arg_type, body) => {
+                let arg_type = self.bind_at(arg_type, depth, arena).trace_err(Trace::Left)?;
+                let body = self.bind_at(body, depth + 1, arena).trace_err(Trace::Right)?;
+                Ok(arg_type.abs(body, arena).inherit_name(t))
+            },
+            Prod(arg_type, body) => {
+                let arg_type = self.bind_at(arg_type, depth, arena).trace_err(Trace::Left)?;
+                let body = self.bind_at(body, depth + 1, arena).trace_err(Trace::Right)?;
+                Ok(arg_type.prod(body, arena).inherit_name(t))
+            },
+            Let(type_, value, body) => {
+                let type_ = self.bind_at(type_, depth, arena).trace_err(Trace::Left)?;
+                let value = self.bind_at(value, depth, arena).trace_err(Trace::Middle)?;
+                let body = self.bind_at(body, depth + 1, arena).trace_err(Trace::Right)?;
+                Ok(Term::let_(type_, value, body, arena).inherit_name(t))
+            },
+            _ => Ok(t),
+        }
+    }
+}
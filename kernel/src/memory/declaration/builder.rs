@@ -27,6 +27,11 @@ pub enum ErrorKind<'arena> {
     /// The declaration is unknown
     #[display(fmt = "unknown declaration {_0}")]
     UnknownDeclaration(&'arena str),
+
+    /// The name of the declaration refers to several names, when names are resolved before
+    /// reaching the kernel
+    #[display(fmt = "ambiguous declaration {_0}, which may refer to {_1}")]
+    AmbiguousDeclaration(String, String),
 }
 
 /// The trait of builders producing declarations.
@@ -107,7 +112,10 @@ pub fn instance<'build, F: BuilderTrait<'build>, V: level::VecBuilderTrait<'buil
 /// levels `levels`.
 #[inline]
 #[must_use]
-pub fn var<'build, V: level::VecBuilderTrait<'build>>(name: &'build str, levels: V) -> impl InstantiatedBuilderTrait<'build> {
+pub fn var<'name, 'build: 'name, V: level::VecBuilderTrait<'build> + 'name>(
+    name: &'name str,
+    levels: V,
+) -> impl InstantiatedBuilderTrait<'build> + 'name {
     move |arena, env| {
         let decl = arena
             .get_binding_decl(name)
@@ -17,9 +17,13 @@ pub mod builder;
 ///
 /// Declarations can be instantiated to create [`InstantiatedDeclaration`]s, which can in turn be
 /// incorporated into [`Term`]s. No variable may remain in the instantiated declaration.
+///
+/// A declaration may also be opaque, as theorems are, in which case it records its name: its
+/// instances are then never unfolded, and stand for uninterpretable constants during conversion
+/// and reduction.
 #[derive(Copy, Clone, Debug, Display, Eq, PartialEq, Hash)]
 #[display(fmt = "{_0}")]
-pub struct Declaration<'arena>(pub(crate) Term<'arena>, pub(crate) usize);
+pub struct Declaration<'arena>(pub(crate) Term<'arena>, pub(crate) usize, pub(crate) Option<&'arena str>);
 
 super::arena::new_dweller!(InstantiatedDeclaration, Header, Payload);
 
@@ -42,25 +46,71 @@ struct Header<'arena> {
 impl<'arena> fmt::Display for InstantiatedDeclaration<'arena> {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        if let Some(term) = self.0.header.term.get() {
-            write!(f, "{term}")
-        } else {
-            write!(f, "({}).{{", self.0.payload.decl)?;
+        // opaque declarations are displayed under their name, since they are never unfolded
+        match self.0.payload.decl.2 {
+            Some(name) if self.0.payload.params.is_empty() => return write!(f, "{name}"),
+            Some(name) => write!(f, "{name}.{{")?,
+            None => {
+                if let Some(term) = self.0.header.term.get() {
+                    return write!(f, "{term}");
+                }
+
+                write!(f, "({}).{{", self.0.payload.decl)?;
+            },
+        }
 
-            let mut iter = self.0.payload.params.iter();
+        let mut iter = self.0.payload.params.iter();
 
-            iter.next().map_or(Ok(()), |level| write!(f, "{level}"))?;
-            iter.try_for_each(|level| write!(f, ", {level}"))?;
+        iter.next().map_or(Ok(()), |level| write!(f, "{level}"))?;
+        iter.try_for_each(|level| write!(f, ", {level}"))?;
 
-            write!(f, "}}")
-        }
+        write!(f, "}}")
     }
 }
 
 impl<'arena> Declaration<'arena> {
     /// Creates a declaration from a pair of arguments.
     pub(crate) const fn new(term: Term<'arena>, vars: usize) -> Self {
-        Self(term, vars)
+        Self(term, vars, None)
+    }
+
+    /// Indicates whether the declaration is opaque, that is, whether its instances are never
+    /// unfolded (see [`Arena::bind_opaque`]).
+    #[inline]
+    #[must_use]
+    pub const fn is_opaque(self) -> bool {
+        self.2.is_some()
+    }
+
+    /// Returns the number of universe variables of the declaration.
+    #[inline]
+    #[must_use]
+    pub const fn vars(self) -> usize {
+        self.1
+    }
+
+    /// Returns the names of the axioms and opaque declarations occurring in the term of the
+    /// declaration (see [`Term::constants`]).
+    #[inline]
+    #[must_use]
+    pub fn constants(self) -> std::collections::HashSet<String> {
+        self.0.constants()
+    }
+
+    /// Indicates whether the constant `constant` occurs in the term of the declaration (see
+    /// [`Term::has_constant`]).
+    #[inline]
+    #[must_use]
+    pub fn has_constant(self, constant: Term<'arena>) -> bool {
+        self.0.has_constant(constant)
+    }
+
+    /// Returns the declaration whose term is abstracted over the constant `constant` (see
+    /// [`Term::abs_constant`]). The universe variables and the opacity of the declaration are kept.
+    #[inline]
+    #[must_use]
+    pub fn abs_constant(self, constant: Term<'arena>, arena: &mut Arena<'arena>) -> Self {
+        Self(self.0.abs_constant(constant, arena), self.1, self.2)
     }
 }
 
@@ -87,6 +137,20 @@ impl<'arena> InstantiatedDeclaration<'arena> {
         }
     }
 
+    /// Returns the declaration being instantiated.
+    #[inline]
+    #[must_use]
+    pub const fn decl(self) -> Declaration<'arena> {
+        self.0.payload.decl
+    }
+
+    /// Returns the universe levels the declaration is instantiated with.
+    #[inline]
+    #[must_use]
+    pub const fn params(self) -> &'arena [Level<'arena>] {
+        self.0.payload.params
+    }
+
     /// Returns the term linked to a definition in a given environment.
     #[inline]
     pub fn get_term(self, arena: &mut Arena<'arena>) -> Term<'arena> {
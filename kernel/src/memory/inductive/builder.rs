@@ -0,0 +1,172 @@
+//! A collection of safe functions to build [`Inductive`]s.
+//!
+//! This module provides a way of building inductive types via closures, in the same fashion as
+//! [terms](crate::memory::term::builder) and [declarations](crate::memory::declaration::builder).
+//!
+//! Building an inductive type binds its name while building its constructors, so that they may
+//! refer to it. The universe variables of the inductive type are bound everywhere.
+//!
+//! Errors are traced as if the inductive type was a pair whose left element is the arity, and
+//! whose right element is the list of constructors, itself represented as a right-nested sequence
+//! of pairs: for instance, the constructor of index `2` is reached through the trace
+//! `[Right, Right, Right, Left]` (read from the root).
+//!
+//! [Structures](structure) are traced as the inductive types they stand for: their parameters
+//! are reached through the arity, while their fields are reached through their constructor.
+
+use super::Inductive;
+use crate::axiom::inductive::Telescope;
+use crate::error::{Error, Result, ResultInductive};
+use crate::memory::arena::Arena;
+use crate::memory::level::builder as level;
+use crate::memory::level::Level;
+use crate::memory::term::builder as term;
+use crate::memory::term::Payload::Sort;
+use crate::memory::term::Term;
+use crate::trace::{Trace, TraceableError};
+use crate::type_checker::ErrorKind;
+
+/// The trait of builders producing inductive types.
+///
+/// Like declarations, inductive types cannot be declared locally, hence the only argument of the
+/// closure is the arena.
+#[allow(clippy::module_name_repetitions)]
+pub trait BuilderTrait<'build> = for<'arena> FnOnce(&mut Arena<'arena>) -> ResultInductive<'arena>;
+
+impl<'arena> Arena<'arena> {
+    /// Returns the inductive type built from the given closure.
+    ///
+    /// # Errors
+    /// If the inductive type could not be built, yields an error indicating the reason.
+    #[inline]
+    pub fn build_inductive<'build, F: BuilderTrait<'build>>(&mut self, f: F) -> ResultInductive<'arena> {
+        f(self)
+    }
+}
+
+/// Appends to `res` the trace leading to the constructor of index `index`.
+pub(crate) fn trace_constructor<T>(res: Result<'_, T>, index: usize) -> Result<'_, T> {
+    (0..index)
+        .fold(res.trace_err(Trace::Left), |res, _| res.trace_err(Trace::Right))
+        .trace_err(Trace::Right)
+}
+
+/// Returns a builder creating the inductive type `name`, where universe variables are described
+/// by `vars`.
+///
+/// The `arity` is the complete type of the inductive type, including its `params` first
+/// arguments, which are the uniform parameters of the inductive type. Likewise, the type of every
+/// constructor must be a product over the same parameters.
+#[inline]
+pub fn inductive<'build, F: term::BuilderTrait<'build>>(
+    name: &'build str,
+    vars: &[&'build str],
+    params: usize,
+    arity: F,
+    constructors: Vec<(&'build str, F)>,
+) -> impl BuilderTrait<'build> {
+    let univs = vars.len();
+    let lvl_env: level::Environment = vars.iter().enumerate().map(|(n, name)| (*name, n)).collect();
+
+    move |arena| {
+        let arity = arity(arena, &term::Environment::new(), &lvl_env, 0.into()).trace_err(Trace::Left)?;
+        let env = term::Environment::new().update(name, (0.into(), arity));
+
+        let constructors = constructors
+            .into_iter()
+            .enumerate()
+            .map(|(index, (constructor, type_))| {
+                trace_constructor(type_(arena, &env, &lvl_env, 1.into()), index).map(|type_| (constructor, type_))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Inductive::declare(name, univs, params, arity, &constructors, arena))
+    }
+}
+
+/// Returns a builder creating the structure `name`, where universe variables are described by
+/// `vars`.
+///
+/// A structure is an inductive type without indices, whose single constructor `name.mk` takes the
+/// `params` of the structure, followed by its `fields`. Each field may refer to the parameters and
+/// to the previous fields, but not to the structure itself. When no `sort` is given, the structure
+/// lives in the smallest universe containing both `Type` and the types of its fields.
+#[inline]
+pub fn structure<'build, F: term::BuilderTrait<'build>>(
+    name: &'build str,
+    vars: &[&'build str],
+    params: Vec<(&'build str, F)>,
+    sort: Option<F>,
+    fields: Vec<(&'build str, F)>,
+) -> impl BuilderTrait<'build> {
+    let univs = vars.len();
+    let lvl_env: level::Environment = vars.iter().enumerate().map(|(n, name)| (*name, n)).collect();
+
+    move |arena| {
+        let nb_params = params.len();
+        let mut env = term::Environment::new();
+        let mut binders = Vec::new();
+
+        // The binders are built once and for all, as they never mention the structure: their
+        // types are the same in the arity and under the binder of the constructor.
+        for (position, (binder, type_)) in params.into_iter().chain(fields).enumerate() {
+            let type_ = type_(arena, &env, &lvl_env, position.into()).trace_err(Trace::Left);
+
+            let type_ = if position < nb_params {
+                (0..position).fold(type_, |res, _| res.trace_err(Trace::Right)).trace_err(Trace::Left)?
+            } else {
+                let type_ = (0..position).fold(type_, |res, _| res.trace_err(Trace::Right));
+                trace_constructor(type_, 0)?
+            };
+
+            if binder != "_" {
+                env.insert(binder, (position.into(), type_));
+            }
+
+            binders.push(type_);
+        }
+
+        let sort = match sort {
+            Some(sort) => {
+                let sort = sort(arena, &term::Environment::new(), &lvl_env, 0.into());
+                (0..nb_params).fold(sort, |res, _| res.trace_err(Trace::Right)).trace_err(Trace::Left)?
+            },
+            None => {
+                let one = Level::from(1, arena);
+                let level = binders.iter().enumerate().skip(nb_params).try_fold(one, |level, (position, type_)| {
+                    let res = field_level(*type_, arena).trace_err(Trace::Left);
+                    let field_level = trace_constructor((0..position).fold(res, |res, _| res.trace_err(Trace::Right)), 0)?;
+
+                    Ok(level.max(field_level, arena))
+                })?;
+
+                Term::sort(level, arena)
+            },
+        };
+
+        let mut arity = Telescope::new();
+        binders.iter().take(nb_params).for_each(|type_| arity.push(*type_));
+        let arity = arity.close_prods(0, sort, arena);
+
+        // The constructor type lives under a binder standing for the structure.
+        let mut constructor = Telescope::new();
+        constructor.push(arity);
+        binders.into_iter().for_each(|type_| constructor.push(type_));
+        let structure = constructor.var(0, arena);
+        let conclusion = constructor.apply(structure, 1..=nb_params, arena);
+        let constructor_type = constructor.close_prods(1, conclusion, arena);
+
+        let constructor_name = format!("{name}.mk");
+        Ok(Inductive::declare(name, univs, nb_params, arity, &[(&constructor_name, constructor_type)], arena))
+    }
+}
+
+/// Returns the universe level `u` such that the type `type_` lives in `Sort u`.
+fn field_level<'arena>(type_: Term<'arena>, arena: &mut Arena<'arena>) -> Result<'arena, Level<'arena>> {
+    let sort = type_.infer(arena)?.whnf(arena);
+
+    match *sort {
+        Sort(level) => Ok(level),
+        _ => Err(Error::new(ErrorKind::NotUniverse(sort).into())),
+    }
+}
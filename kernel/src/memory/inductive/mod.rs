@@ -0,0 +1,266 @@
+//! User-declared inductive types.
+//!
+//! An inductive type is described by its arity (the type of the type former), the number of
+//! its uniform parameters, and a list of constructors. Once [checked](Inductive::check), the
+//! kernel automatically derives the types of its constructors and of its recursor, as well as the
+//! associated ι-reduction rule (see [`crate::axiom::inductive`]).
+
+use core::cell::OnceCell;
+use core::fmt;
+
+use super::arena::Arena;
+use super::declaration::Declaration;
+use super::level::Level;
+use super::term::Term;
+use crate::axiom::{self, Axiom};
+use crate::error::Result;
+
+pub mod builder;
+mod structure;
+
+super::arena::new_dweller!(Inductive, Header, Payload);
+
+/// The header of an inductive type.
+struct Header<'arena> {
+    /// Whether the recursor of the inductive type may eliminate into any sort, or only into `Prop`.
+    ///
+    /// This is only known once the inductive type has been checked.
+    large_elimination: OnceCell<bool>,
+
+    /// Lazy structure to store the type of the recursor.
+    recursor_type: OnceCell<Term<'arena>>,
+}
+
+/// A constructor of an inductive type.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Constructor<'arena> {
+    /// The name of the constructor.
+    name: &'arena str,
+
+    /// The type of the constructor.
+    ///
+    /// This term lives under a binder, where the variable of index 1 stands for the inductive type
+    /// being defined. It is of the shape `(params) -> (fields) -> I params indices`.
+    type_: Term<'arena>,
+}
+
+/// An inductive type.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Payload<'arena> {
+    /// The name of the inductive type.
+    name: &'arena str,
+
+    /// The number of universe variables of the inductive type.
+    univs: usize,
+
+    /// The number of uniform parameters.
+    params: usize,
+
+    /// The type of the inductive type, of the shape `(params) -> (indices) -> Sort u`.
+    arity: Term<'arena>,
+
+    /// The constructors of the inductive type.
+    constructors: &'arena [Constructor<'arena>],
+}
+
+impl<'arena> Constructor<'arena> {
+    /// Returns the name of the constructor.
+    pub(crate) const fn name(self) -> &'arena str {
+        self.name
+    }
+
+    /// Returns the type of the constructor, which lives under the binder of the inductive type
+    /// being defined.
+    pub(crate) const fn get_type(self) -> Term<'arena> {
+        self.type_
+    }
+}
+
+impl<'arena> fmt::Display for Inductive<'arena> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0.payload.name)
+    }
+}
+
+impl<'arena> Inductive<'arena> {
+    /// Creates a new inductive type from its base components.
+    ///
+    /// No verification is made on the well-formedness of the inductive type, which is the purpose
+    /// of [`Inductive::check`].
+    pub(crate) fn declare(
+        name: &str,
+        univs: usize,
+        params: usize,
+        arity: Term<'arena>,
+        constructors: &[(&str, Term<'arena>)],
+        arena: &mut Arena<'arena>,
+    ) -> Self {
+        let constructors = constructors
+            .iter()
+            .map(|&(name, type_)| Constructor {
+                name: arena.store_name(name),
+                type_,
+            })
+            .collect::<Vec<_>>();
+
+        let new_node = Node {
+            header: Header {
+                large_elimination: OnceCell::new(),
+                recursor_type: OnceCell::new(),
+            },
+            payload: Payload {
+                name: arena.store_name(name),
+                univs,
+                params,
+                arity,
+                constructors: arena.alloc.alloc_slice_copy(&constructors),
+            },
+        };
+
+        if let Some(addr) = arena.hashcons_inductives.get(&new_node) {
+            Self::new(addr)
+        } else {
+            let addr = arena.alloc.alloc(new_node);
+            arena.hashcons_inductives.insert(addr);
+            Self::new(addr)
+        }
+    }
+
+    /// Returns the name of the inductive type.
+    #[inline]
+    #[must_use]
+    pub fn name(self) -> &'arena str {
+        self.0.payload.name
+    }
+
+    /// Returns the number of universe variables of the inductive type.
+    pub(crate) const fn univs(self) -> usize {
+        self.0.payload.univs
+    }
+
+    /// Returns the number of uniform parameters of the inductive type.
+    pub(crate) const fn params(self) -> usize {
+        self.0.payload.params
+    }
+
+    /// Returns the type of the inductive type.
+    pub(crate) const fn arity(self) -> Term<'arena> {
+        self.0.payload.arity
+    }
+
+    /// Returns the constructors of the inductive type, in order.
+    pub(crate) const fn constructors(self) -> &'arena [Constructor<'arena>] {
+        self.0.payload.constructors
+    }
+
+    /// Returns the names of the constructors of the inductive type, in order.
+    #[inline]
+    pub fn constructor_names(self) -> impl Iterator<Item = &'arena str> {
+        self.0.payload.constructors.iter().map(|constructor| constructor.name)
+    }
+
+    /// Returns the name of the recursor of the inductive type.
+    #[inline]
+    #[must_use]
+    pub fn recursor_name(self, arena: &Arena<'arena>) -> &'arena str {
+        arena.store_name(&format!("{}_rec", self.0.payload.name))
+    }
+
+    /// Returns the number of universe variables of the recursor.
+    ///
+    /// When the inductive type allows large elimination, the recursor has an extra universe
+    /// variable, corresponding to the sort of the motive.
+    pub(crate) fn recursor_univs(self) -> usize {
+        self.0.payload.univs + usize::from(self.is_large_eliminating())
+    }
+
+    /// Indicates whether the recursor of the inductive type can eliminate into any sort.
+    ///
+    /// An inductive type which has not been checked is conservatively considered to only
+    /// eliminate into `Prop`.
+    pub(crate) fn is_large_eliminating(self) -> bool {
+        self.0.header.large_elimination.get().copied().unwrap_or(false)
+    }
+
+    /// Records whether the recursor of the inductive type can eliminate into any sort.
+    pub(crate) fn set_large_elimination(self, large_elimination: bool) {
+        self.0.header.large_elimination.set(large_elimination).ok();
+    }
+
+    /// Returns the type of the recursor, lazily computing the closure `f`.
+    pub(crate) fn get_recursor_type_or_init<F>(self, f: F) -> Term<'arena>
+    where
+        F: FnOnce() -> Term<'arena>,
+    {
+        *self.0.header.recursor_type.get_or_init(f)
+    }
+
+    /// Returns the universe variables `u0, ..., un` of the inductive type, in order.
+    pub(crate) fn identity_levels(self, arena: &mut Arena<'arena>) -> Vec<Level<'arena>> {
+        (0..self.0.payload.univs).map(|i| Level::var(i, arena)).collect()
+    }
+
+    /// Returns the generic term associated to the given axiom, where universe variables are left
+    /// uninstantiated.
+    fn generic_term(axiom: axiom::inductive::Inductive<'arena>, univs: usize, arena: &mut Arena<'arena>) -> Term<'arena> {
+        let levels = (0..univs).map(|i| Level::var(i, arena)).collect::<Vec<_>>();
+        Term::axiom(Axiom::Inductive(axiom), &levels, arena)
+    }
+}
+
+impl<'arena> Arena<'arena> {
+    /// Checks an inductive type, then binds it, its constructors and its recursor to their
+    /// respective names.
+    ///
+    /// Universe-polymorphic elements are bound as [declarations](Declaration).
+    ///
+    /// # Errors
+    /// If the inductive type is ill-formed (see [`Inductive::check`]), nothing is bound and the
+    /// corresponding error is yielded.
+    #[inline]
+    pub fn bind_inductive(&mut self, inductive: Inductive<'arena>) -> Result<'arena, ()> {
+        inductive.check(self)?;
+        self.bind_checked_inductive(inductive);
+
+        Ok(())
+    }
+
+    /// Binds an inductive type, its constructors and its recursor to their respective names. The
+    /// inductive type must have been checked beforehand.
+    fn bind_checked_inductive(&mut self, inductive: Inductive<'arena>) {
+        use axiom::inductive::Inductive::{Constructor, Recursor, Type};
+
+        let univs = inductive.0.payload.univs;
+
+        let bind = |name: &str, axiom, univs, arena: &mut Self| {
+            let term = Inductive::generic_term(axiom, univs, arena);
+
+            if univs == 0 {
+                arena.bind(name, term);
+            } else {
+                arena.bind_decl(name, Declaration::new(term, univs));
+            }
+        };
+
+        bind(inductive.name(), Type(inductive), univs, self);
+
+        inductive
+            .constructor_names()
+            .enumerate()
+            .for_each(|(i, name)| bind(name, Constructor(inductive, i), univs, self));
+
+        let recursor_name = inductive.recursor_name(self);
+        bind(recursor_name, Recursor(inductive), inductive.recursor_univs(), self);
+
+        let name = inductive.name();
+        self.named_inductives.insert(name, inductive);
+    }
+
+    /// Retrieves the inductive type of a given name, if one exists.
+    #[inline]
+    #[must_use]
+    pub fn get_inductive(&self, name: &str) -> Option<Inductive<'arena>> {
+        self.named_inductives.get(name).copied()
+    }
+}
@@ -0,0 +1,181 @@
+//! Structures, that is inductive types without indices and with a single constructor, whose
+//! fields are accessed through projections.
+//!
+//! Projections are derived from the recursor: the projection onto the field `f` of a structure `S`
+//! with parameters `(p: P)` and fields `(f0: A0) ... (fn: An)` is
+//! ```text
+//! fun (p: P) (s: S p) => S_rec p (fun s: S p => A) (fun f0 ... fn => f) s
+//! ```
+//! where the fields occurring in the type `A` of `f` are replaced by their own projections of `s`.
+
+use super::Inductive;
+use crate::axiom::inductive::{Inductive as InductiveAxiom, Telescope};
+use crate::axiom::Axiom;
+use crate::error::{Error, Result};
+use crate::memory::arena::Arena;
+use crate::memory::declaration::Declaration;
+use crate::memory::level::Level;
+use crate::memory::term::Payload::{Abs, App, Let, Prod, Sort, Var};
+use crate::memory::term::Term;
+use crate::type_checker::ErrorKind;
+
+impl<'arena> Inductive<'arena> {
+    /// Returns the projections of the structure `self` onto each of its fields, in order.
+    ///
+    /// Universe variables are left uninstantiated in the projections.
+    fn projections(self, arena: &mut Arena<'arena>) -> Result<'arena, Vec<Term<'arena>>> {
+        let params = self.params;
+        let type_former = self.type_former(arena);
+        let not_a_structure = || Error::new(ErrorKind::NotAStructure(type_former).into());
+
+        if self.constructors.len() != 1 || self.indices(arena) != 0 {
+            return Err(not_a_structure());
+        }
+
+        let (param_types, _) = self.arity.telescope(arena);
+
+        // skip the parameters, so that fields live in the context of the parameters
+        let mut type_ = InductiveAxiom::Constructor(self, 0).get_type(arena);
+        for _ in 0..params {
+            let Prod(_, body) = *type_.whnf_unfolded(arena) else {
+                unreachable!("constructor types start with the parameters");
+            };
+            type_ = body;
+        }
+
+        let (fields, _) = type_.telescope(arena);
+
+        let mut projections = Vec::with_capacity(fields.len());
+
+        for (index, field) in fields.iter().enumerate() {
+            if field.telescope(arena).1.is_instance_of(self, arena) {
+                return Err(not_a_structure());
+            }
+
+            let sort = field.infer(arena)?.whnf(arena);
+            let Sort(level) = *sort else {
+                return Err(Error::new(ErrorKind::NotUniverse(sort).into()));
+            };
+
+            let mut levels = self.identity_levels(arena);
+            if self.is_large_eliminating() {
+                levels.push(level);
+            } else if !level.is_eq(Level::zero(arena), arena) {
+                return Err(Error::new(ErrorKind::ProjectionOutOfProp(*field).into()));
+            }
+
+            let mut ctx = Telescope::new();
+            param_types.iter().for_each(|type_| ctx.push(*type_));
+
+            // fun s: S p => A, where the previous fields are replaced by their projections of s
+            let structure = ctx.apply(type_former, 0..params, arena);
+            ctx.push(structure);
+
+            let motive = (0..index).rev().fold(field.shift(1, index, arena), |motive, position| {
+                let projection = *projections.get(position).unwrap_or_else(|| unreachable!());
+                let projection = ctx.apply(projection, 0..=params, arena).shift(position, 0, arena);
+                motive.substitute(projection, 1, arena)
+            });
+            let motive = ctx.close_abs(params, motive, arena);
+
+            // fun f0 ... fn => f
+            fields.iter().for_each(|field| ctx.push(*field));
+            let minor = ctx.var(params + index, arena);
+            let minor = ctx.close_abs(params, minor, arena);
+
+            ctx.push(structure);
+            let recursor = Term::axiom(Axiom::Inductive(InductiveAxiom::Recursor(self)), &levels, arena);
+            let recursor = ctx.apply(recursor, 0..params, arena);
+            let motive = ctx.lift(motive, params, arena);
+            let minor = ctx.lift(minor, params, arena);
+            let major = ctx.var(params, arena);
+
+            let projection = recursor.app(motive, arena).app(minor, arena).app(major, arena);
+            let projection = ctx.close_abs(0, projection, arena);
+            projections.push(annotate(projection, &mut Vec::new(), arena));
+        }
+
+        Ok(projections)
+    }
+}
+
+impl<'arena> Arena<'arena> {
+    /// Checks a structure, then binds it, its constructor and its recursor to their respective
+    /// names, as well as its projections, which are named after the structure and the given
+    /// `fields`.
+    ///
+    /// Universe-polymorphic projections are bound as [declarations](Declaration).
+    ///
+    /// # Errors
+    /// If `inductive` is ill-formed (see [`Inductive::check`]), if it is not a structure whose
+    /// fields are named by `fields`, or if some of its fields cannot be projected, nothing is bound
+    /// and the corresponding error is yielded.
+    #[inline]
+    pub fn bind_structure(&mut self, inductive: Inductive<'arena>, fields: &[&str]) -> Result<'arena, ()> {
+        inductive.check(self)?;
+        let projections = inductive.projections(self)?;
+
+        if projections.len() != fields.len() {
+            let type_former = inductive.type_former(self);
+            return Err(Error::new(ErrorKind::NotAStructure(type_former).into()));
+        }
+
+        self.bind_checked_inductive(inductive);
+
+        let univs = inductive.univs();
+        for (field, projection) in fields.iter().zip(projections) {
+            let name = format!("{}.{field}", inductive.name());
+
+            if univs == 0 {
+                self.bind(&name, projection);
+            } else {
+                self.bind_decl(&name, Declaration::new(projection, univs));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Annotates every variable of `term` with the type of the binder it refers to, where `binders`
+/// holds the types of the binders enclosing `term`, from the outermost to the innermost one.
+///
+/// Projections are assembled from pieces built in different contexts, whose variables would
+/// otherwise keep the annotations of the context they were built in.
+fn annotate<'arena>(term: Term<'arena>, binders: &mut Vec<Term<'arena>>, arena: &mut Arena<'arena>) -> Term<'arena> {
+    match *term {
+        Var(index, _) => {
+            let position = binders.len() - usize::from(index);
+            let type_ = *binders.get(position).unwrap_or_else(|| unreachable!("projections are closed"));
+            Term::var(index, type_.shift(index.into(), 0, arena), arena)
+        },
+        App(t1, t2) => {
+            let t1 = annotate(t1, binders, arena);
+            let t2 = annotate(t2, binders, arena);
+            t1.app(t2, arena)
+        },
+        Abs(arg_type, body) => {
+            let arg_type = annotate(arg_type, binders, arena);
+            binders.push(arg_type);
+            let body = annotate(body, binders, arena);
+            binders.pop();
+            arg_type.abs(body, arena)
+        },
+        Prod(arg_type, body) => {
+            let arg_type = annotate(arg_type, binders, arena);
+            binders.push(arg_type);
+            let body = annotate(body, binders, arena);
+            binders.pop();
+            arg_type.prod(body, arena)
+        },
+        Let(type_, value, body) => {
+            let type_ = annotate(type_, binders, arena);
+            let value = annotate(value, binders, arena);
+            binders.push(type_);
+            let body = annotate(body, binders, arena);
+            binders.pop();
+            Term::let_(type_, value, body, arena)
+        },
+        _ => term,
+    }
+}
@@ -100,7 +100,9 @@ impl<'arena> Level<'arena> {
     }
 
     /// Returns the level + n.
-    pub(crate) fn add(self, n: u32, arena: &mut Arena<'arena>) -> Self {
+    #[inline]
+    #[must_use]
+    pub fn add(self, n: u32, arena: &mut Arena<'arena>) -> Self {
         Self::hashcons(Add(self, n), arena)
     }
 
@@ -115,7 +117,9 @@ impl<'arena> Level<'arena> {
     }
 
     /// Returns the level associated to a given variable.
-    pub(crate) fn var(id: usize, arena: &mut Arena<'arena>) -> Self {
+    #[inline]
+    #[must_use]
+    pub fn var(id: usize, arena: &mut Arena<'arena>) -> Self {
         Self::hashcons(Var(id), arena)
     }
 
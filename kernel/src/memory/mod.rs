@@ -2,10 +2,13 @@
 //!
 //! This module provides a paradigm for building and manipulating [terms](term::Term) in the
 //! calculus of construction, centered around the notion of [arena](`arena::Arena`). Terms also
-//! rely on other structures like [declarations](declaration::Declaration) and [universe
-//! levels](level::Level).
+//! rely on other structures like [declarations](declaration::Declaration), [universe
+//! levels](level::Level) and [inductive types](inductive::Inductive). Open terms are manipulated
+//! under a [typing context](context::Context).
 
 pub mod arena;
+pub mod context;
 pub mod declaration;
+pub mod inductive;
 pub mod level;
 pub mod term;
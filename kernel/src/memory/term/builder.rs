@@ -10,12 +10,15 @@
 
 use derive_more::Display;
 use im_rc::hashmap::HashMap as ImHashMap;
+use num_bigint::BigUint;
 
 use super::{DeBruijnIndex, Term};
 use crate::error::{Error, ResultTerm};
 use crate::memory::arena::Arena;
+use crate::memory::context::Context;
 use crate::memory::declaration::builder as declaration;
 use crate::memory::level::builder as level;
+use crate::memory::level::Level;
 use crate::trace::{Trace, TraceableError};
 
 /// The kind of errors that can occur when building a [`Term`].
@@ -25,8 +28,28 @@ pub enum ErrorKind<'arena> {
     /// Unknown identifier
     #[display(fmt = "unknown identifier {_0}")]
     ConstNotFound(&'arena str),
+
+    /// Identifier referring to several names, when names are resolved before reaching the kernel
+    #[display(fmt = "ambiguous identifier {_0}, which may refer to {_1}")]
+    AmbiguousName(String, String),
+
+    /// Unknown pinned term
+    #[display(fmt = "unknown pinned term {_0}")]
+    UnknownPin(Pin),
+
+    /// Inductive type instantiated with other universe levels than its own within its definition
+    #[display(fmt = "{_0} may only be instantiated with its own universe variables, in order")]
+    LocalInstance(&'arena str),
 }
 
+/// A handle on a closed term pinned in an arena.
+///
+/// Pins allow terms obtained from the arena, like the type of some constant, to be embedded in
+/// terms built afterwards, through the [`pinned`] builder.
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq, Hash)]
+#[display(fmt = "#{_0}")]
+pub struct Pin(usize);
+
 /// Local environment used to store correspondence between locally-bound variables and the pair
 /// (depth at which they were bound, their type).
 pub type Environment<'build, 'arena> = ImHashMap<&'build str, (DeBruijnIndex, Term<'arena>)>;
@@ -57,12 +80,49 @@ impl<'arena> Arena<'arena> {
     pub fn build<'build, F: BuilderTrait<'build>>(&mut self, f: F) -> ResultTerm<'arena> {
         f(self, &Environment::new(), &level::Environment::new(), 0.into())
     }
+
+    /// Returns the term built from the given closure, provided with the hypotheses of `ctx`, at the
+    /// depth of `ctx`.
+    ///
+    /// # Errors
+    /// If the term could not be built, yields an error indicating the reason.
+    #[inline]
+    pub fn build_in<'build, F: BuilderTrait<'build>>(&mut self, ctx: &Context<'arena>, f: F) -> ResultTerm<'arena>
+    where
+        'arena: 'build,
+    {
+        let env = ctx
+            .iter()
+            .enumerate()
+            .map(|(depth, &(name, type_))| (name, (depth.into(), type_)))
+            .collect();
+
+        f(self, &env, &level::Environment::new(), ctx.len().into())
+    }
+
+    /// Pins the term `term` in the arena, so that it can be embedded in terms built afterwards.
+    ///
+    /// Only closed terms can be pinned: the types annotating their variables do not depend on
+    /// the context they are embedded in. If `term` is not closed, returns `None`.
+    #[inline]
+    pub fn pin(&mut self, term: Term<'arena>) -> Option<Pin> {
+        if !term.is_closed() {
+            return None;
+        }
+
+        let position = *self.pins.entry(term).or_insert_with(|| {
+            self.pinned_terms.push(term);
+            self.pinned_terms.len() - 1
+        });
+
+        Some(Pin(position))
+    }
 }
 
 /// Returns a closure building a variable associated to the name `name`.
 #[inline]
 #[must_use]
-pub const fn var(name: &str) -> impl BuilderTrait<'_> {
+pub const fn var<'build>(name: &str) -> impl BuilderTrait<'build> + '_ {
     move |arena, env, _, depth| {
         env.get(name)
             .map(|&(bind_depth, term)| {
@@ -76,6 +136,34 @@ pub const fn var(name: &str) -> impl BuilderTrait<'_> {
     }
 }
 
+/// Returns a closure building the term associated to the name `name`, instantiated with the
+/// universe levels `levels`.
+///
+/// While an inductive type is built, it refers to itself through a locally bound variable, which
+/// may only be instantiated with the universe variables in scope, in order. Names which are not
+/// locally bound refer to the declaration `name`.
+#[inline]
+#[must_use]
+pub fn var_instance<'name, 'build: 'name, V: level::VecBuilderTrait<'build> + 'name>(
+    name: &'name str,
+    levels: V,
+) -> impl BuilderTrait<'build> + 'name {
+    move |arena, env, lvl_env, depth| {
+        if !env.contains_key(name) {
+            return decl(declaration::var(name, levels))(arena, env, lvl_env, depth);
+        }
+
+        let levels = levels(arena, lvl_env)?;
+        let identity: Vec<_> = (0..lvl_env.len()).map(|index| Level::var(index, arena)).collect();
+
+        if levels != identity {
+            return Err(Error::new(ErrorKind::LocalInstance(arena.store_name(name)).into()));
+        }
+
+        var(name)(arena, env, lvl_env, depth)
+    }
+}
+
 /// Returns a closure building the Prop term.
 #[inline]
 #[must_use]
@@ -111,6 +199,13 @@ pub const fn sort_usize<'build>(level: u32) -> impl BuilderTrait<'build> {
     move |arena, _, _, _| Ok(Term::sort_usize(level, arena))
 }
 
+/// Returns a closure building the natural number literal of value `n`.
+#[inline]
+#[must_use]
+pub const fn nat_lit(n: &BigUint) -> impl BuilderTrait<'_> {
+    move |arena, _, _, _| Ok(Term::nat_lit(n, arena))
+}
+
 /// Returns a closure building the application of two terms built from the given closures `u1` and
 /// `u2`.
 #[inline]
@@ -140,7 +235,7 @@ pub const fn abs<'build, F1: BuilderTrait<'build>, F2: BuilderTrait<'build>>(
             let env = env.update(name, (depth, arg_type));
             body(arena, &env, lvl_env, depth + 1.into()).trace_err(Trace::Right)?
         };
-        Ok(arg_type.abs(body, arena))
+        Ok(arg_type.abs(body, arena).set_name(name, arena))
     }
 }
 
@@ -161,7 +256,30 @@ pub const fn prod<'build, F1: BuilderTrait<'build>, F2: BuilderTrait<'build>>(
             let env = env.update(name, (depth, arg_type));
             body(arena, &env, lvl_env, depth + 1.into()).trace_err(Trace::Right)?
         };
-        Ok(arg_type.prod(body, arena))
+        Ok(arg_type.prod(body, arena).set_name(name, arena))
+    }
+}
+
+/// Returns a closure building the local definition of `name`, of type built from `type_` and of
+/// value built from `value`, in the term built from `body`.
+#[inline]
+#[coverage(off)]
+pub const fn let_<'build, F1: BuilderTrait<'build>, F2: BuilderTrait<'build>, F3: BuilderTrait<'build>>(
+    name: &'build str,
+    type_: F1,
+    value: F2,
+    body: F3,
+) -> impl BuilderTrait<'build> {
+    move |arena, env, lvl_env, depth| {
+        let type_ = type_(arena, env, lvl_env, depth).trace_err(Trace::Left)?;
+        let value = value(arena, env, lvl_env, depth).trace_err(Trace::Middle)?;
+        let body = if name == "_" {
+            body(arena, env, lvl_env, depth + 1.into()).trace_err(Trace::Right)?
+        } else {
+            let env = env.update(name, (depth, type_));
+            body(arena, &env, lvl_env, depth + 1.into()).trace_err(Trace::Right)?
+        };
+        Ok(Term::let_(type_, value, body, arena).set_name(name, arena))
     }
 }
 
@@ -172,6 +290,19 @@ pub const fn decl<'build, F: declaration::InstantiatedBuilderTrait<'build>>(decl
     move |arena, _, lvl_env, _| Ok(Term::decl(decl(arena, lvl_env)?, arena))
 }
 
+/// Returns a closure building the term pinned by `pin`.
+#[inline]
+#[must_use]
+pub const fn pinned<'build>(pin: Pin) -> impl BuilderTrait<'build> {
+    move |arena, _, _, _| {
+        arena
+            .pinned_terms
+            .get(pin.0)
+            .copied()
+            .ok_or_else(|| Error::new(ErrorKind::UnknownPin(pin).into()))
+    }
+}
+
 #[cfg(test)]
 pub(crate) mod raw {
     use super::*;
@@ -5,6 +5,7 @@
 use core::cell::OnceCell;
 
 use derive_more::{Add, Display, From, Into, Sub};
+use num_bigint::BigUint;
 
 use super::declaration::InstantiatedDeclaration;
 use super::level::Level;
@@ -34,6 +35,9 @@ struct Header<'arena> {
 
     /// Whether the term is *known* to be closed.
     is_certainly_closed: OnceCell<()>,
+
+    /// The name of the variable bound by the term, if it is a binder which has been given one.
+    name: OnceCell<&'arena str>,
 }
 
 impl<'arena> Header<'arena> {
@@ -46,6 +50,7 @@ impl<'arena> Header<'arena> {
             type_: OnceCell::new(),
             is_relevant: OnceCell::new(),
             is_certainly_closed: if is_certainly_closed { OnceCell::from(()) } else { OnceCell::new() },
+            name: OnceCell::new(),
         }
     }
 }
@@ -75,14 +80,21 @@ pub enum Payload<'arena> {
     /// The dependant product of the term on the right over all elements of the type on the left.
     Prod(Term<'arena>, Term<'arena>),
 
+    /// A local definition: the type and the value of the bound variable come first, the body in
+    /// which it is bound comes last.
+    Let(Term<'arena>, Term<'arena>, Term<'arena>),
+
     /// An instance of a universe-polymorphic declaration.
     Decl(InstantiatedDeclaration<'arena>),
 
     /// An axiom.
-    Axiom(axiom::Axiom, &'arena [Level<'arena>]),
+    Axiom(axiom::Axiom<'arena>, &'arena [Level<'arena>]),
+
+    /// A natural number literal, stored as the little-endian sequence of its base 2^32 digits.
+    NatLit(&'arena [u32]),
 }
 
-use Payload::{Abs, App, Axiom, Decl, Prod, Sort, Var};
+use Payload::{Abs, App, Axiom, Decl, Let, NatLit, Prod, Sort, Var};
 
 impl<'arena> Term<'arena> {
     /// This function is the base low-level function for creating terms.
@@ -107,7 +119,7 @@ impl<'arena> Term<'arena> {
     }
 
     /// Returns an axiom term with the given axiom.
-    pub(crate) fn axiom(axiom: axiom::Axiom, lvl: &[Level<'arena>], arena: &mut Arena<'arena>) -> Self {
+    pub(crate) fn axiom(axiom: axiom::Axiom<'arena>, lvl: &[Level<'arena>], arena: &mut Arena<'arena>) -> Self {
         let lvl = arena.store_level_slice(lvl);
         let header = Header::new(true);
         let payload = Axiom(axiom, lvl);
@@ -177,6 +189,18 @@ impl<'arena> Term<'arena> {
         Self::hashcons(Node { header, payload }, arena)
     }
 
+    /// Returns the local definition of a variable of type `type_` and of value `value` in the term
+    /// `body`.
+    ///
+    /// Please note that no verification is done that occurrences of this variable in `body` have
+    /// the same type.
+    pub(crate) fn let_(type_: Self, value: Self, body: Self, arena: &mut Arena<'arena>) -> Self {
+        let header = Header::new(type_.is_certainly_closed() && value.is_certainly_closed() && body.is_certainly_closed());
+        let payload = Let(type_, value, body);
+
+        Self::hashcons(Node { header, payload }, arena)
+    }
+
     /// Returns the term associated to the given instantiated declaration.
     pub(crate) fn decl(decl: InstantiatedDeclaration<'arena>, arena: &mut Arena<'arena>) -> Self {
         let header = Header::new(false);
@@ -185,6 +209,25 @@ impl<'arena> Term<'arena> {
         Self::hashcons(Node { header, payload }, arena)
     }
 
+    /// Returns the natural number literal of value `n`.
+    pub(crate) fn nat_lit(n: &BigUint, arena: &mut Arena<'arena>) -> Self {
+        let digits = arena.store_digit_slice(&n.to_u32_digits());
+        let header = Header::new(true);
+        let payload = NatLit(digits);
+
+        Self::hashcons(Node { header, payload }, arena)
+    }
+
+    /// Returns the value of the term if it is a natural number literal; returns `None` otherwise.
+    #[inline]
+    #[must_use]
+    pub fn to_nat_lit(self) -> Option<BigUint> {
+        match *self {
+            NatLit(digits) => Some(BigUint::from_slice(digits)),
+            _ => None,
+        }
+    }
+
     /// Returns the weak head normal form of the term, lazily computing the closure `f`.
     pub(crate) fn get_whnf_or_init<F>(self, f: F) -> Self
     where
@@ -220,6 +263,39 @@ impl<'arena> Term<'arena> {
     pub(crate) fn set_as_closed(self) {
         self.0.header.is_certainly_closed.set(()).ok();
     }
+
+    /// Returns the name of the variable bound by the term, if it is a binder which has been given
+    /// one.
+    ///
+    /// As terms are shared, this is the first name given to any binder equal to the term: after
+    /// `fun x: Nat => x` is built, `fun y: Nat => y` is also named after `x`.
+    #[inline]
+    #[must_use]
+    pub fn name(self) -> Option<&'arena str> {
+        self.0.header.name.get().copied()
+    }
+
+    /// Gives the name `name` to the variable bound by the term, unless it already has one.
+    ///
+    /// Names are not part of the payload, and are only used for printing: binders which only
+    /// differ by the name of their variable are the same term, which keeps the first name given.
+    /// Names which cannot be written in the input, such as `_` or `#0`, are not given.
+    pub(crate) fn set_name(self, name: &str, arena: &mut Arena<'arena>) -> Self {
+        if name != "_" && !name.starts_with('#') && self.name().is_none() {
+            self.0.header.name.set(arena.store_name(name)).ok();
+        }
+
+        self
+    }
+
+    /// Gives the name of the variable bound by `other`, if any, to the variable bound by the term.
+    pub(crate) fn inherit_name(self, other: Self) -> Self {
+        if let Some(name) = other.name() {
+            self.0.header.name.set(name).ok();
+        }
+
+        self
+    }
 }
 
 impl<'arena> Arena<'arena> {
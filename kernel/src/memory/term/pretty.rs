@@ -2,7 +2,9 @@
 
 use core::fmt;
 
-use super::Payload::{Abs, App, Axiom, Decl, Prod, Sort, Var};
+use num_bigint::BigUint;
+
+use super::Payload::{Abs, App, Axiom, Decl, Let, NatLit, Prod, Sort, Var};
 
 /// Thin wrapper used internally to print a term associated to a (classic) identifier as a letter.
 struct PrettyVar(usize);
@@ -20,17 +22,114 @@ impl fmt::Display for PrettyVar {
 impl<'arena> fmt::Display for super::Term<'arena> {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.pretty_print(f, 0, 0, false)
+        self.pretty_print(f, &mut Vec::new(), &[], false)
     }
 }
 
 /// A term that is ready to be pretty-printed with named variables. This term must be closed.
+///
+/// Variables are printed with the names given to their binders when the term was built, and a
+/// letter otherwise. A variable is renamed, with a numeric suffix, only if its name would
+/// capture a variable bound above it.
 pub struct Term<'arena>(pub super::Term<'arena>);
 
 impl<'arena> fmt::Display for Term<'arena> {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.0.pretty_print(f, 0, 0, true)
+        self.0.pretty_print(f, &mut Vec::new(), &[], true)
+    }
+}
+
+/// A term that is ready to be pretty-printed with named variables and with the given notations.
+/// This term must be closed.
+///
+/// The applications of a constant for which a notation has been declared are printed with its
+/// symbol, the leading arguments hidden by the notation being left out.
+pub struct Notated<'notations, 'arena>(pub super::Term<'arena>, pub &'notations [Notation<'arena>]);
+
+impl<'notations, 'arena> fmt::Display for Notated<'notations, 'arena> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.pretty_print(f, &mut Vec::new(), self.1, true)
+    }
+}
+
+/// A term that is ready to be pretty-printed with named variables, under binders whose variables
+/// are named by the given names, from the outermost one.
+///
+/// This is used to print subterms of a term, such as the fields of a constructor, which may refer
+/// to the variables bound above them.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Open<'arena>(pub super::Term<'arena>, pub Vec<String>);
+
+impl<'arena> fmt::Display for Open<'arena> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.pretty_print(f, &mut self.1.clone(), &[], true)
+    }
+}
+
+/// The position of the operands of a notation, relative to its symbol.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Fixity {
+    /// A unary operator, written before its operand.
+    Prefix,
+
+    /// A binary operator, which cannot be chained without parentheses.
+    Infix,
+
+    /// A left-associative binary operator.
+    InfixLeft,
+
+    /// A right-associative binary operator.
+    InfixRight,
+}
+
+/// A notation, which writes the application of a constant to its operands with a symbol.
+///
+/// Notations are declared in the [arena](crate::memory::arena::Arena::add_notation), where they are
+/// used both by the parser and by the [pretty-printer](Notated).
+#[derive(Clone, Copy, Debug)]
+pub struct Notation<'arena> {
+    /// The symbol of the notation.
+    pub symbol: &'arena str,
+
+    /// The position of the operands of the notation.
+    pub fixity: Fixity,
+
+    /// The precedence of the notation: the higher, the tighter it binds its operands.
+    pub precedence: usize,
+
+    /// The name of the constant the notation stands for.
+    pub name: &'arena str,
+
+    /// The number of leading arguments of the constant which do not appear in the notation.
+    pub hidden: usize,
+
+    /// The term (or the term of the declaration) bound to the name.
+    pub(crate) head: super::Term<'arena>,
+}
+
+impl<'arena> Notation<'arena> {
+    /// Returns the number of operands of the notation.
+    #[inline]
+    #[must_use]
+    pub const fn arity(&self) -> usize {
+        match self.fixity {
+            Fixity::Prefix => 1,
+            Fixity::Infix | Fixity::InfixLeft | Fixity::InfixRight => 2,
+        }
+    }
+
+    /// Returns the minimal precedences of the notations which can be written without parentheses
+    /// as the leftmost and the rightmost operands of `self`.
+    const fn operand_precedences(&self) -> (usize, usize) {
+        match self.fixity {
+            Fixity::Prefix => (self.precedence, self.precedence),
+            Fixity::Infix => (self.precedence + 1, self.precedence + 1),
+            Fixity::InfixLeft => (self.precedence, self.precedence + 1),
+            Fixity::InfixRight => (self.precedence + 1, self.precedence),
+        }
     }
 }
 
@@ -42,28 +141,176 @@ impl<'arena> super::Term<'arena> {
 
     /// Indicates whether a term is a binder, which also entails specific printing rules.
     fn is_binder(self) -> bool {
-        matches!(*self, Abs(..) | Prod(..))
+        matches!(*self, Abs(..) | Prod(..) | Let(..))
+    }
+
+    /// Indicates whether the head of an application `self` is the constant `head`, up to the
+    /// universe levels it is instantiated with.
+    fn has_head(self, head: Self) -> bool {
+        self == head
+            || match (&*self, &*head) {
+                (&Decl(decl), _) => decl.decl.0 == head,
+                (&Axiom(axiom, _), &Axiom(other, _)) => axiom == other,
+                _ => false,
+            }
+    }
+
+    /// Returns the notation under which `self` is printed, along with its operands, if any.
+    ///
+    /// The most recently declared notations take precedence.
+    fn notation<'notations>(
+        self,
+        notations: &'notations [Notation<'arena>],
+    ) -> Option<(&'notations Notation<'arena>, Vec<Self>)> {
+        if notations.is_empty() || !self.is_app() {
+            return None;
+        }
+
+        let mut args = Vec::new();
+        let mut head = self;
+
+        while let App(fun, arg) = *head {
+            args.push(arg);
+            head = fun;
+        }
+
+        args.reverse();
+
+        let notation = notations
+            .iter()
+            .rev()
+            .find(|notation| args.len() == notation.hidden + notation.arity() && head.has_head(notation.head))?;
+
+        Some((notation, args.split_off(notation.hidden)))
+    }
+
+    /// Prints the operand `self` of a notation, where `precedence` is the minimal precedence of
+    /// the notations it can be written with without parentheses.
+    fn pretty_print_operand(
+        self,
+        f: &mut fmt::Formatter,
+        names: &mut Vec<String>,
+        notations: &[Notation<'arena>],
+        precedence: usize,
+        is_root_closed: bool,
+    ) -> fmt::Result {
+        let tight = self
+            .notation(notations)
+            .map_or_else(|| !self.is_binder(), |(notation, _)| notation.precedence >= precedence);
+
+        if tight {
+            self.pretty_print(f, names, notations, is_root_closed)
+        } else {
+            self.pretty_print_parens(f, names, notations, is_root_closed)
+        }
+    }
+
+    /// Prints `operands` with the symbol of `notation`.
+    fn pretty_print_notation(
+        notation: &Notation<'arena>,
+        operands: &[Self],
+        f: &mut fmt::Formatter,
+        names: &mut Vec<String>,
+        notations: &[Notation<'arena>],
+        is_root_closed: bool,
+    ) -> fmt::Result {
+        let (left, right) = notation.operand_precedences();
+
+        match *operands {
+            [operand] => {
+                write!(f, "{}", notation.symbol)?;
+
+                // the symbols of consecutive prefix notations would be read as a single one
+                if matches!(operand.notation(notations), Some((inner, _)) if inner.fixity == Fixity::Prefix) {
+                    operand.pretty_print_parens(f, names, notations, is_root_closed)
+                } else {
+                    operand.pretty_print_operand(f, names, notations, right, is_root_closed)
+                }
+            },
+            [lhs, rhs] => {
+                lhs.pretty_print_operand(f, names, notations, left, is_root_closed)?;
+                write!(f, " {} ", notation.symbol)?;
+                rhs.pretty_print_operand(f, names, notations, right, is_root_closed)
+            },
+            _ => unreachable!("notations have one or two operands"),
+        }
+    }
+
+    /// Returns the name under which the variable bound by `self` is printed, where `body` is the
+    /// term in which it is bound, and `names` are the names of the variables bound above.
+    ///
+    /// The name is suffixed with a number if it would otherwise capture one of the variables bound
+    /// above which occur in `body`.
+    pub(crate) fn binder_name(self, body: Self, names: &[String]) -> String {
+        let base = self.name().map_or_else(|| PrettyVar(names.len()).to_string(), str::to_owned);
+
+        let captures = |name: &str| {
+            names
+                .iter()
+                .enumerate()
+                .any(|(position, bound)| bound == name && body.has_free_var(names.len() - position + 1))
+        };
+
+        let mut name = base.clone();
+        let mut suffix = 0_usize;
+
+        while captures(&name) {
+            suffix += 1;
+            name = format!("{base}{suffix}");
+        }
+
+        name
+    }
+
+    /// Prints `self` in between parentheses.
+    fn pretty_print_parens(
+        self,
+        f: &mut fmt::Formatter,
+        names: &mut Vec<String>,
+        notations: &[Notation<'arena>],
+        is_root_closed: bool,
+    ) -> fmt::Result {
+        write!(f, "(")?;
+        self.pretty_print(f, names, notations, is_root_closed)?;
+        write!(f, ")")
+    }
+
+    /// Prints `body`, in which a variable named `name` is bound.
+    fn pretty_print_under(
+        body: Self,
+        f: &mut fmt::Formatter,
+        names: &mut Vec<String>,
+        notations: &[Notation<'arena>],
+        name: String,
+    ) -> fmt::Result {
+        names.push(name);
+        let result = body.pretty_print(f, names, notations, true);
+        names.pop();
+
+        result
     }
 
     /// This function generates the pretty print of a term.
     ///
-    /// `depth` parameter indicates the number of existing variables (number of Abs/Prop from the root of the term to the
-    /// considered subterm).
-    /// `distance` parameter corresponds to the number of Abs/Prop between the environement where the variables live and these
-    /// variables.
+    /// `names` are the names of the variables bound above the considered subterm, from the
+    /// outermost one. They are only tracked below a closed subterm.
+    /// `notations` are the notations with which applications are printed.
     /// `is_root_closed` indicates if the root is certain to be closed. If true, the De Bruijn indexes will not appear but
     /// transformed in named variables instead.
     #[coverage(off)]
-    fn pretty_print(self, f: &mut fmt::Formatter, depth: usize, distance: usize, mut is_root_closed: bool) -> fmt::Result {
+    fn pretty_print(
+        self,
+        f: &mut fmt::Formatter,
+        names: &mut Vec<String>,
+        notations: &[Notation<'arena>],
+        mut is_root_closed: bool,
+    ) -> fmt::Result {
         is_root_closed |= self.is_certainly_closed();
 
         match *self {
-            Var(index, _) => {
-                if is_root_closed {
-                    write!(f, "{}", PrettyVar(depth - distance - index.0))
-                } else {
-                    write!(f, "{index}")
-                }
+            Var(index, _) => match names.len().checked_sub(index.0).and_then(|position| names.get(position)) {
+                Some(name) if is_root_closed => write!(f, "{name}"),
+                _ => write!(f, "{index}"),
             },
             Sort(level) => match level.to_numeral() {
                 Some(n) => match n {
@@ -74,44 +321,78 @@ impl<'arena> super::Term<'arena> {
                 None => write!(f, "Sort {level}"),
             },
             App(fun, arg) => {
-                if fun.is_binder() {
-                    write!(f, "(")?;
-                    fun.pretty_print(f, depth, distance, is_root_closed)?;
-                    write!(f, ")")?;
+                if let Some((notation, operands)) = self.notation(notations) {
+                    return Self::pretty_print_notation(notation, &operands, f, names, notations, is_root_closed);
+                }
+
+                if fun.is_binder() || fun.notation(notations).is_some() {
+                    fun.pretty_print_parens(f, names, notations, is_root_closed)?;
                 } else {
-                    fun.pretty_print(f, depth, distance, is_root_closed)?;
+                    fun.pretty_print(f, names, notations, is_root_closed)?;
                 }
                 write!(f, " ")?;
                 if arg.is_app() || arg.is_binder() {
-                    write!(f, "(")?;
-                    arg.pretty_print(f, depth, distance, is_root_closed)?;
-                    write!(f, ")")
+                    arg.pretty_print_parens(f, names, notations, is_root_closed)
                 } else {
-                    arg.pretty_print(f, depth, distance, is_root_closed)
+                    arg.pretty_print(f, names, notations, is_root_closed)
                 }
             },
             Abs(argtype, body) => {
                 write!(f, "\u{003BB} ")?;
-                if is_root_closed {
-                    write!(f, "{}: ", PrettyVar(depth))?;
-                };
-                argtype.pretty_print(f, depth + 1, distance + 1, is_root_closed)?;
+                if !is_root_closed {
+                    argtype.pretty_print(f, names, notations, false)?;
+                    write!(f, " => ")?;
+                    return body.pretty_print(f, names, notations, false);
+                }
+
+                let name = self.binder_name(body, names);
+                write!(f, "{name}: ")?;
+                argtype.pretty_print(f, names, notations, true)?;
                 write!(f, " => ")?;
-                body.pretty_print(f, depth + 1, distance, is_root_closed)
+                Self::pretty_print_under(body, f, names, notations, name)
             },
             Prod(argtype, body) => {
-                if is_root_closed {
-                    write!(f, "({}: ", PrettyVar(depth))?;
-                };
-                argtype.pretty_print(f, depth + 1, distance + 1, is_root_closed)?;
-                if is_root_closed {
-                    write!(f, ")")?;
-                };
-                write!(f, " -> ")?;
-                body.pretty_print(f, depth + 1, distance, is_root_closed)
+                if !is_root_closed || !body.has_free_var(1) {
+                    if is_root_closed && argtype.is_binder() {
+                        argtype.pretty_print_parens(f, names, notations, is_root_closed)?;
+                    } else {
+                        argtype.pretty_print(f, names, notations, is_root_closed)?;
+                    }
+                    write!(f, " -> ")?;
+                    return if is_root_closed {
+                        Self::pretty_print_under(body, f, names, notations, "_".to_owned())
+                    } else {
+                        body.pretty_print(f, names, notations, false)
+                    };
+                }
+
+                let name = self.binder_name(body, names);
+                write!(f, "({name}: ")?;
+                argtype.pretty_print(f, names, notations, true)?;
+                write!(f, ") -> ")?;
+                Self::pretty_print_under(body, f, names, notations, name)
+            },
+            Let(type_, value, body) => {
+                write!(f, "let ")?;
+                if !is_root_closed {
+                    type_.pretty_print(f, names, notations, false)?;
+                    write!(f, " := ")?;
+                    value.pretty_print(f, names, notations, false)?;
+                    write!(f, "; ")?;
+                    return body.pretty_print(f, names, notations, false);
+                }
+
+                let name = self.binder_name(body, names);
+                write!(f, "{name}: ")?;
+                type_.pretty_print(f, names, notations, true)?;
+                write!(f, " := ")?;
+                value.pretty_print(f, names, notations, true)?;
+                write!(f, "; ")?;
+                Self::pretty_print_under(body, f, names, notations, name)
             },
             Decl(decl) => write!(f, "{decl}"),
             Axiom(s, _) => write!(f, "{s}"),
+            NatLit(digits) => write!(f, "{}", BigUint::from_slice(digits)),
         }
     }
 }
@@ -127,7 +408,7 @@ mod tests {
     #[test]
     fn display_1() {
         use_arena(|arena| {
-            let decl = InstantiatedDeclaration::instantiate(Declaration(Term::prop(arena), 0), &Vec::new(), arena);
+            let decl = InstantiatedDeclaration::instantiate(Declaration::new(Term::prop(arena), 0), &Vec::new(), arena);
             let prop = Term::decl(decl, arena);
 
             assert_eq!(prop.to_string(), "(Prop).{}");
@@ -176,4 +457,90 @@ mod tests {
             assert_eq!(two.to_string(), "Succ (Succ Zero)");
         });
     }
+
+    #[test]
+    fn display_names() {
+        use_arena_with_axioms(|arena| {
+            use crate::memory::term::builder::{abs, prod, prop, var};
+
+            let id = arena.build(abs("A", prop(), abs("x", var("A"), var("x")))).unwrap();
+            let type_ = id.infer(arena).unwrap();
+
+            assert_eq!(pretty::Term(id).to_string(), "λ A: Prop => λ x: A => x");
+            assert_eq!(pretty::Term(type_).to_string(), "(A: Prop) -> A -> A");
+
+            let arrow = arena.build(prod("_", prod("_", prop(), prop()), prop())).unwrap();
+            assert_eq!(pretty::Term(arrow).to_string(), "(Prop -> Prop) -> Prop");
+        });
+    }
+
+    #[test]
+    fn display_shared_names() {
+        use_arena_with_axioms(|arena| {
+            use crate::memory::term::builder::{abs, prop, var};
+
+            let first = arena.build(abs("x", prop(), var("x"))).unwrap();
+            let second = arena.build(abs("y", prop(), var("y"))).unwrap();
+
+            // binders which only differ by the name of their variable are the same term, which
+            // keeps the first name given
+            assert_eq!(first, second);
+            assert_eq!(pretty::Term(second).to_string(), "λ x: Prop => x");
+        });
+    }
+
+    #[test]
+    fn display_capture() {
+        use_arena_with_axioms(|arena| {
+            use crate::memory::term::builder::{abs, app, prop, var};
+
+            // λ x: Prop => λ z: x => (λ y: Prop => λ x: Prop => y) x
+            let term = arena
+                .build(abs("x", prop(), abs("z", var("x"), app(abs("y", prop(), abs("x", prop(), var("y"))), var("x")))))
+                .unwrap();
+
+            assert_eq!(pretty::Term(term.normal_form(arena)).to_string(), "λ x: Prop => λ z: x => λ x1: Prop => x");
+        });
+    }
+
+    #[test]
+    fn display_notations() {
+        use_arena_with_axioms(|arena| {
+            use crate::memory::declaration::builder as declaration;
+            use crate::memory::level::builder::{append, const_, nil};
+            use crate::memory::term::builder::{abs, decl, prop, type_usize, var};
+            use crate::memory::term::pretty::{Fixity, Notated};
+
+            let and = arena.build(abs("A", prop(), abs("B", prop(), var("A")))).unwrap();
+            arena.bind("And", and);
+            let not = arena.build(abs("A", prop(), var("A"))).unwrap();
+            arena.bind("Not", not);
+
+            assert!(arena.add_notation("/\\", Fixity::InfixRight, 35, "And", 0));
+            assert!(arena.add_notation("~", Fixity::Prefix, 40, "Not", 0));
+            assert!(arena.add_notation("=", Fixity::Infix, 50, "Eq", 1));
+            assert!(!arena.add_notation("+", Fixity::InfixLeft, 65, "add", 0));
+
+            let a = arena.build(prop()).unwrap();
+            let b = arena.build(type_usize(0)).unwrap();
+            let eq = arena.build(decl(declaration::var("Eq", append(nil(), const_(2))))).unwrap();
+
+            // (~a /\ a = b) /\ ~(b /\ a) /\ ~(~b)
+            let not_a = not.app(a, arena);
+            let a_eq_b = eq.app(b, arena).app(a, arena).app(b, arena);
+            let lhs = and.app(not_a, arena).app(a_eq_b, arena);
+            let b_and_a = and.app(b, arena).app(a, arena);
+            let not_b_and_a = not.app(b_and_a, arena);
+            let not_b = not.app(b, arena);
+            let not_not_b = not.app(not_b, arena);
+            let rhs = and.app(not_b_and_a, arena).app(not_not_b, arena);
+            let term = and.app(lhs, arena).app(rhs, arena);
+
+            assert_eq!(
+                Notated(term, arena.notations()).to_string(),
+                "(~Prop /\\ Prop = Type) /\\ ~(Type /\\ Prop) /\\ ~(~Type)"
+            );
+            assert!(!pretty::Term(term).to_string().contains("/\\"));
+        });
+    }
 }
@@ -8,13 +8,16 @@ use crate::error::Error;
 /// An element of a trace that indicates which branch has been taken at each step of the execution
 /// of an algorithm.
 ///
-/// Please note that there are only two possible values since the structures of interest have at
-/// most two children.
+/// Please note that there are only three possible values since the structures of interest have at
+/// most three children. Structures with two children only use the left and the right branches.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Trace {
     /// Left branch.
     Left,
 
+    /// Middle branch.
+    Middle,
+
     /// Right branch.
     Right,
 }
@@ -4,10 +4,17 @@
 
 use derive_more::Display;
 
+use crate::axiom::natural::Natural;
+use crate::axiom::sigma::Sigma;
 use crate::error::{Error, Result, ResultTerm};
 use crate::memory::arena::Arena;
+use crate::memory::context::Context;
 use crate::memory::declaration::Declaration;
-use crate::memory::term::Payload::{Abs, App, Axiom, Decl, Prod, Sort, Var};
+use crate::memory::inductive::builder::trace_constructor;
+use crate::memory::inductive::{Constructor, Inductive};
+use crate::memory::level::Level;
+use crate::memory::term::Payload::{Abs, App, Axiom, Decl, Let, NatLit, Prod, Sort, Var};
+use crate::memory::term::pretty::Open;
 use crate::memory::term::Term;
 use crate::trace::{Trace, TraceableError};
 
@@ -27,6 +34,10 @@ pub enum ErrorKind<'arena> {
     #[display(fmt = "{_0} is not a universe")]
     NotUniverse(Term<'arena>),
 
+    /// This type of an opaque declaration is not a proposition.
+    #[display(fmt = "{_0} is not a proposition")]
+    NotAProposition(Term<'arena>),
+
     /// These two terms are not definitionally equal.
     #[display(fmt = "{_0} and {_1} are not definitionally equal")]
     NotDefEq(Term<'arena>, Term<'arena>),
@@ -42,6 +53,37 @@ pub enum ErrorKind<'arena> {
     /// These types mismatch.
     #[display(fmt = "expected {_0}, got {_1}")]
     TypeMismatch(Term<'arena>, Term<'arena>),
+
+    /// This term is not of the shape `(params) -> (indices) -> Sort u`.
+    #[display(fmt = "{_0} is not the arity of an inductive type")]
+    NotAnArity(Term<'arena>),
+
+    /// The type of this constructor does not end with the inductive type applied to its
+    /// parameters, but with this type.
+    #[display(fmt = "invalid type for constructor {_0}, ending with {_1}")]
+    NotAConstructor(&'arena str, Open<'arena>),
+
+    /// The inductive type occurs in a non strictly positive position in this argument of a
+    /// constructor.
+    #[display(fmt = "non strictly positive occurrence of the inductive type in {_1}, argument of constructor {_0}")]
+    NotStrictlyPositive(&'arena str, Open<'arena>),
+
+    /// This argument of a constructor, of the given sort, lives in a universe too large for the
+    /// inductive type.
+    #[display(fmt = "argument {_1}: {_2} of constructor {_0} is too large for an inductive type living in {_3}")]
+    UniverseTooLarge(&'arena str, Open<'arena>, Term<'arena>, Term<'arena>),
+
+    /// This inductive type is not a structure with the expected fields.
+    #[display(fmt = "{_0} is not a structure with the expected fields")]
+    NotAStructure(Term<'arena>),
+
+    /// A field of this type cannot be projected out of a structure living in `Prop`.
+    #[display(fmt = "a field of type {_0} cannot be projected out of a proposition")]
+    ProjectionOutOfProp(Term<'arena>),
+
+    /// This variable is not bound in the context.
+    #[display(fmt = "variable {_0} is not bound in the context")]
+    UnboundVariable(Term<'arena>),
 }
 
 impl<'arena> Term<'arena> {
@@ -55,6 +97,7 @@ impl<'arena> Term<'arena> {
         }
 
         // We assume that self and rhs have the same type. As such, we only need to check whether
+        // one of them is a proof to know whether both are.
         if !self.is_relevant(arena) {
             return true;
         }
@@ -66,7 +109,7 @@ impl<'arena> Term<'arena> {
             return true;
         }
 
-        match (&*lhs, &*rhs) {
+        let structural = match (&*lhs, &*rhs) {
             (&Sort(l1), &Sort(l2)) => l1.is_eq(l2, arena),
 
             (&Var(i, _), &Var(j, _)) => i == j,
@@ -84,11 +127,99 @@ impl<'arena> Term<'arena> {
             // We do not automatically unfold definitions during normalisation because of how costly it is.
             // Instead, when the same declaration is met on both terms, they're also equal in memory.
             // Otherwise, either one of them is not a decl, or they are two different decls. In both case, we unfold decls to check
-            // equality.
-            (&Decl(decl), _) => decl.get_term(arena).conversion(rhs, arena),
+            // equality, unless they are opaque.
+            (&Decl(decl), _) if !decl.decl().is_opaque() => decl.get_term(arena).conversion(rhs, arena),
+
+            (_, &Decl(decl)) if !decl.decl().is_opaque() => decl.get_term(arena).conversion(lhs, arena),
+
+            // Natural number literals are equated with their unary representation. Because the
+            // successor of a literal reduces to a literal, the expanded forms are compared without
+            // being reduced again.
+            (&NatLit(_), &NatLit(_)) => false,
 
-            (_, &Decl(decl)) => decl.get_term(arena).conversion(lhs, arena),
+            (&NatLit(_), _) | (_, &NatLit(_)) => {
+                let lhs = lhs.expand_nat_lit(arena);
+                let rhs = rhs.expand_nat_lit(arena);
+
+                match (&*lhs, &*rhs) {
+                    (&App(t1, u1), &App(t2, u2)) => t1.conversion(t2, arena) && u1.conversion(u2, arena),
+                    _ => lhs == rhs,
+                }
+            },
+
+            // Eta-conversion: `fun x: A => t` and `u` are definitionally equal whenever `t` and
+            // `u x` are (where `u` is shifted under the new binder).
+            (&Abs(arg_type, t), _) => {
+                let var = Term::var(1.into(), arg_type.shift(1, 0, arena), arena);
+                let rhs = rhs.shift(1, 0, arena).app(var, arena);
+
+                t.conversion(rhs, arena)
+            },
+
+            (_, &Abs(arg_type, u)) => {
+                let var = Term::var(1.into(), arg_type.shift(1, 0, arena), arena);
+                let lhs = lhs.shift(1, 0, arena).app(var, arena);
+
+                lhs.conversion(u, arena)
+            },
 
+            _ => false,
+        };
+
+        // Eta for unit-like types and pairs needs types to be inferred or definitions to be unfolded,
+        // and is only tried as a last resort.
+        structural || lhs.eta_conversion(rhs, arena)
+    }
+
+    /// Eta rules of the conversion for types with a single constructor, where `self` and `rhs` are
+    /// in weak head normal form.
+    fn eta_conversion(self, rhs: Self, arena: &mut Arena<'arena>) -> bool {
+        // Sorts and products are types, which inhabit neither unit-like types nor pairs.
+        if matches!(*self, Sort(_) | Prod(..)) || matches!(*rhs, Sort(_) | Prod(..)) {
+            return false;
+        }
+
+        let (lhs_head, _) = self.whnf_unfolded(arena).spine(arena);
+        let (rhs_head, _) = rhs.whnf_unfolded(arena).spine(arena);
+
+        // Values built with distinct constructors are never equal.
+        if let (&Axiom(lhs_axiom, _), &Axiom(rhs_axiom, _)) = (&*lhs_head, &*rhs_head) {
+            if lhs_axiom.is_constructor() && rhs_axiom.is_constructor() && lhs_axiom != rhs_axiom {
+                return false;
+            }
+        }
+
+        // Any two inhabitants of a unit-like type are definitionally equal. Two values built with
+        // constructors are already compared structurally, so the type is only inferred when one
+        // side is neutral.
+        if (lhs_head.is_neutral() || rhs_head.is_neutral()) && self.infer(arena).is_ok_and(|type_| {
+            let type_ = type_.whnf_unfolded(arena);
+            crate::axiom::Axiom::is_unit_like(type_, arena)
+        }) {
+            return true;
+        }
+
+        // Eta for pairs: `Sigma_mk A B a b` and `p` are definitionally equal whenever `a` and `b`
+        // are respectively equal to the projections of `p`.
+        if let Some(pair) = Sigma::to_pair(self, arena) {
+            let (fst, snd) = Sigma::projections(pair, rhs, arena);
+            return pair.fst().conversion(fst, arena) && pair.snd().conversion(snd, arena);
+        }
+
+        if let Some(pair) = Sigma::to_pair(rhs, arena) {
+            let (fst, snd) = Sigma::projections(pair, self, arena);
+            return fst.conversion(pair.fst(), arena) && snd.conversion(pair.snd(), arena);
+        }
+
+        false
+    }
+
+    /// Indicates whether `self`, the head of a term in weak-head normal form, makes the term
+    /// neutral, that is, whether it is a variable or a constant which does not build a value.
+    fn is_neutral(self) -> bool {
+        match *self {
+            Var(..) | Decl(_) => true,
+            Axiom(axiom, _) => !axiom.is_constructor(),
             _ => false,
         }
     }
@@ -127,6 +258,7 @@ impl<'arena> Term<'arena> {
             Sort(lvl) => Ok(Term::sort(lvl.succ(arena), arena)),
             Var(_, type_) => Ok(type_),
             Axiom(ax, lvl) => Ok(ax.get_type(arena).substitute_univs(lvl, arena)),
+            NatLit(_) => Ok(Natural::Nat.term(arena)),
 
             Prod(t, u) => {
                 let univ_t = t.infer(arena).trace_err(Trace::Left)?;
@@ -143,7 +275,7 @@ impl<'arena> Term<'arena> {
                 match *type_t {
                     Sort(_) => {
                         let type_u = u.infer(arena).trace_err(Trace::Right)?;
-                        Ok(t.prod(type_u, arena))
+                        Ok(t.prod(type_u, arena).inherit_name(self))
                     },
 
                     _ => Err(Error::new(ErrorKind::NotUniverse(type_t).into())).trace_err(Trace::Left),
@@ -169,6 +301,21 @@ impl<'arena> Term<'arena> {
                 }
             },
 
+            Let(type_, value, body) => {
+                let sort = type_.infer(arena).trace_err(Trace::Left)?;
+
+                match *sort {
+                    Sort(_) => {
+                        value.check(type_, arena).trace_err(Trace::Middle)?;
+
+                        // the body is typed once the value is substituted, as its type may depend on the value
+                        body.substitute(value, 1, arena).infer(arena).trace_err(Trace::Right)
+                    },
+
+                    _ => Err(Error::new(ErrorKind::NotUniverse(sort).into())).trace_err(Trace::Left),
+                }
+            },
+
             Decl(decl) => decl.get_type_or_try_init(Term::infer, arena),
         })
     }
@@ -186,6 +333,30 @@ impl<'arena> Term<'arena> {
             .then_some(())
             .ok_or_else(|| Error::new(ErrorKind::TypeMismatch(tty, ty).into()))
     }
+
+    /// Infers the type of the open term `self`, whose free variables refer to the hypotheses of
+    /// `ctx`.
+    ///
+    /// # Errors
+    /// If `self` has a free variable not bound in `ctx`, or cannot be typed, yields the
+    /// corresponding error.
+    #[inline]
+    pub fn infer_in(self, ctx: &Context<'arena>, arena: &mut Arena<'arena>) -> ResultTerm<'arena> {
+        let type_ = ctx.bind(self, arena)?.infer(arena)?;
+        ctx.bind(type_, arena)
+    }
+
+    /// Checks whether the open term `self`, whose free variables refer to the hypotheses of `ctx`,
+    /// is of type `ty` in this context.
+    ///
+    /// # Errors
+    /// If `self` or `ty` has a free variable not bound in `ctx`, `self` cannot be typed, or `ty` is
+    /// not the type of `self`, yields the corresponding error.
+    #[inline]
+    pub fn check_in(self, ty: Self, ctx: &Context<'arena>, arena: &mut Arena<'arena>) -> Result<'arena, ()> {
+        let ty = ctx.bind(ty, arena)?;
+        ctx.bind(self, arena)?.check(ty, arena)
+    }
 }
 
 impl<'arena> Declaration<'arena> {
@@ -212,6 +383,245 @@ impl<'arena> Declaration<'arena> {
     pub fn check(self, ty: Self, arena: &mut Arena<'arena>) -> Result<'arena, ()> {
         self.0.check(ty.0, arena)
     }
+
+    /// Checks whether the declaration `self` living in `arena` is a proof, that is whether its type
+    /// is a proposition.
+    ///
+    /// # Errors
+    /// If `self` cannot be typed, or its type is not a proposition, this yields the corresponding
+    /// error.
+    #[inline]
+    pub fn check_proof(self, arena: &mut Arena<'arena>) -> Result<'arena, ()> {
+        let type_ = self.0.infer(arena)?;
+        let sort = type_.infer(arena)?;
+
+        sort.conversion(Term::prop(arena), arena)
+            .then_some(())
+            .ok_or_else(|| Error::new(ErrorKind::NotAProposition(type_).into()))
+    }
+}
+
+/// Appends to `res` the trace leading to the `rights`-th binder of a product, or to the body
+/// following it when `left` is false.
+fn trace_binder<T>(res: Result<'_, T>, rights: usize, left: bool) -> Result<'_, T> {
+    let res = if left { res.trace_err(Trace::Left) } else { res };
+    (0..rights).fold(res, |res, _| res.trace_err(Trace::Right))
+}
+
+impl<'arena> Inductive<'arena> {
+    /// Checks whether the inductive type `self` is well-formed, and determines into which sorts
+    /// its recursor may eliminate.
+    ///
+    /// The arity must be a well-typed product over at least the parameters, ending with a sort
+    /// `Sort u`. Each constructor must be a well-typed product over the same parameters, whose
+    /// fields only mention the inductive type in strictly positive positions and live in a
+    /// universe at most `u` (unless `u` is `Prop`), and whose conclusion is the inductive type
+    /// applied to the parameters and to some indices.
+    ///
+    /// # Errors
+    /// If the inductive type is ill-formed, this yields an error indicating where the problem is.
+    #[inline]
+    pub fn check(self, arena: &mut Arena<'arena>) -> Result<'arena, ()> {
+        let sort = self.arity().infer(arena).trace_err(Trace::Left)?;
+
+        if !matches!(*sort.whnf(arena), Sort(_)) {
+            return Err(Error::new(ErrorKind::NotUniverse(sort).into())).trace_err(Trace::Left);
+        }
+
+        let (binders, conclusion) = self.arity().telescope(arena);
+
+        let Sort(level) = *conclusion else {
+            return Err(Error::new(ErrorKind::NotAnArity(self.arity()).into())).trace_err(Trace::Left);
+        };
+
+        if binders.len() < self.params() {
+            return Err(Error::new(ErrorKind::NotAnArity(self.arity()).into())).trace_err(Trace::Left);
+        }
+
+        let (params, indices) = binders.split_at(self.params());
+
+        let constructors = self
+            .constructors()
+            .iter()
+            .enumerate()
+            .map(|(index, constructor)| {
+                trace_constructor(self.check_constructor(*constructor, level, params, indices.len(), arena), index)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // The recursor may eliminate into any sort when the inductive type cannot be a
+        // proposition, or when it is a syntactic subsingleton.
+        let large_elimination = Level::from(1, arena).geq(level, 0, arena)
+            || match constructors.as_slice() {
+                [] => true,
+                [fields] => fields.iter().all(|field| *field),
+                _ => false,
+            };
+
+        self.set_large_elimination(large_elimination);
+        Ok(())
+    }
+
+    /// Checks whether `constructor` has a valid type for a constructor of the inductive type, when
+    /// the latter lives in `Sort level`.
+    ///
+    /// For each field of the constructor, returns whether it does not prevent the inductive type
+    /// from being a subsingleton, that is, whether it is a proof or appears as an index in the
+    /// conclusion.
+    fn check_constructor(
+        self,
+        constructor: Constructor<'arena>,
+        level: Level<'arena>,
+        params: &[Term<'arena>],
+        indices: usize,
+        arena: &mut Arena<'arena>,
+    ) -> Result<'arena, Vec<bool>> {
+        let sort = constructor.get_type().infer(arena)?;
+
+        if !matches!(*sort.whnf(arena), Sort(_)) {
+            return Err(Error::new(ErrorKind::NotUniverse(sort).into()));
+        }
+
+        // Errors can only be traced back along the structural binders of the type.
+        let mut rights = 0;
+        let mut structural = true;
+        let mut type_ = constructor.get_type();
+
+        // The names of the variables bound above `type_`, the first one being the inductive type.
+        let mut names = vec![self.name().to_owned()];
+
+        for param in params {
+            let whnf = type_.whnf_unfolded(arena);
+            structural &= whnf == type_;
+
+            let Prod(arg_type, body) = *whnf else {
+                let err = Err(Error::new(ErrorKind::NotAConstructor(constructor.name(), Open(whnf, names)).into()));
+                return trace_binder(err, rights, false);
+            };
+
+            if !arg_type.conversion(*param, arena) {
+                let err = Err(Error::new(ErrorKind::TypeMismatch(*param, arg_type).into()));
+                return trace_binder(err, rights, structural);
+            }
+
+            rights += usize::from(structural);
+            names.push(whnf.binder_name(body, &names));
+            type_ = body;
+        }
+
+        let mut depth = params.len();
+        let mut fields = Vec::new();
+
+        let conclusion = loop {
+            let whnf = type_.whnf_unfolded(arena);
+            structural &= whnf == type_;
+
+            let Prod(arg_type, body) = *whnf else {
+                break whnf;
+            };
+
+            let res = self.check_field(constructor.name(), arg_type, &names, level, arena);
+            fields.push(trace_binder(res, rights, structural)?);
+
+            rights += usize::from(structural);
+            names.push(whnf.binder_name(body, &names));
+            depth += 1;
+            type_ = body;
+        };
+
+        let (head, args) = conclusion.spine(arena);
+
+        let is_valid = matches!(*head, Var(i, _) if i == (depth + 1).into())
+            && args.len() == params.len() + indices
+            && args.iter().enumerate().all(|(position, arg)| {
+                if position < params.len() {
+                    matches!(**arg, Var(i, _) if i == (depth - position).into())
+                } else {
+                    !arg.has_free_var(depth + 1)
+                }
+            });
+
+        if !is_valid {
+            let err = Err(Error::new(ErrorKind::NotAConstructor(constructor.name(), Open(conclusion, names)).into()));
+            return trace_binder(err, rights, false);
+        }
+
+        // A field is harmless when it appears as an index, as it is then determined by the type.
+        let fields = fields
+            .into_iter()
+            .enumerate()
+            .map(|(position, is_proof)| {
+                let var = depth - params.len() - position;
+                is_proof || args.iter().skip(params.len()).any(|arg| matches!(**arg, Var(i, _) if i == var.into()))
+            })
+            .collect();
+
+        Ok(fields)
+    }
+
+    /// Checks whether the field `field` of the constructor `constructor` is well-formed, and
+    /// returns whether it is a proof.
+    ///
+    /// The field lives under binders whose variables are named by `names`, the first one standing
+    /// for the inductive type.
+    fn check_field(
+        self,
+        constructor: &'arena str,
+        field: Term<'arena>,
+        names: &[String],
+        level: Level<'arena>,
+        arena: &mut Arena<'arena>,
+    ) -> Result<'arena, bool> {
+        let index = names.len();
+
+        if field.has_free_var(index) && !self.is_strictly_positive(field, index, arena) {
+            return Err(Error::new(ErrorKind::NotStrictlyPositive(constructor, Open(field, names.to_vec())).into()));
+        }
+
+        let sort = field.infer(arena)?.whnf(arena);
+
+        let Sort(field_level) = *sort else {
+            return Err(Error::new(ErrorKind::NotUniverse(sort).into()));
+        };
+
+        let zero = Level::zero(arena);
+
+        if !level.is_eq(zero, arena) && !field_level.geq(level, 0, arena) {
+            let inductive_sort = Term::sort(level, arena);
+            let field = Open(field, names.to_vec());
+            return Err(Error::new(ErrorKind::UniverseTooLarge(constructor, field, sort, inductive_sort).into()));
+        }
+
+        Ok(field_level.is_eq(zero, arena))
+    }
+
+    /// Indicates whether the variable of index `index`, standing for the inductive type, only
+    /// occurs strictly positively in `field`.
+    fn is_strictly_positive(self, field: Term<'arena>, index: usize, arena: &mut Arena<'arena>) -> bool {
+        let mut index = index;
+        let mut field = field.whnf_unfolded(arena);
+
+        while let Prod(arg_type, body) = *field {
+            if arg_type.has_free_var(index) {
+                return false;
+            }
+
+            index += 1;
+            field = body.whnf_unfolded(arena);
+        }
+
+        let (head, args) = field.spine(arena);
+
+        matches!(*head, Var(i, _) if i == index.into())
+            && args.len() == self.arity().telescope(arena).0.len()
+            && args.iter().enumerate().all(|(position, arg)| {
+                if position < self.params() {
+                    matches!(**arg, Var(i, _) if i == (index - 1 - position).into())
+                } else {
+                    !arg.has_free_var(index)
+                }
+            })
+    }
 }
 
 #[cfg(test)]
@@ -258,7 +668,7 @@ mod tests {
     #[test]
     fn conv_decl() {
         use_arena(|arena| {
-            let decl_ = InstantiatedDeclaration::instantiate(Declaration(Term::prop(arena), 0), &Vec::new(), arena);
+            let decl_ = InstantiatedDeclaration::instantiate(Declaration::new(Term::prop(arena), 0), &Vec::new(), arena);
             let term = Term::decl(decl_, arena);
 
             let prop = arena.build_term_raw(prop());
@@ -271,7 +681,7 @@ mod tests {
     #[test]
     fn infer_decl() {
         use_arena(|arena| {
-            let decl_ = InstantiatedDeclaration::instantiate(Declaration(Term::prop(arena), 0), &Vec::new(), arena);
+            let decl_ = InstantiatedDeclaration::instantiate(Declaration::new(Term::prop(arena), 0), &Vec::new(), arena);
 
             let term = Term::decl(decl_, arena);
             let ty = arena.build_term_raw(type_usize(0));
@@ -569,19 +979,86 @@ mod tests {
 
     #[test]
     fn irrelevance_conversion() {
-        use crate::axiom::false_::False::{False, FalseRec};
-        use crate::axiom::Axiom;
-        use crate::memory::level::Level;
+        use crate::memory::arena::use_arena_with_axioms;
 
-        use_arena(|arena| {
-            let false_ = Term::axiom(Axiom::False(False), &[], arena);
-            let false_rec = Term::axiom(Axiom::False(FalseRec), &[Level::zero(arena)], arena);
+        use_arena_with_axioms(|arena| {
+            let false_ = arena.get_binding("False").unwrap();
+            let false_rec = arena.get_binding_decl("False_rec").unwrap();
+            let false_rec = Term::decl(InstantiatedDeclaration::instantiate(false_rec, &[Level::zero(arena)], arena), arena);
             let tt1 = false_.abs(Term::var(1.into(), false_, arena), arena);
             let tt2 = false_.abs(false_rec.app(false_, arena).app(Term::var(1.into(), false_, arena), arena), arena);
             assert!(tt1.conversion(tt2, arena));
         });
     }
 
+    #[test]
+    fn eta_conversion() {
+        use_arena(|arena| {
+            // in a context where `f: Prop -> Prop`
+            let f = arena.build_term_raw(var(1.into(), prod(prop(), prop())));
+            let eta_f = arena.build_term_raw(abs(prop(), app(var(2.into(), prod(prop(), prop())), var(1.into(), prop()))));
+
+            assert!(eta_f.conversion(f, arena));
+            assert!(f.conversion(eta_f, arena));
+        });
+    }
+
+    #[test]
+    fn eta_conversion_nested() {
+        use_arena(|arena| {
+            // in a context where `f: Prop -> Prop -> Prop`
+            let f = arena.build_term_raw(var(1.into(), prod(prop(), prod(prop(), prop()))));
+            let eta_f = arena.build_term_raw(abs(
+                prop(),
+                abs(
+                    prop(),
+                    app(app(var(3.into(), prod(prop(), prod(prop(), prop()))), var(2.into(), prop())), var(1.into(), prop())),
+                ),
+            ));
+            let partial_eta_f =
+                arena.build_term_raw(abs(prop(), app(var(2.into(), prod(prop(), prod(prop(), prop()))), var(1.into(), prop()))));
+
+            assert!(eta_f.conversion(f, arena));
+            assert!(f.conversion(eta_f, arena));
+            assert!(eta_f.conversion(partial_eta_f, arena));
+            assert!(partial_eta_f.conversion(eta_f, arena));
+        });
+    }
+
+    #[test]
+    fn eta_conversion_shifted() {
+        use_arena(|arena| {
+            // in a context where `f: Prop -> Prop`, under an extra binder `z: Prop`
+            let f = arena.build_term_raw(abs(prop(), var(2.into(), prod(prop(), prop()))));
+            let eta_f =
+                arena.build_term_raw(abs(prop(), abs(prop(), app(var(3.into(), prod(prop(), prop())), var(1.into(), prop())))));
+
+            assert!(eta_f.conversion(f, arena));
+            assert!(f.conversion(eta_f, arena));
+
+            // `fun z: Prop -> Prop => fun x => z x` is not `fun z => f`
+            let wrong = arena.build_term_raw(abs(
+                prod(prop(), prop()),
+                abs(prop(), app(var(2.into(), prod(prop(), prop())), var(1.into(), prop()))),
+            ));
+
+            assert!(!wrong.conversion(f, arena));
+            assert!(!f.conversion(wrong, arena));
+        });
+    }
+
+    #[test]
+    fn eta_conversion_failure() {
+        use_arena(|arena| {
+            // in a context where `f g: Prop -> Prop`
+            let f = arena.build_term_raw(var(2.into(), prod(prop(), prop())));
+            let eta_g = arena.build_term_raw(abs(prop(), app(var(2.into(), prod(prop(), prop())), var(1.into(), prop()))));
+
+            assert!(!eta_g.conversion(f, arena));
+            assert!(!f.conversion(eta_g, arena));
+        });
+    }
+
     mod failed_type_inference {
         use super::*;
 
@@ -0,0 +1,112 @@
+use kernel::error::Error;
+use kernel::memory::arena::use_arena;
+use kernel::memory::context::Context;
+use kernel::memory::term::builder::*;
+use kernel::type_checker::ErrorKind;
+
+#[test]
+fn infer_under_hypotheses() {
+    use_arena(|arena| {
+        let mut ctx = Context::new();
+
+        let a = arena.build_in(&ctx, prop()).unwrap();
+        ctx.push("A", a, arena).unwrap();
+        let p = arena.build_in(&ctx, prod("_", var("A"), prop())).unwrap();
+        ctx.push("P", p, arena).unwrap();
+        let x = arena.build_in(&ctx, var("A")).unwrap();
+        ctx.push("x", x, arena).unwrap();
+        let h = arena.build_in(&ctx, app(var("P"), var("x"))).unwrap();
+        ctx.push("h", h, arena).unwrap();
+
+        assert_eq!(ctx.len(), 4);
+
+        let term = arena.build_in(&ctx, var("h")).unwrap();
+        let expected = arena.build_in(&ctx, app(var("P"), var("x"))).unwrap();
+
+        assert_eq!(term.infer_in(&ctx, arena), Ok(expected));
+        assert!(term.check_in(expected, &ctx, arena).is_ok());
+
+        // fun y: A => h has type A -> P x
+        let term = arena.build_in(&ctx, abs("y", var("A"), var("h"))).unwrap();
+        let expected = arena.build_in(&ctx, prod("y", var("A"), app(var("P"), var("x")))).unwrap();
+
+        assert!(term.check_in(expected, &ctx, arena).is_ok());
+
+        let wrong = arena.build_in(&ctx, var("A")).unwrap();
+
+        assert!(term.check_in(wrong, &ctx, arena).is_err());
+    });
+}
+
+#[test]
+fn lookup() {
+    use_arena(|arena| {
+        let mut ctx = Context::new();
+
+        let a = arena.build_in(&ctx, prop()).unwrap();
+        ctx.push("A", a, arena).unwrap();
+        let x = arena.build_in(&ctx, var("A")).unwrap();
+        ctx.push("x", x, arena).unwrap();
+        let x = arena.build_in(&ctx, var("A")).unwrap();
+        ctx.push("x", x, arena).unwrap();
+
+        let innermost = ctx.get_var("x", arena).unwrap();
+        let built = arena.build_in(&ctx, var("x")).unwrap();
+
+        assert_eq!(innermost, built);
+
+        let (name, type_) = ctx.get(3.into(), arena).unwrap();
+        let a = arena.build_in(&ctx, var("A")).unwrap();
+
+        assert_eq!(name, "A");
+        assert_eq!(type_, arena.build(prop()).unwrap());
+        assert_eq!(ctx.get(2.into(), arena), Some(("x", a)));
+        assert_eq!(ctx.get(0.into(), arena), None);
+        assert_eq!(ctx.get(4.into(), arena), None);
+        assert_eq!(ctx.get_var("y", arena), None);
+
+        assert_eq!(ctx.pop().map(|(name, _)| name), Some("x"));
+        assert_eq!(ctx.len(), 2);
+    });
+}
+
+#[test]
+fn unbound_variable() {
+    use_arena(|arena| {
+        let mut ctx = Context::new();
+
+        let a = arena.build_in(&ctx, prop()).unwrap();
+        ctx.push("A", a, arena).unwrap();
+        let x = arena.build_in(&ctx, var("A")).unwrap();
+        ctx.push("x", x, arena).unwrap();
+
+        let term = arena.build_in(&ctx, var("A")).unwrap();
+        ctx.pop();
+        ctx.pop();
+
+        assert!(matches!(
+            term.infer_in(&ctx, arena),
+            Err(Error {
+                kind: kernel::error::Kind::TypeChecker(ErrorKind::UnboundVariable(_)),
+                ..
+            })
+        ));
+    });
+}
+
+#[test]
+fn push_not_a_type() {
+    use_arena(|arena| {
+        let mut ctx = Context::new();
+
+        let a = arena.build_in(&ctx, prop()).unwrap();
+        ctx.push("A", a, arena).unwrap();
+        let x = arena.build_in(&ctx, var("A")).unwrap();
+        ctx.push("x", x, arena).unwrap();
+
+        let not_a_type = arena.build_in(&ctx, var("x")).unwrap();
+
+        assert!(ctx.push("y", not_a_type, arena).is_err());
+        assert_eq!(ctx.len(), 2);
+    });
+}
@@ -0,0 +1,444 @@
+#![feature(trait_alias)]
+
+use kernel::error::{Error, Kind, Result};
+use kernel::memory::arena::{use_arena, Arena};
+use kernel::memory::declaration::builder as declaration;
+use kernel::memory::inductive::builder::{inductive, BuilderTrait};
+use kernel::memory::level::builder::{self as level, append, const_, nil};
+use kernel::memory::term::builder::*;
+use kernel::trace::Trace;
+use kernel::type_checker;
+
+type Boxed<'build> = Box<dyn kernel::memory::term::builder::BuilderTrait<'build> + 'build>;
+
+fn boxed<'build, F: kernel::memory::term::builder::BuilderTrait<'build> + 'build>(f: F) -> Boxed<'build> {
+    Box::new(f)
+}
+
+/// Builds, checks and binds the given inductive type.
+fn declare<'arena, 'build, F: BuilderTrait<'build>>(arena: &mut Arena<'arena>, f: F) -> Result<'arena, ()> {
+    let inductive = arena.build_inductive(f)?;
+    arena.bind_inductive(inductive)
+}
+
+fn nat<'build>() -> impl BuilderTrait<'build> {
+    inductive("Nat", &[], 0, boxed(type_usize(0)), vec![
+        ("Zero", boxed(var("Nat"))),
+        ("Succ", boxed(prod("_", var("Nat"), var("Nat")))),
+    ])
+}
+
+fn use_nat_arena<F, T>(f: F) -> T
+where
+    F: for<'arena> FnOnce(&mut Arena<'arena>) -> T,
+{
+    use_arena(|arena| {
+        declare(arena, nat()).unwrap();
+
+        f(arena)
+    })
+}
+
+fn kind<'err, 'arena>(err: &'err Error<'arena>) -> &'err type_checker::ErrorKind<'arena> {
+    match err.kind {
+        Kind::TypeChecker(ref kind) => kind,
+        _ => panic!("unexpected error {err}"),
+    }
+}
+
+#[test]
+fn nat_add() {
+    use_nat_arena(|arena| {
+        // add n m := Nat_rec (fun _ => Nat) n (fun _ ih => Succ ih) m
+        let add = arena
+            .build(abs(
+                "n",
+                var("Nat"),
+                abs(
+                    "m",
+                    var("Nat"),
+                    app(
+                        app(
+                            app(
+                                app(decl(declaration::var("Nat_rec", append(nil(), const_(1)))), abs("_", var("Nat"), var("Nat"))),
+                                var("n"),
+                            ),
+                            abs("_", var("Nat"), abs("ih", var("Nat"), app(var("Succ"), var("ih")))),
+                        ),
+                        var("m"),
+                    ),
+                ),
+            ))
+            .unwrap();
+
+        let expected = arena.build(prod("_", var("Nat"), prod("_", var("Nat"), var("Nat")))).unwrap();
+        assert!(add.check(expected, arena).is_ok());
+        arena.bind("add", add);
+
+        let two_plus_one = arena
+            .build(app(app(var("add"), app(var("Succ"), app(var("Succ"), var("Zero")))), app(var("Succ"), var("Zero"))))
+            .unwrap();
+        let three = arena.build(app(var("Succ"), app(var("Succ"), app(var("Succ"), var("Zero"))))).unwrap();
+
+        assert!(two_plus_one.is_def_eq(three, arena).is_ok());
+        assert_eq!(two_plus_one.normal_form(arena), three);
+    });
+}
+
+#[test]
+fn stuck_recursor() {
+    use_nat_arena(|arena| {
+        let rec = arena
+            .build(abs(
+                "n",
+                var("Nat"),
+                app(
+                    app(
+                        app(
+                            app(decl(declaration::var("Nat_rec", append(nil(), const_(1)))), abs("_", var("Nat"), var("Nat"))),
+                            var("Zero"),
+                        ),
+                        abs("_", var("Nat"), abs("ih", var("Nat"), var("ih"))),
+                    ),
+                    var("n"),
+                ),
+            ))
+            .unwrap();
+
+        let zero = arena.build(abs("n", var("Nat"), var("Zero"))).unwrap();
+
+        assert!(rec.is_def_eq(zero, arena).is_err());
+    });
+}
+
+#[test]
+fn list_length() {
+    use_nat_arena(|arena| {
+        declare(
+            arena,
+            inductive("List", &[], 1, boxed(prod("A", type_usize(0), type_usize(0))), vec![
+                ("Nil", boxed(prod("A", type_usize(0), app(var("List"), var("A"))))),
+                (
+                    "Cons",
+                    boxed(prod(
+                        "A",
+                        type_usize(0),
+                        prod("_", var("A"), prod("_", app(var("List"), var("A")), app(var("List"), var("A")))),
+                    )),
+                ),
+            ]),
+        )
+        .unwrap();
+
+        // length l := List_rec Nat (fun _ => Nat) Zero (fun _ _ ih => Succ ih) l
+        let length = arena
+            .build(abs(
+                "l",
+                app(var("List"), var("Nat")),
+                app(
+                    app(
+                        app(
+                            app(
+                                app(decl(declaration::var("List_rec", append(nil(), const_(1)))), var("Nat")),
+                                abs("_", app(var("List"), var("Nat")), var("Nat")),
+                            ),
+                            var("Zero"),
+                        ),
+                        abs(
+                            "_",
+                            var("Nat"),
+                            abs("_", app(var("List"), var("Nat")), abs("ih", var("Nat"), app(var("Succ"), var("ih")))),
+                        ),
+                    ),
+                    var("l"),
+                ),
+            ))
+            .unwrap();
+
+        let expected = arena.build(prod("_", app(var("List"), var("Nat")), var("Nat"))).unwrap();
+        assert!(length.check(expected, arena).is_ok());
+        arena.bind("length", length);
+
+        let list = arena
+            .build(app(
+                var("length"),
+                app(
+                    app(app(var("Cons"), var("Nat")), var("Zero")),
+                    app(app(app(var("Cons"), var("Nat")), var("Zero")), app(var("Nil"), var("Nat"))),
+                ),
+            ))
+            .unwrap();
+        let two = arena.build(app(var("Succ"), app(var("Succ"), var("Zero")))).unwrap();
+
+        assert_eq!(list.normal_form(arena), two);
+    });
+}
+
+#[test]
+fn indexed_family() {
+    use_nat_arena(|arena| {
+        declare(
+            arena,
+            inductive("Vec", &[], 1, boxed(prod("A", type_usize(0), prod("_", var("Nat"), type_usize(0)))), vec![
+                ("VNil", boxed(prod("A", type_usize(0), app(app(var("Vec"), var("A")), var("Zero"))))),
+                (
+                    "VCons",
+                    boxed(prod(
+                        "A",
+                        type_usize(0),
+                        prod(
+                            "n",
+                            var("Nat"),
+                            prod(
+                                "_",
+                                var("A"),
+                                prod(
+                                    "_",
+                                    app(app(var("Vec"), var("A")), var("n")),
+                                    app(app(var("Vec"), var("A")), app(var("Succ"), var("n"))),
+                                ),
+                            ),
+                        ),
+                    )),
+                ),
+            ]),
+        )
+        .unwrap();
+
+        let rec = arena.build(decl(declaration::var("Vec_rec", append(nil(), const_(1))))).unwrap();
+        let rec_type = rec.infer(arena).unwrap();
+
+        assert!(rec_type.infer(arena).is_ok());
+    });
+}
+
+#[test]
+fn infinitely_branching() {
+    use_nat_arena(|arena| {
+        declare(
+            arena,
+            inductive("Tree", &[], 0, boxed(type_usize(0)), vec![
+                ("Leaf", boxed(var("Tree"))),
+                ("Node", boxed(prod("_", prod("_", var("Nat"), var("Tree")), var("Tree")))),
+            ]),
+        )
+        .unwrap();
+
+        // Tree_rec (fun _ => Nat) Zero (fun _ ih => Succ (ih Zero)) (Node (fun _ => Leaf))
+        let term = arena
+            .build(app(
+                app(
+                    app(
+                        app(decl(declaration::var("Tree_rec", append(nil(), const_(1)))), abs("_", var("Tree"), var("Nat"))),
+                        var("Zero"),
+                    ),
+                    abs(
+                        "_",
+                        prod("_", var("Nat"), var("Tree")),
+                        abs("ih", prod("_", var("Nat"), var("Nat")), app(var("Succ"), app(var("ih"), var("Zero")))),
+                    ),
+                ),
+                app(var("Node"), abs("_", var("Nat"), var("Leaf"))),
+            ))
+            .unwrap();
+        let one = arena.build(app(var("Succ"), var("Zero"))).unwrap();
+
+        assert!(term.check(var_type(arena, "Nat"), arena).is_ok());
+        assert_eq!(term.normal_form(arena), one);
+    });
+}
+
+fn var_type<'arena>(arena: &mut Arena<'arena>, name: &str) -> kernel::memory::term::Term<'arena> {
+    arena.build(var(name)).unwrap()
+}
+
+#[test]
+fn elimination_sorts() {
+    use_arena(|arena| {
+        // Eq is a subsingleton, and may eliminate into any sort
+        declare(
+            arena,
+            inductive("Eq", &[], 2, boxed(prod("A", type_usize(0), prod("_", var("A"), prod("_", var("A"), prop())))), vec![(
+                "Refl",
+                boxed(prod("A", type_usize(0), prod("a", var("A"), app(app(app(var("Eq"), var("A")), var("a")), var("a"))))),
+            )]),
+        )
+        .unwrap();
+
+        assert!(arena.build(decl(declaration::var("Eq_rec", append(nil(), const_(1))))).is_ok());
+
+        // Or has two constructors, and may only eliminate into Prop
+        declare(
+            arena,
+            inductive("Or", &[], 2, boxed(prod("A", prop(), prod("B", prop(), prop()))), vec![
+                ("Inl", boxed(prod("A", prop(), prod("B", prop(), prod("_", var("A"), app(app(var("Or"), var("A")), var("B"))))))),
+                ("Inr", boxed(prod("A", prop(), prod("B", prop(), prod("_", var("B"), app(app(var("Or"), var("A")), var("B"))))))),
+            ]),
+        )
+        .unwrap();
+
+        assert!(arena.get_binding("Or_rec").is_some());
+        assert!(arena.get_binding_decl("Or_rec").is_none());
+    });
+}
+
+#[test]
+fn universe_polymorphic() {
+    use_arena(|arena| {
+        declare(
+            arena,
+            inductive("Box", &["u"], 1, boxed(prod("A", sort(level::var("u")), sort(level::succ(level::var("u"))))), vec![(
+                "Wrap",
+                boxed(prod("A", sort(level::var("u")), prod("_", var("A"), app(var("Box"), var("A"))))),
+            )]),
+        )
+        .unwrap();
+
+        let wrap = arena.build(decl(declaration::var("Wrap", append(nil(), const_(1))))).unwrap();
+        assert!(wrap.infer(arena).is_ok());
+
+        let rec = arena
+            .build(decl(declaration::var("Box_rec", append(append(nil(), const_(1)), const_(2)))))
+            .unwrap();
+        let rec_type = rec.infer(arena).unwrap();
+        assert!(rec_type.infer(arena).is_ok());
+    });
+}
+
+#[test]
+fn universe_polymorphic_self_reference() {
+    use_arena(|arena| {
+        let self_instance = |lvl| var_instance("List", append(nil(), lvl));
+
+        // the constructors of List.{u} may refer to it as List.{u}
+        declare(
+            arena,
+            inductive("List", &["u"], 1, boxed(prod("A", sort(level::var("u")), sort(level::var("u")))), vec![
+                ("Nil", boxed(prod("A", sort(level::var("u")), app(self_instance(level::var("u")), var("A"))))),
+                (
+                    "Cons",
+                    boxed(prod(
+                        "A",
+                        sort(level::var("u")),
+                        prod(
+                            "_",
+                            var("A"),
+                            prod("_", app(self_instance(level::var("u")), var("A")), app(var("List"), var("A"))),
+                        ),
+                    )),
+                ),
+            ]),
+        )
+        .unwrap();
+
+        let empty = arena.build(decl(declaration::var("Nil", append(nil(), const_(1))))).unwrap();
+        assert!(empty.infer(arena).is_ok());
+
+        // but not with other universe levels
+        let err = declare(
+            arena,
+            inductive("Bad", &["u"], 1, boxed(prod("A", sort(level::var("u")), sort(level::var("u")))), vec![(
+                "bad",
+                boxed(prod("A", sort(level::var("u")), app(var_instance("Bad", append(nil(), const_(0))), var("A")))),
+            )]),
+        )
+        .unwrap_err();
+
+        assert_eq!(err.kind, Kind::Term(ErrorKind::LocalInstance("Bad")));
+        assert_eq!(err.to_string(), "Bad may only be instantiated with its own universe variables, in order");
+    });
+}
+
+#[test]
+fn not_strictly_positive() {
+    use_arena(|arena| {
+        let err = declare(
+            arena,
+            inductive("Bad", &[], 0, boxed(type_usize(0)), vec![(
+                "Mk",
+                boxed(prod("_", prod("_", var("Bad"), var("Bad")), var("Bad"))),
+            )]),
+        )
+        .unwrap_err();
+
+        assert!(matches!(kind(&err), type_checker::ErrorKind::NotStrictlyPositive(..)));
+        assert_eq!(err.trace, vec![Trace::Left, Trace::Left, Trace::Right]);
+        assert_eq!(err.to_string(), "non strictly positive occurrence of the inductive type in Bad -> Bad, argument of constructor Mk");
+
+        // the variables of the offending argument are printed with their names
+        let err = declare(
+            arena,
+            inductive("Bad", &[], 1, boxed(prod("A", type_usize(0), type_usize(0))), vec![(
+                "Mk",
+                boxed(prod(
+                    "A",
+                    type_usize(0),
+                    prod("f", prod("_", app(var("Bad"), var("A")), var("A")), app(var("Bad"), var("A"))),
+                )),
+            )]),
+        )
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "non strictly positive occurrence of the inductive type in Bad A -> A, argument of constructor Mk");
+    });
+}
+
+#[test]
+fn wrong_conclusion() {
+    use_nat_arena(|arena| {
+        let err = declare(
+            arena,
+            inductive("Bad", &[], 0, boxed(type_usize(0)), vec![
+                ("Other", boxed(var("Bad"))),
+                ("Mk", boxed(prod("_", var("Bad"), var("Nat")))),
+            ]),
+        )
+        .unwrap_err();
+
+        assert!(matches!(kind(&err), type_checker::ErrorKind::NotAConstructor(..)));
+        assert_eq!(err.trace, vec![Trace::Right, Trace::Left, Trace::Right, Trace::Right]);
+        assert_eq!(err.to_string(), "invalid type for constructor Mk, ending with Nat");
+    });
+}
+
+#[test]
+fn universe_too_large() {
+    use_arena(|arena| {
+        let err = declare(
+            arena,
+            inductive("Big", &[], 0, boxed(type_usize(0)), vec![("Mk", boxed(prod("_", type_usize(0), var("Big"))))]),
+        )
+        .unwrap_err();
+
+        assert!(matches!(kind(&err), type_checker::ErrorKind::UniverseTooLarge(..)));
+        assert_eq!(err.to_string(), "argument Type: Type 1 of constructor Mk is too large for an inductive type living in Type");
+    });
+}
+
+#[test]
+fn parameter_mismatch() {
+    use_arena(|arena| {
+        let err = declare(
+            arena,
+            inductive("Bad", &[], 1, boxed(prod("A", type_usize(0), type_usize(0))), vec![(
+                "Mk",
+                boxed(prod("A", prop(), app(var("Bad"), var("A")))),
+            )]),
+        )
+        .unwrap_err();
+
+        assert!(matches!(kind(&err), type_checker::ErrorKind::WrongArgumentType(..)));
+        assert_eq!(err.trace, vec![Trace::Right, Trace::Left, Trace::Right]);
+    });
+}
+
+#[test]
+fn not_an_arity() {
+    use_arena(|arena| {
+        assert!(declare(arena, inductive("Pred", &[], 0, boxed(prod("_", prop(), prop())), vec![])).is_ok());
+
+        let err = declare(arena, inductive("Bad", &[], 2, boxed(prod("_", prop(), prop())), vec![])).unwrap_err();
+        assert!(matches!(kind(&err), type_checker::ErrorKind::NotAnArity(_)));
+        assert_eq!(err.trace, vec![Trace::Left]);
+    });
+}
@@ -0,0 +1,275 @@
+use kernel::memory::arena::{use_arena_with_axioms, Arena};
+use kernel::memory::declaration::builder as declaration;
+use kernel::memory::level::builder::{append, const_, nil, var as lvl_var};
+use kernel::memory::term::builder::*;
+
+fn use_irrelevance_arena<F, T>(f: F) -> T
+where
+    F: for<'arena> FnOnce(&mut Arena<'arena>) -> T,
+{
+    use_arena_with_axioms(|arena| {
+        let my_true = arena.build(prod("_", var("False"), var("False"))).unwrap();
+        arena.bind("myTrue", my_true);
+
+        let id = arena
+            .build_declaration(declaration::declaration(abs("A", sort(lvl_var("u")), abs("x", var("A"), var("x"))), &["u"]))
+            .unwrap();
+        arena.bind_decl("id", id);
+
+        f(arena)
+    })
+}
+
+#[test]
+fn irrelevance_example() {
+    use_irrelevance_arena(|arena| {
+        let tt1 = arena
+            .build(app(decl(declaration::var("id", append(nil(), const_(0)))), var("False")))
+            .unwrap();
+        let tt2 = arena
+            .build(abs(
+                "h",
+                var("False"),
+                app(
+                    app(decl(declaration::var("False_rec", append(nil(), const_(0)))), abs("h", var("False"), var("False"))),
+                    var("h"),
+                ),
+            ))
+            .unwrap();
+
+        assert!(tt1.is_def_eq(tt2, arena).is_ok());
+
+        arena.bind("tt1", tt1);
+        arena.bind("tt2", tt2);
+
+        let refl = arena
+            .build(app(app(decl(declaration::var("Refl", append(nil(), const_(0)))), var("myTrue")), var("tt1")))
+            .unwrap();
+        let eq = arena
+            .build(app(app(app(decl(declaration::var("Eq", append(nil(), const_(0)))), var("myTrue")), var("tt1")), var("tt2")))
+            .unwrap();
+
+        assert!(refl.check(eq, arena).is_ok());
+    });
+}
+
+#[test]
+fn irrelevance_open_terms() {
+    use_irrelevance_arena(|arena| {
+        // fun (P: Prop) (h1 h2: P) => Refl.{0} P h1 : (P: Prop) -> (h1 h2: P) -> Eq.{0} P h1 h2
+        let proof = arena
+            .build(abs(
+                "P",
+                prop(),
+                abs(
+                    "h1",
+                    var("P"),
+                    abs("h2", var("P"), app(app(decl(declaration::var("Refl", append(nil(), const_(0)))), var("P")), var("h1"))),
+                ),
+            ))
+            .unwrap();
+        let goal = arena
+            .build(prod(
+                "P",
+                prop(),
+                prod(
+                    "h1",
+                    var("P"),
+                    prod(
+                        "h2",
+                        var("P"),
+                        app(app(app(decl(declaration::var("Eq", append(nil(), const_(0)))), var("P")), var("h1")), var("h2")),
+                    ),
+                ),
+            ))
+            .unwrap();
+
+        assert!(proof.check(goal, arena).is_ok());
+    });
+}
+
+#[test]
+fn relevance_open_terms() {
+    use_irrelevance_arena(|arena| {
+        // fun (A: Type) (x y: A) => Refl.{1} A x is not of type (A: Type) -> (x y: A) -> Eq.{1} A x y
+        let proof = arena
+            .build(abs(
+                "A",
+                type_usize(0),
+                abs(
+                    "x",
+                    var("A"),
+                    abs("y", var("A"), app(app(decl(declaration::var("Refl", append(nil(), const_(1)))), var("A")), var("x"))),
+                ),
+            ))
+            .unwrap();
+        let goal = arena
+            .build(prod(
+                "A",
+                type_usize(0),
+                prod(
+                    "x",
+                    var("A"),
+                    prod(
+                        "y",
+                        var("A"),
+                        app(app(app(decl(declaration::var("Eq", append(nil(), const_(1)))), var("A")), var("x")), var("y")),
+                    ),
+                ),
+            ))
+            .unwrap();
+
+        assert!(proof.check(goal, arena).is_err());
+    });
+}
+
+#[test]
+fn irrelevance_universe_polymorphic() {
+    use_irrelevance_arena(|arena| {
+        // id.{0} P h1 and h2 are proofs of the same proposition
+        let proof = arena
+            .build(abs(
+                "P",
+                prop(),
+                abs(
+                    "h1",
+                    var("P"),
+                    abs(
+                        "h2",
+                        var("P"),
+                        app(
+                            app(decl(declaration::var("Refl", append(nil(), const_(0)))), var("P")),
+                            app(app(decl(declaration::var("id", append(nil(), const_(0)))), var("P")), var("h1")),
+                        ),
+                    ),
+                ),
+            ))
+            .unwrap();
+        let goal = arena
+            .build(prod(
+                "P",
+                prop(),
+                prod(
+                    "h1",
+                    var("P"),
+                    prod(
+                        "h2",
+                        var("P"),
+                        app(
+                            app(
+                                app(decl(declaration::var("Eq", append(nil(), const_(0)))), var("P")),
+                                app(app(decl(declaration::var("id", append(nil(), const_(0)))), var("P")), var("h1")),
+                            ),
+                            var("h2"),
+                        ),
+                    ),
+                ),
+            ))
+            .unwrap();
+
+        assert!(proof.check(goal, arena).is_ok());
+
+        // id.{1} A x and y are not identified when A is a type
+        let proof = arena
+            .build(abs(
+                "A",
+                type_usize(0),
+                abs(
+                    "x",
+                    var("A"),
+                    abs(
+                        "y",
+                        var("A"),
+                        app(
+                            app(decl(declaration::var("Refl", append(nil(), const_(1)))), var("A")),
+                            app(app(decl(declaration::var("id", append(nil(), const_(1)))), var("A")), var("x")),
+                        ),
+                    ),
+                ),
+            ))
+            .unwrap();
+        let goal = arena
+            .build(prod(
+                "A",
+                type_usize(0),
+                prod(
+                    "x",
+                    var("A"),
+                    prod(
+                        "y",
+                        var("A"),
+                        app(
+                            app(
+                                app(decl(declaration::var("Eq", append(nil(), const_(1)))), var("A")),
+                                app(app(decl(declaration::var("id", append(nil(), const_(1)))), var("A")), var("x")),
+                            ),
+                            var("y"),
+                        ),
+                    ),
+                ),
+            ))
+            .unwrap();
+
+        assert!(proof.check(goal, arena).is_err());
+    });
+}
+
+#[test]
+fn irrelevance_polymorphic_declaration() {
+    use_irrelevance_arena(|arena| {
+        // a declaration whose body is only a proof once its universe is instantiated with 0
+        let decl_ = arena
+            .build_declaration(declaration::declaration(
+                abs("A", sort(lvl_var("u")), abs("x", var("A"), abs("y", var("A"), var("x")))),
+                &["u"],
+            ))
+            .unwrap();
+        arena.bind_decl("first", decl_);
+
+        let first = |level| {
+            abs(
+                "P",
+                prop(),
+                abs(
+                    "h1",
+                    var("P"),
+                    abs(
+                        "h2",
+                        var("P"),
+                        app(
+                            app(app(decl(declaration::var("first", append(nil(), const_(level)))), var("P")), var("h1")),
+                            var("h2"),
+                        ),
+                    ),
+                ),
+            )
+        };
+        let second = abs("P", prop(), abs("h1", var("P"), abs("h2", var("P"), var("h2"))));
+
+        let first = arena.build(first(0)).unwrap();
+        let second = arena.build(second).unwrap();
+
+        assert!(first.is_def_eq(second, arena).is_ok());
+
+        let first = arena
+            .build(abs(
+                "A",
+                type_usize(0),
+                abs(
+                    "x",
+                    var("A"),
+                    abs(
+                        "y",
+                        var("A"),
+                        app(app(app(decl(declaration::var("first", append(nil(), const_(1)))), var("A")), var("x")), var("y")),
+                    ),
+                ),
+            ))
+            .unwrap();
+        let second = arena
+            .build(abs("A", type_usize(0), abs("x", var("A"), abs("y", var("A"), var("y")))))
+            .unwrap();
+
+        assert!(first.is_def_eq(second, arena).is_err());
+    });
+}
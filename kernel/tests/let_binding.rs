@@ -0,0 +1,115 @@
+use kernel::memory::arena::use_arena_with_axioms;
+use kernel::memory::term::builder::*;
+use kernel::memory::term::pretty;
+use kernel::trace::Trace;
+use num_bigint::BigUint;
+
+#[test]
+fn zeta_reduction() {
+    use_arena_with_axioms(|arena| {
+        let three = BigUint::from(3_u32);
+
+        // let n: Nat := 3; Nat_add n n
+        let term = arena
+            .build(let_("n", var("Nat"), nat_lit(&three), app(app(var("Nat_add"), var("n")), var("n"))))
+            .unwrap();
+        let expected = arena.build(nat_lit(&BigUint::from(6_u32))).unwrap();
+
+        assert_eq!(term.infer(arena), Ok(arena.build(var("Nat")).unwrap()));
+        assert_eq!(term.whnf(arena), expected);
+        assert_eq!(term.normal_form(arena), expected);
+    });
+}
+
+#[test]
+fn dependent_body() {
+    use_arena_with_axioms(|arena| {
+        let three = BigUint::from(3_u32);
+        let nat = arena.build(var("Nat")).unwrap();
+
+        // let A: Type := Nat; (fun y: A => y) 3, which is only well-typed because A is known to be Nat
+        let term = arena
+            .build(let_("A", type_usize(0), var("Nat"), app(abs("y", var("A"), var("y")), nat_lit(&three))))
+            .unwrap();
+
+        assert_eq!(term.infer(arena), Ok(nat));
+
+        // let A: Type := Nat; let a: A := 3; Succ a
+        let term = arena
+            .build(let_("A", type_usize(0), var("Nat"), let_("a", var("A"), nat_lit(&three), app(var("Succ"), var("a")))))
+            .unwrap();
+
+        assert_eq!(term.infer(arena), Ok(nat));
+
+        // fun (P: Nat -> Prop) (h: P 3) => let n: Nat := 3; (fun h': P n => h') h, whose body is a
+        // proof of P n, which is only possible because the value of n is known in the body
+        let term = arena
+            .build(abs(
+                "P",
+                prod("_", var("Nat"), prop()),
+                abs(
+                    "h",
+                    app(var("P"), nat_lit(&three)),
+                    let_("n", var("Nat"), nat_lit(&three), app(abs("h'", app(var("P"), var("n")), var("h'")), var("h"))),
+                ),
+            ))
+            .unwrap();
+        let expected = arena
+            .build(prod(
+                "P",
+                prod("_", var("Nat"), prop()),
+                prod("h", app(var("P"), nat_lit(&three)), app(var("P"), nat_lit(&three))),
+            ))
+            .unwrap();
+
+        assert!(term.check(expected, arena).is_ok());
+    });
+}
+
+#[test]
+fn let_in_type() {
+    use_arena_with_axioms(|arena| {
+        let three = BigUint::from(3_u32);
+
+        // fun (P: Nat -> Prop) => (let n: Nat := 3; P n) -> P 3
+        let term = arena
+            .build(abs(
+                "P",
+                prod("_", var("Nat"), prop()),
+                prod("h", let_("n", var("Nat"), nat_lit(&three), app(var("P"), var("n"))), app(var("P"), nat_lit(&three))),
+            ))
+            .unwrap();
+        let identity = arena
+            .build(abs(
+                "P",
+                prod("_", var("Nat"), prop()),
+                prod("h", app(var("P"), nat_lit(&three)), app(var("P"), nat_lit(&three))),
+            ))
+            .unwrap();
+
+        assert!(term.is_def_eq(identity, arena).is_ok());
+    });
+}
+
+#[test]
+fn ill_typed_value() {
+    use_arena_with_axioms(|arena| {
+        // let x: Prop := Prop; x
+        let term = arena.build(let_("x", prop(), prop(), var("x"))).unwrap();
+        let err = term.infer(arena).unwrap_err();
+
+        assert_eq!(err.trace, vec![Trace::Middle]);
+    });
+}
+
+#[test]
+fn pretty_print() {
+    use_arena_with_axioms(|arena| {
+        let term = arena
+            .build(abs("A", prop(), abs("a", var("A"), let_("x", var("A"), var("a"), var("x")))))
+            .unwrap();
+
+        assert_eq!(pretty::Term(term).to_string(), "λ A: Prop => λ a: A => let x: A := a; x");
+        assert_eq!(term.to_string(), "λ Prop => λ 1 => let 2 := 1; 1");
+    });
+}
@@ -0,0 +1,159 @@
+use kernel::memory::arena::use_arena_with_axioms;
+use kernel::memory::declaration::builder as declaration;
+use kernel::memory::level::builder::{append, const_, nil};
+use kernel::memory::term::builder::*;
+use num_bigint::BigUint;
+
+fn big(n: &str) -> BigUint {
+    n.parse().unwrap()
+}
+
+#[test]
+fn literal_type() {
+    use_arena_with_axioms(|arena| {
+        let n = big("123456789123456789123456789");
+        let lit = arena.build(nat_lit(&n)).unwrap();
+        let nat = arena.build(var("Nat")).unwrap();
+
+        assert_eq!(lit.infer(arena), Ok(nat));
+        assert_eq!(lit.to_nat_lit(), Some(n));
+        assert_eq!(lit.to_string(), "123456789123456789123456789");
+    });
+}
+
+#[test]
+fn literal_conversion() {
+    use_arena_with_axioms(|arena| {
+        let zero = BigUint::from(0_u32);
+        let three = BigUint::from(3_u32);
+
+        let lit_zero = arena.build(nat_lit(&zero)).unwrap();
+        let unary_zero = arena.build(var("Zero")).unwrap();
+
+        assert!(lit_zero.is_def_eq(unary_zero, arena).is_ok());
+
+        let lit = arena.build(nat_lit(&three)).unwrap();
+        let unary = arena.build(app(var("Succ"), app(var("Succ"), app(var("Succ"), var("Zero"))))).unwrap();
+        let mixed = arena.build(app(var("Succ"), nat_lit(&BigUint::from(2_u32)))).unwrap();
+
+        assert!(lit.is_def_eq(unary, arena).is_ok());
+        assert!(unary.is_def_eq(lit, arena).is_ok());
+        assert!(lit.is_def_eq(mixed, arena).is_ok());
+
+        let wrong = arena.build(app(var("Succ"), nat_lit(&three))).unwrap();
+
+        assert!(lit.is_def_eq(wrong, arena).is_err());
+        assert!(lit.is_def_eq(lit_zero, arena).is_err());
+
+        // fun n: Nat => Succ n is not the constant function equal to 1
+        let succ = arena.build(abs("n", var("Nat"), app(var("Succ"), var("n")))).unwrap();
+        let one = arena.build(abs("n", var("Nat"), nat_lit(&BigUint::from(1_u32)))).unwrap();
+
+        assert!(succ.is_def_eq(one, arena).is_err());
+    });
+}
+
+#[test]
+fn accelerated_arithmetic() {
+    use_arena_with_axioms(|arena| {
+        let cases = [
+            ("Nat_add", "99999999999999999999", "1", "100000000000000000000"),
+            (
+                "Nat_mul",
+                "1267650600228229401496703205376",
+                "1267650600228229401496703205376",
+                "1606938044258990275541962092341162602522202993782792835301376",
+            ),
+            ("Nat_sub", "100000000000000000000", "1", "99999999999999999999"),
+            ("Nat_sub", "3", "5", "0"),
+            ("Nat_div", "17", "5", "3"),
+            ("Nat_div", "17", "0", "0"),
+            ("Nat_mod", "17", "5", "2"),
+            ("Nat_mod", "17", "0", "17"),
+        ];
+
+        for (operation, m, n, expected) in cases {
+            let (m, n, expected) = (big(m), big(n), big(expected));
+            let term = arena.build(app(app(var(operation), nat_lit(&m)), nat_lit(&n))).unwrap();
+            let expected = arena.build(nat_lit(&expected)).unwrap();
+
+            assert_eq!(term.whnf(arena), expected, "{operation}");
+        }
+
+        let comparisons = [
+            ("Nat_beq", "12345678901234567890", "12345678901234567890", "BTrue"),
+            ("Nat_beq", "12345678901234567890", "12345678901234567891", "BFalse"),
+            ("Nat_ble", "12345678901234567890", "12345678901234567891", "BTrue"),
+            ("Nat_ble", "12345678901234567891", "12345678901234567890", "BFalse"),
+        ];
+
+        for (operation, m, n, expected) in comparisons {
+            let (m, n) = (big(m), big(n));
+            let term = arena.build(app(app(var(operation), nat_lit(&m)), nat_lit(&n))).unwrap();
+            let expected = arena.build(var(expected)).unwrap();
+
+            assert_eq!(term.whnf(arena), expected, "{operation}");
+        }
+    });
+}
+
+#[test]
+fn structural_arithmetic() {
+    use_arena_with_axioms(|arena| {
+        let two = BigUint::from(2_u32);
+
+        // fun n: Nat => Nat_add n 2 is fun n: Nat => Succ (Succ n)
+        let add = arena
+            .build(abs("n", var("Nat"), app(app(var("Nat_add"), var("n")), nat_lit(&two))))
+            .unwrap();
+        let expected = arena.build(abs("n", var("Nat"), app(var("Succ"), app(var("Succ"), var("n"))))).unwrap();
+
+        assert!(add.is_def_eq(expected, arena).is_ok());
+
+        // fun n: Nat => Nat_sub (Succ n) 1 is fun n: Nat => n
+        let sub = arena
+            .build(abs("n", var("Nat"), app(app(var("Nat_sub"), app(var("Succ"), var("n"))), nat_lit(&BigUint::from(1_u32)))))
+            .unwrap();
+        let expected = arena.build(abs("n", var("Nat"), var("n"))).unwrap();
+
+        assert!(sub.is_def_eq(expected, arena).is_ok());
+
+        // fun n: Nat => Nat_ble 0 n is fun n: Nat => BTrue
+        let ble = arena
+            .build(abs("n", var("Nat"), app(app(var("Nat_ble"), var("Zero")), var("n"))))
+            .unwrap();
+        let expected = arena.build(abs("n", var("Nat"), var("BTrue"))).unwrap();
+
+        assert!(ble.is_def_eq(expected, arena).is_ok());
+
+        // fun n: Nat => Nat_add 2 n is stuck
+        let add = arena
+            .build(abs("n", var("Nat"), app(app(var("Nat_add"), nat_lit(&two)), var("n"))))
+            .unwrap();
+        let expected = arena.build(abs("n", var("Nat"), app(var("Succ"), app(var("Succ"), var("n"))))).unwrap();
+
+        assert!(add.is_def_eq(expected, arena).is_err());
+    });
+}
+
+#[test]
+fn recursor_on_literals() {
+    use_arena_with_axioms(|arena| {
+        // Nat_rec.{1} (fun _ => Nat) 4 (fun _ ih => Succ ih) 3
+        let rec = arena
+            .build(app(
+                app(
+                    app(
+                        app(decl(declaration::var("Nat_rec", append(nil(), const_(1)))), abs("_", var("Nat"), var("Nat"))),
+                        nat_lit(&BigUint::from(4_u32)),
+                    ),
+                    abs("_", var("Nat"), abs("ih", var("Nat"), app(var("Succ"), var("ih")))),
+                ),
+                nat_lit(&BigUint::from(3_u32)),
+            ))
+            .unwrap();
+        let expected = arena.build(nat_lit(&BigUint::from(7_u32))).unwrap();
+
+        assert!(rec.is_def_eq(expected, arena).is_ok());
+    });
+}
@@ -0,0 +1,73 @@
+use kernel::memory::arena::use_arena_with_axioms;
+use kernel::memory::declaration::builder as declaration;
+use kernel::memory::level::builder::{append, const_, nil, var as lvl_var};
+use kernel::memory::term::builder::*;
+
+#[test]
+fn opaque_terms() {
+    use_arena_with_axioms(|arena| {
+        let refl_zero = || app(app(decl(declaration::var("Refl", append(nil(), const_(1)))), var("Nat")), var("Zero"));
+        let eq_zero = app(app(app(decl(declaration::var("Eq", append(nil(), const_(1)))), var("Nat")), var("Zero")), var("Zero"));
+
+        let proof = arena.build_declaration(declaration::declaration(refl_zero(), &[])).unwrap();
+        assert!(!proof.is_opaque());
+        assert!(arena.bind_opaque("zero_eq", proof).is_ok());
+
+        let opaque_proof = arena.build(var("zero_eq")).unwrap();
+        let statement = arena.build(eq_zero).unwrap();
+        let refl_zero = arena.build(refl_zero()).unwrap();
+
+        // the term is still typed as usual
+        assert!(opaque_proof.infer(arena).unwrap().is_def_eq(statement, arena).is_ok());
+
+        // but it is never unfolded
+        assert_eq!(opaque_proof.whnf(arena), opaque_proof);
+        assert_eq!(opaque_proof.normal_form(arena), opaque_proof);
+
+        // it remains convertible to the other proofs of its statement, by proof irrelevance
+        assert!(opaque_proof.is_def_eq(refl_zero, arena).is_ok());
+    });
+}
+
+#[test]
+fn opaque_declarations() {
+    use_arena_with_axioms(|arena| {
+        // fun (A : Sort u) (x : A) => Refl.{u} A x
+        let refl = arena
+            .build_declaration(declaration::declaration(
+                abs(
+                    "A",
+                    sort(lvl_var("u")),
+                    abs("x", var("A"), app(app(decl(declaration::var("Refl", append(nil(), lvl_var("u")))), var("A")), var("x"))),
+                ),
+                &["u"],
+            ))
+            .unwrap();
+        assert!(arena.bind_opaque("refl", refl).is_ok());
+
+        let refl_zero = arena
+            .build(app(app(decl(declaration::var("refl", append(nil(), const_(1)))), var("Nat")), var("Zero")))
+            .unwrap();
+
+        assert_eq!(refl_zero.whnf(arena), refl_zero);
+        assert_eq!(refl_zero.normal_form(arena), refl_zero);
+    });
+}
+
+#[test]
+fn opaque_non_proofs() {
+    use_arena_with_axioms(|arena| {
+        let two = arena
+            .build_declaration(declaration::declaration(app(var("Succ"), app(var("Succ"), var("Zero"))), &[]))
+            .unwrap();
+        let id = arena
+            .build_declaration(declaration::declaration(abs("A", sort(lvl_var("u")), abs("x", var("A"), var("x"))), &["u"]))
+            .unwrap();
+
+        // only proofs may be opaque
+        assert_eq!(arena.bind_opaque("two", two).unwrap_err().to_string(), "Nat is not a proposition");
+        assert!(arena.bind_opaque("id", id).is_err());
+        assert!(!arena.is_bound("two"));
+        assert!(!arena.is_bound("id"));
+    });
+}
@@ -0,0 +1,35 @@
+use kernel::memory::arena::use_arena_with_axioms;
+use kernel::memory::context::Context;
+use kernel::memory::term::builder::*;
+
+#[test]
+fn pinned_terms() {
+    use_arena_with_axioms(|arena| {
+        // fun x: Nat => Succ x
+        let succ = arena.build(abs("x", var("Nat"), app(var("Succ"), var("x")))).unwrap();
+        let pin = arena.pin(succ).unwrap();
+
+        assert_eq!(arena.pin(succ), Some(pin));
+        assert_eq!(arena.build(pinned(pin)), Ok(succ));
+
+        // pinned terms can be embedded under binders
+        let term = arena.build(abs("n", var("Nat"), app(pinned(pin), var("n")))).unwrap();
+        let expected = arena.build(abs("n", var("Nat"), app(var("Succ"), var("n")))).unwrap();
+
+        assert!(term.is_def_eq(expected, arena).is_ok());
+        assert!(term.infer(arena).is_ok());
+    });
+}
+
+#[test]
+fn open_terms_cannot_be_pinned() {
+    use_arena_with_axioms(|arena| {
+        let mut ctx = Context::new();
+        let nat = arena.build(var("Nat")).unwrap();
+        ctx.push("n", nat, arena).unwrap();
+
+        let n = arena.build_in(&ctx, app(var("Succ"), var("n"))).unwrap();
+
+        assert_eq!(arena.pin(n), None);
+    });
+}
@@ -0,0 +1,110 @@
+use kernel::memory::arena::use_arena_with_axioms;
+use kernel::memory::declaration::builder as declaration;
+use kernel::memory::level::builder::{append, const_, nil};
+use kernel::memory::term::builder::*;
+use num_bigint::BigUint;
+
+/// Builds the instance of the declaration `name` at universe levels 1 and 0.
+fn sigma_decl<'build>(name: &'build str) -> impl BuilderTrait<'build> {
+    decl(declaration::var(name, append(append(nil(), const_(1)), const_(0))))
+}
+
+/// Builds `fun n: Nat => Eq.{1} Nat n n`.
+fn family<'build>() -> impl BuilderTrait<'build> {
+    let eq = decl(declaration::var("Eq", append(nil(), const_(1))));
+
+    abs("n", var("Nat"), app(app(app(eq, var("Nat")), var("n")), var("n")))
+}
+
+/// Builds `Sigma.{1, 0} Nat (fun n: Nat => Eq.{1} Nat n n)`.
+fn sigma<'build>() -> impl BuilderTrait<'build> {
+    app(app(sigma_decl("Sigma"), var("Nat")), family())
+}
+
+/// Builds `Sigma_mk.{1, 0} Nat (fun n: Nat => Eq.{1} Nat n n) n (Refl.{1} Nat n)`.
+fn pair(n: &BigUint) -> impl BuilderTrait<'_> {
+    let refl = decl(declaration::var("Refl", append(nil(), const_(1))));
+
+    app(app(app(app(sigma_decl("Sigma_mk"), var("Nat")), family()), nat_lit(n)), app(app(refl, var("Nat")), nat_lit(n)))
+}
+
+/// Builds the projection `name` of `term`.
+fn proj<'build, F: BuilderTrait<'build>>(name: &'build str, term: F) -> impl BuilderTrait<'build> {
+    app(app(app(sigma_decl(name), var("Nat")), family()), term)
+}
+
+#[test]
+fn axiom_types() {
+    use_arena_with_axioms(|arena| {
+        for name in ["Sigma", "Sigma_mk", "Sigma_fst", "Sigma_snd"] {
+            let axiom = arena.build(sigma_decl(name)).unwrap();
+            let type_ = axiom.infer(arena).unwrap();
+
+            assert!(type_.infer(arena).is_ok(), "{name}");
+        }
+
+        let type_ = arena.build(sigma()).unwrap();
+        let sort = arena.build(type_usize(0)).unwrap();
+
+        assert_eq!(type_.infer(arena), Ok(sort));
+    });
+}
+
+#[test]
+fn projections() {
+    use_arena_with_axioms(|arena| {
+        let two = BigUint::from(2_u32);
+
+        let term = arena.build(pair(&two)).unwrap();
+        let type_ = arena.build(sigma()).unwrap();
+
+        assert!(term.check(type_, arena).is_ok());
+
+        let fst = arena.build(proj("Sigma_fst", pair(&two))).unwrap();
+        let expected = arena.build(nat_lit(&two)).unwrap();
+
+        assert_eq!(fst.whnf(arena), expected);
+
+        let snd = arena.build(proj("Sigma_snd", pair(&two))).unwrap();
+        let expected = arena
+            .build(app(app(decl(declaration::var("Refl", append(nil(), const_(1)))), var("Nat")), nat_lit(&two)))
+            .unwrap();
+
+        assert_eq!(snd.whnf(arena), expected);
+
+        // fun p: Sigma Nat family => Sigma_snd Nat family p : (p: Sigma Nat family) -> Eq Nat (Sigma_fst p) (Sigma_fst p)
+        let snd = arena.build(abs("p", sigma(), proj("Sigma_snd", var("p")))).unwrap();
+        let eq = decl(declaration::var("Eq", append(nil(), const_(1))));
+        let expected = arena
+            .build(prod("p", sigma(), app(app(app(eq, var("Nat")), proj("Sigma_fst", var("p"))), proj("Sigma_fst", var("p")))))
+            .unwrap();
+
+        assert!(snd.check(expected, arena).is_ok());
+    });
+}
+
+#[test]
+fn pair_eta() {
+    use_arena_with_axioms(|arena| {
+        // fun p: Sigma Nat family => p is fun p: Sigma Nat family => Sigma_mk Nat family (Sigma_fst p) (Sigma_snd p)
+        let id = arena.build(abs("p", sigma(), var("p"))).unwrap();
+        let expanded = arena
+            .build(abs(
+                "p",
+                sigma(),
+                app(
+                    app(app(app(sigma_decl("Sigma_mk"), var("Nat")), family()), proj("Sigma_fst", var("p"))),
+                    proj("Sigma_snd", var("p")),
+                ),
+            ))
+            .unwrap();
+
+        assert!(id.is_def_eq(expanded, arena).is_ok());
+        assert!(expanded.is_def_eq(id, arena).is_ok());
+
+        let three = BigUint::from(3_u32);
+        let wrong = arena.build(abs("p", sigma(), pair(&three))).unwrap();
+
+        assert!(id.is_def_eq(wrong, arena).is_err());
+    });
+}
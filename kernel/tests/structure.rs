@@ -0,0 +1,171 @@
+#![feature(trait_alias)]
+
+use kernel::error::{Error, Kind, Result};
+use kernel::memory::arena::{use_arena, Arena};
+use kernel::memory::declaration::builder as declaration;
+use kernel::memory::inductive::builder::{structure, BuilderTrait};
+use kernel::memory::level::builder::{self as level, append, nil};
+use kernel::memory::term::builder::*;
+use kernel::trace::Trace;
+use kernel::type_checker;
+
+type Boxed<'build> = Box<dyn kernel::memory::term::builder::BuilderTrait<'build> + 'build>;
+
+fn boxed<'build, F: kernel::memory::term::builder::BuilderTrait<'build> + 'build>(f: F) -> Boxed<'build> {
+    Box::new(f)
+}
+
+/// Builds, checks and binds the given structure.
+fn declare<'arena, 'build, F: BuilderTrait<'build>>(arena: &mut Arena<'arena>, f: F, fields: &[&str]) -> Result<'arena, ()> {
+    let inductive = arena.build_inductive(f)?;
+    arena.bind_structure(inductive, fields)
+}
+
+fn kind<'err, 'arena>(err: &'err Error<'arena>) -> &'err type_checker::ErrorKind<'arena> {
+    match err.kind {
+        Kind::TypeChecker(ref kind) => kind,
+        _ => panic!("unexpected error {err}"),
+    }
+}
+
+#[test]
+fn pair_projections() {
+    use_arena(|arena| {
+        // structure Pair (A: Type) (B: Type) where fst: A; snd: B
+        let pair = structure("Pair", &[], vec![("A", boxed(type_usize(0))), ("B", boxed(type_usize(0)))], None, vec![
+            ("fst", boxed(var("A"))),
+            ("snd", boxed(var("B"))),
+        ]);
+        declare(arena, pair, &["fst", "snd"]).unwrap();
+
+        let type_ = arena.build(var("Pair")).unwrap();
+        let expected = arena.build(prod("_", type_usize(0), prod("_", type_usize(0), type_usize(0)))).unwrap();
+        assert_eq!(type_.infer(arena), Ok(expected));
+
+        // Pair.mk : (A B: Type) -> A -> B -> Pair A B
+        let mk = arena.build(var("Pair.mk")).unwrap();
+        let expected = arena
+            .build(prod(
+                "A",
+                type_usize(0),
+                prod("B", type_usize(0), prod("_", var("A"), prod("_", var("B"), app(app(var("Pair"), var("A")), var("B"))))),
+            ))
+            .unwrap();
+        assert!(mk.check(expected, arena).is_ok());
+
+        // Pair.snd : (A B: Type) -> Pair A B -> B
+        let snd = arena.build(var("Pair.snd")).unwrap();
+        let expected = arena
+            .build(prod(
+                "A",
+                type_usize(0),
+                prod("B", type_usize(0), prod("_", app(app(var("Pair"), var("A")), var("B")), var("B"))),
+            ))
+            .unwrap();
+        assert!(snd.check(expected, arena).is_ok());
+
+        // Pair.fst Prop Type (Pair.mk Prop Type True Prop) ~> True
+        let fst = arena
+            .build(app(
+                app(app(var("Pair.fst"), prop()), type_usize(0)),
+                app(app(app(app(var("Pair.mk"), prop()), type_usize(0)), prod("P", prop(), prod("_", var("P"), var("P")))), prop()),
+            ))
+            .unwrap();
+        let expected = arena.build(prod("P", prop(), prod("_", var("P"), var("P")))).unwrap();
+        assert_eq!(fst.normal_form(arena), expected);
+    });
+}
+
+#[test]
+fn dependent_fields() {
+    use_arena(|arena| {
+        // structure Subtype (A: Type) (P: A -> Prop) where val: A; property: P val
+        let subtype =
+            structure("Subtype", &[], vec![("A", boxed(type_usize(0))), ("P", boxed(prod("_", var("A"), prop())))], None, vec![
+                ("val", boxed(var("A"))),
+                ("property", boxed(app(var("P"), var("val")))),
+            ]);
+        declare(arena, subtype, &["val", "property"]).unwrap();
+
+        // Subtype.property : (A: Type) -> (P: A -> Prop) -> (s: Subtype A P) -> P (Subtype.val A P s)
+        let property = arena.build(var("Subtype.property")).unwrap();
+        let expected = arena
+            .build(prod(
+                "A",
+                type_usize(0),
+                prod(
+                    "P",
+                    prod("_", var("A"), prop()),
+                    prod(
+                        "s",
+                        app(app(var("Subtype"), var("A")), var("P")),
+                        app(var("P"), app(app(app(var("Subtype.val"), var("A")), var("P")), var("s"))),
+                    ),
+                ),
+            ))
+            .unwrap();
+        assert!(property.check(expected, arena).is_ok());
+    });
+}
+
+#[test]
+fn universe_polymorphic() {
+    use_arena(|arena| {
+        // structure Box.{u} (A: Sort u) where unbox: A
+        let box_ = structure("Box", &["u"], vec![("A", boxed(sort(level::var("u"))))], None, vec![("unbox", boxed(var("A")))]);
+        declare(arena, box_, &["unbox"]).unwrap();
+
+        // Box.{0} lives in Sort (max 1 0)
+        let type_ = arena.build(decl(declaration::var("Box", append(nil(), level::const_(0))))).unwrap();
+        let expected = arena.build(prod("_", prop(), type_usize(0))).unwrap();
+        assert!(type_.check(expected, arena).is_ok());
+
+        // Box.unbox.{1} Prop (Box.mk.{1} Prop Prop) ~> Prop
+        let unbox = arena
+            .build(app(
+                app(decl(declaration::var("Box.unbox", append(nil(), level::const_(1)))), prop()),
+                app(app(decl(declaration::var("Box.mk", append(nil(), level::const_(1)))), prop()), prop()),
+            ))
+            .unwrap();
+        let expected = arena.build(prop()).unwrap();
+
+        assert_eq!(unbox.normal_form(arena), expected);
+    });
+}
+
+#[test]
+fn prop_structure() {
+    use_arena(|arena| {
+        // structure Exists (A: Type) (P: A -> Prop) : Prop where witness: A; proof: P witness
+        let exists = structure(
+            "Exists",
+            &[],
+            vec![("A", boxed(type_usize(0))), ("P", boxed(prod("_", var("A"), prop())))],
+            Some(boxed(prop())),
+            vec![("witness", boxed(var("A"))), ("proof", boxed(app(var("P"), var("witness"))))],
+        );
+
+        let err = declare(arena, exists, &["witness", "proof"]).unwrap_err();
+        assert!(matches!(kind(&err), type_checker::ErrorKind::ProjectionOutOfProp(_)));
+        assert!(arena.get_binding("Exists.witness").is_none());
+    });
+}
+
+#[test]
+fn traced_errors() {
+    use_arena(|arena| {
+        // structure Wrong (A: Type) where x: A; y: x
+        let wrong =
+            structure("Wrong", &[], vec![("A", boxed(type_usize(0)))], None, vec![("x", boxed(var("A"))), ("y", boxed(var("x")))]);
+
+        let err = declare(arena, wrong, &["x", "y"]).unwrap_err();
+        assert!(matches!(kind(&err), type_checker::ErrorKind::NotUniverse(_)));
+        assert_eq!(err.trace, vec![Trace::Left, Trace::Right, Trace::Right, Trace::Left, Trace::Right]);
+
+        // structure Wrong (A: B) where x: A
+        let wrong = structure("Wrong", &[], vec![("A", boxed(var("B")))], None, vec![("x", boxed(var("A")))]);
+
+        let err = declare(arena, wrong, &["x"]).unwrap_err();
+        assert_eq!(err.trace, vec![Trace::Left, Trace::Left]);
+    });
+}
@@ -0,0 +1,147 @@
+#![feature(trait_alias)]
+
+use kernel::memory::arena::{use_arena_with_axioms, Arena};
+use kernel::memory::declaration::builder as declaration;
+use kernel::memory::inductive::builder::{inductive, BuilderTrait};
+use kernel::memory::level::builder::{append, const_, nil};
+use kernel::memory::term::builder::*;
+
+type Boxed<'build> = Box<dyn kernel::memory::term::builder::BuilderTrait<'build> + 'build>;
+
+fn boxed<'build, F: kernel::memory::term::builder::BuilderTrait<'build> + 'build>(f: F) -> Boxed<'build> {
+    Box::new(f)
+}
+
+/// Builds, checks and binds the given inductive type.
+fn declare<'build, F: BuilderTrait<'build>>(arena: &mut Arena<'_>, f: F) {
+    let inductive = arena.build_inductive(f).unwrap();
+    arena.bind_inductive(inductive).unwrap();
+}
+
+#[test]
+fn unit_eta() {
+    use_arena_with_axioms(|arena| {
+        // fun (x y: Unit) => Refl.{1} Unit x : (x y: Unit) -> Eq.{1} Unit x y
+        let proof = arena
+            .build(abs(
+                "x",
+                var("Unit"),
+                abs("y", var("Unit"), app(app(decl(declaration::var("Refl", append(nil(), const_(1)))), var("Unit")), var("x"))),
+            ))
+            .unwrap();
+        let goal = arena
+            .build(prod(
+                "x",
+                var("Unit"),
+                prod(
+                    "y",
+                    var("Unit"),
+                    app(app(app(decl(declaration::var("Eq", append(nil(), const_(1)))), var("Unit")), var("x")), var("y")),
+                ),
+            ))
+            .unwrap();
+
+        assert!(proof.check(goal, arena).is_ok());
+    });
+}
+
+#[test]
+fn punit_eta() {
+    use_arena_with_axioms(|arena| {
+        let punit = || decl(declaration::var("PUnit", append(nil(), const_(3))));
+
+        let id = arena.build(abs("x", punit(), var("x"))).unwrap();
+        let star = arena
+            .build(abs("x", punit(), decl(declaration::var("Star", append(nil(), const_(3))))))
+            .unwrap();
+
+        assert!(id.is_def_eq(star, arena).is_ok());
+
+        // any two variables of the unit type are convertible
+        let first = arena.build(abs("x", punit(), abs("y", punit(), var("x")))).unwrap();
+        let second = arena.build(abs("x", punit(), abs("y", punit(), var("y")))).unwrap();
+
+        assert!(first.is_def_eq(second, arena).is_ok());
+    });
+}
+
+#[test]
+fn punit_rec() {
+    use_arena_with_axioms(|arena| {
+        // fun x: Unit => PUnit_rec.{1, 1} (fun _: Unit => Nat) Zero x
+        let rec = arena
+            .build(abs(
+                "x",
+                var("Unit"),
+                app(
+                    app(
+                        app(
+                            decl(declaration::var("PUnit_rec", append(append(nil(), const_(1)), const_(1)))),
+                            abs("_", var("Unit"), var("Nat")),
+                        ),
+                        var("Zero"),
+                    ),
+                    var("x"),
+                ),
+            ))
+            .unwrap();
+        let expected = arena.build(prod("x", var("Unit"), var("Nat"))).unwrap();
+
+        assert!(rec.check(expected, arena).is_ok());
+
+        let zero = arena.build(abs("x", var("Unit"), var("Zero"))).unwrap();
+
+        assert!(rec.is_def_eq(zero, arena).is_ok());
+    });
+}
+
+#[test]
+fn inductive_unit_like() {
+    use_arena_with_axioms(|arena| {
+        declare(arena, inductive("MyUnit", &[], 0, boxed(type_usize(0)), vec![("my_star", boxed(var("MyUnit")))]));
+        declare(
+            arena,
+            inductive("Wrap", &[], 1, boxed(prod("A", type_usize(0), type_usize(0))), vec![(
+                "wrap",
+                boxed(prod("A", type_usize(0), app(var("Wrap"), var("A")))),
+            )]),
+        );
+
+        let id = arena.build(abs("x", var("MyUnit"), var("x"))).unwrap();
+        let star = arena.build(abs("x", var("MyUnit"), var("my_star"))).unwrap();
+
+        assert!(id.is_def_eq(star, arena).is_ok());
+
+        let id = arena.build(abs("x", app(var("Wrap"), var("Nat")), var("x"))).unwrap();
+        let wrap = arena.build(abs("x", app(var("Wrap"), var("Nat")), app(var("wrap"), var("Nat")))).unwrap();
+
+        assert!(id.is_def_eq(wrap, arena).is_ok());
+    });
+}
+
+#[test]
+fn inductive_not_unit_like() {
+    use_arena_with_axioms(|arena| {
+        declare(
+            arena,
+            inductive("Bool", &[], 0, boxed(type_usize(0)), vec![("true", boxed(var("Bool"))), ("false", boxed(var("Bool")))]),
+        );
+        declare(arena, inductive("Box", &[], 0, boxed(type_usize(0)), vec![("box", boxed(prod("_", var("Nat"), var("Box"))))]));
+
+        let id = arena.build(abs("x", var("Bool"), var("x"))).unwrap();
+        let true_ = arena.build(abs("x", var("Bool"), var("true"))).unwrap();
+
+        assert!(id.is_def_eq(true_, arena).is_err());
+
+        // values built with distinct constructors are told apart without trying eta rules
+        let true_ = arena.build(var("true")).unwrap();
+        let false_ = arena.build(var("false")).unwrap();
+
+        assert!(true_.is_def_eq(false_, arena).is_err());
+
+        let id = arena.build(abs("x", var("Box"), var("x"))).unwrap();
+        let box_ = arena.build(abs("x", var("Box"), app(var("box"), var("Zero")))).unwrap();
+
+        assert!(id.is_def_eq(box_, arena).is_err());
+    });
+}
@@ -1,5 +1,8 @@
 use mini_proost::process_input;
  fn main()  {
     let input = "def x: Prop := True";
-    process_input(input).expect("Bad");
-}
\ No newline at end of file
+
+    if let Err(err) = process_input(input) {
+        eprintln!("{err}");
+    }
+}
@@ -1,6 +1,10 @@
 //! Error management
 
+use core::fmt;
+use std::path::{Path, PathBuf};
+
 use derive_more::{Display, From};
+use elaboration::elaborator;
 use elaboration::location::Location;
 use kernel::memory::term::Term;
 use kernel::trace::Traceable;
@@ -17,6 +21,10 @@ pub enum Error<'arena, 'build> {
     #[display(fmt = "{_1}")]
     Kernel(&'build dyn Traceable<Location>, kernel::error::Error<'arena>),
 
+    /// An error raised by the [elaborator](elaboration::elaborator).
+    #[display(fmt = "{_1}")]
+    Elaboration(Location, elaborator::ErrorKind),
+
     /// An error raised by the [`parser`].
     Parser(parser::error::Error),
 
@@ -27,6 +35,59 @@ pub enum Error<'arena, 'build> {
     Io(std::io::Error),
 }
 
+impl<'arena, 'build> From<elaborator::Error<'build, 'arena>> for Error<'arena, 'build> {
+    fn from(err: elaborator::Error<'build, 'arena>) -> Self {
+        match err {
+            elaborator::Error::Kernel(builder, err) => Error::Kernel(builder, err),
+            elaborator::Error::Elaboration(location, kind) => Error::Elaboration(location, kind),
+        }
+    }
+}
+
+impl Error<'_, '_> {
+    /// Returns the location of the error in the input it has been raised on, if any.
+    #[inline]
+    #[must_use]
+    pub fn location(&self) -> Option<Location> {
+        match *self {
+            Self::Kernel(builder, ref err) => Some(builder.apply_trace(&err.trace)),
+            Self::Elaboration(location, _) => Some(location),
+            Self::Parser(ref err) => Some(err.location),
+            Self::TopLevel(ref err) => Some(err.location),
+            Self::Io(_) => None,
+        }
+    }
+
+    /// Returns an owned version of the error, raised on the file `file` if given.
+    #[inline]
+    #[must_use]
+    pub fn into_owned(self, file: Option<&Path>) -> OwnedError {
+        let kind = match self {
+            Self::Kernel(..) => Kind::Kernel,
+            Self::Elaboration(..) => Kind::Elaboration,
+            Self::Parser(_) => Kind::Parser,
+            Self::TopLevel(_) => Kind::TopLevel,
+            Self::Io(_) => Kind::Io,
+        };
+
+        let cause = match self {
+            Self::TopLevel(evaluator::Error {
+                kind: evaluator::ErrorKind::FileError(_, ref cause),
+                ..
+            }) => Some(cause.clone()),
+            _ => None,
+        };
+
+        OwnedError {
+            kind,
+            message: self.to_string(),
+            location: self.location(),
+            file: file.map(Path::to_path_buf),
+            cause,
+        }
+    }
+}
+
 impl core::fmt::Debug for Error<'_, '_> {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         core::fmt::Display::fmt(self, f)
@@ -35,8 +96,83 @@ impl core::fmt::Debug for Error<'_, '_> {
 
 impl std::error::Error for Error<'_, '_> {}
 
+/// The part of Proost an [`OwnedError`] has been raised by.
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
+pub enum Kind {
+    /// The [`kernel`].
+    #[display(fmt = "kernel")]
+    Kernel,
+
+    /// The [elaborator](elaboration::elaborator).
+    #[display(fmt = "elaborator")]
+    Elaboration,
+
+    /// The [`parser`].
+    #[display(fmt = "parser")]
+    Parser,
+
+    /// The [evaluator](crate::evaluator).
+    #[display(fmt = "evaluator")]
+    TopLevel,
+
+    /// Input/output operations.
+    #[display(fmt = "input/output")]
+    Io,
+}
+
+/// An error which, unlike [`Error`], borrows neither the arena nor the input, as returned to
+/// library users.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OwnedError {
+    /// The part of Proost which raised the error.
+    pub kind: Kind,
+
+    /// The description of the error.
+    pub message: String,
+
+    /// The location of the error in its input, if any.
+    pub location: Option<Location>,
+
+    /// The file the error has been raised on, if any.
+    pub file: Option<PathBuf>,
+
+    /// The error which caused this one, if any, such as an error in an imported file.
+    pub cause: Option<Box<Self>>,
+}
+
+impl fmt::Display for OwnedError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.file, self.location) {
+            (Some(file), Some(location)) => write!(f, "{}:{}: {}", file.display(), location.start, self.message),
+            (Some(file), None) => write!(f, "{}: {}", file.display(), self.message),
+            (None, Some(location)) => write!(f, "{}: {}", location.start, self.message),
+            (None, None) => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for OwnedError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        let cause: &Self = self.cause.as_deref()?;
+        Some(cause)
+    }
+}
+
 /// The type of results yielded by the toplevel.
 pub type Result<'arena, 'build, T> = core::result::Result<T, Error<'arena, 'build>>;
 
 /// The type of objects which typically results from the processing of a command.
-pub type ResultProcess<'arena, 'build> = Result<'arena, 'build, Option<Term<'arena>>>;
+pub type ResultProcess<'arena, 'build> = Result<'arena, 'build, Option<Outcome<'arena>>>;
+
+/// The outcome of a command, when it has something to display.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Outcome<'arena> {
+    /// A term, such as a type or a normal form.
+    Term(Term<'arena>),
+
+    /// The full names found by a search, along with their types.
+    Matches(Vec<(&'arena str, Term<'arena>)>),
+}
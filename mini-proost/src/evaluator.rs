@@ -1,18 +1,23 @@
 //! Tools to evaluate commands, as provided by the parser
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs::read_to_string;
 use std::path::{Path, PathBuf};
 
 use derive_more::Display;
-use elaboration::builder::Buildable;
+use elaboration::builder::{declaration, term, Buildable};
+use elaboration::elaborator::output::Elaborated;
+use elaboration::elaborator::{self, Elaborator, Goal, Implicits};
 use elaboration::location::Location;
+use elaboration::scope::Scope;
 use kernel::memory::arena::Arena;
+use kernel::memory::declaration::builder::declaration as build_declaration;
+use kernel::memory::declaration::Declaration;
+use kernel::memory::term::Term;
 use parser::command::{parse, Command};
 
-use crate::error::Error::{Kernel, TopLevel};
-use crate::error::{Result, ResultProcess};
-use crate::display;
+use crate::error::Error::{Kernel, Parser, TopLevel};
+use crate::error::{Outcome, OwnedError, Result, ResultProcess};
 
 /// Type representing parser errors.
 #[derive(Clone, Debug, Display, Eq, PartialEq)]
@@ -33,9 +38,9 @@ pub enum ErrorKind {
     #[display(fmt = "{_0} is not a file")]
     FileNotFound(String),
 
-    /// The given file could not be imported
+    /// The given file could not be imported, because of the given error
     #[display(fmt = "errors occurred while reading file {_0}")]
-    FileError(String),
+    FileError(String, Box<OwnedError>),
 
     /// These files have a cyclic dependency
     #[display(fmt = "cyclic dependency:\n{_0}")]
@@ -45,8 +50,37 @@ pub enum ErrorKind {
     #[display(fmt = "identifier {_0} already defined")]
     BoundVariable(String),
 
-    #[display(fmt = "Miscellaneous error: {_0}")]
-    MiscError(String)
+    /// This variable is not defined
+    #[display(fmt = "unknown identifier {_0}")]
+    UnboundVariable(String),
+
+    /// This namespace does not exist
+    #[display(fmt = "unknown namespace {_0}")]
+    UnknownNamespace(String),
+
+    /// This namespace or section is not the current one
+    #[display(fmt = "{_0} is not the current namespace or section")]
+    NotCurrentScope(String),
+
+    /// No section is currently entered
+    #[display(fmt = "no section to end")]
+    NoSection,
+
+    /// This variable is not declared in a section
+    #[display(fmt = "variable {_0} must be declared in a section")]
+    VariableOutsideSection(String),
+
+    /// Inductive types cannot be abstracted over section variables
+    #[display(fmt = "inductive types cannot be declared in a section with variables")]
+    InductiveInSection,
+
+    /// Axioms cannot be abstracted over section variables
+    #[display(fmt = "axioms cannot be declared in a section with variables")]
+    AxiomInSection,
+
+    /// This axiom cannot be assumed, axioms being forbidden
+    #[display(fmt = "axiom {_0} cannot be assumed, as axioms are forbidden")]
+    ForbiddenAxiom(String),
 }
 
 impl std::error::Error for Error {}
@@ -62,6 +96,45 @@ pub struct Evaluator {
 
     /// Whether the evaluator should be verbose in designated contexts
     verbose: bool,
+
+    /// The implicit arguments of the terms and declarations defined so far
+    implicits: Implicits,
+
+    /// The namespaces entered and opened so far
+    scope: Scope,
+
+    /// The sections currently entered, from the outermost one
+    sections: Vec<Section>,
+
+    /// The errors raised so far in imported files, when checking files without stopping at the
+    /// first failing command
+    diagnostics: Option<Vec<OwnedError>>,
+
+    /// Whether axioms may be assumed
+    axioms: bool,
+
+    /// The full names of the axioms assumed so far
+    assumptions: HashSet<String>,
+
+    /// The full names each term or declaration defined so far refers to, either in its input or in
+    /// its elaborated form
+    dependencies: HashMap<String, Vec<String>>,
+}
+
+/// A section entered with the `section` command.
+struct Section {
+    /// The name of the section, if any
+    name: Option<String>,
+
+    /// The number of namespaces entered before entering the section
+    depth: usize,
+
+    /// The full names of the variables declared in the section, along with whether they are
+    /// implicit, from the first one
+    variables: Vec<(String, bool)>,
+
+    /// The full names of the terms and declarations defined in the section, from the first one
+    definitions: Vec<String>,
 }
 
 impl<'arena> Evaluator {
@@ -71,9 +144,29 @@ impl<'arena> Evaluator {
             path,
             imported: HashSet::new(),
             verbose,
+            implicits: Implicits::new(),
+            scope: Scope::new(),
+            sections: Vec::new(),
+            diagnostics: None,
+            axioms: true,
+            assumptions: HashSet::new(),
+            dependencies: HashMap::new(),
         }
     }
 
+    /// Forbids assuming axioms in the following commands
+    #[inline]
+    pub const fn forbid_axioms(&mut self) {
+        self.axioms = false;
+    }
+
+    /// Indicates whether `name` is already bound in the current namespace
+    #[inline]
+    #[must_use]
+    pub fn is_bound(&self, arena: &Arena<'arena>, name: &str) -> bool {
+        arena.is_bound(&self.scope.qualify(name))
+    }
+
     /// Create a new path from a relative path
     fn create_path<'build>(
         &self,
@@ -165,27 +258,321 @@ impl<'arena> Evaluator {
         file_path: &Path,
         importing: &mut Vec<PathBuf>,
     ) -> ResultProcess<'arena, 'static> {
-        let commands = parse::file(file)?;
+        if self.diagnostics.is_some() {
+            self.check_file(arena, file, file_path, importing);
+            return Ok(None);
+        }
+
+        let mut commands = parse::file(file).map_err(|err| file_error(location, file_path, Parser(err)))?;
+
+        // commands are parsed one at a time, as they may use the notations declared by the previous ones
+        while let Some(command) = commands.next(arena.notations()) {
+            let command = command.map_err(|err| file_error(location, file_path, Parser(err)))?;
+
+            if self.verbose {
+                println!("{command}");
+            }
+
+            // errors may depend on the imported file, which is discarded right after: they are
+            // turned into owned ones, attached to an error about the command itself
+            self.process(arena, &command, importing)
+                .map_err(|err| file_error(location, file_path, err))?;
+        }
+
+        Ok(None)
+    }
 
-        commands
+    /// Processes a given file, recording the errors raised by its commands in `self.diagnostics`
+    /// instead of stopping at the first one. Failing commands are skipped, and bind nothing.
+    fn check_file(&mut self, arena: &mut Arena<'arena>, file: &str, file_path: &Path, importing: &mut Vec<PathBuf>) {
+        let mut commands = parse::file_recovering(file);
+
+        while let Some(command) = commands.next(arena.notations()) {
+            let result = match command {
+                Ok(ref command) => {
+                    if self.verbose {
+                        println!("{command}");
+                    }
+
+                    self.process(arena, command, importing).map(|_| ())
+                },
+                Err(err) => Err(Parser(err)),
+            };
+
+            if let Err(err) = result {
+                let err = err.into_owned(Some(file_path));
+                self.diagnostics.get_or_insert_with(Vec::new).push(err);
+            }
+        }
+    }
+
+    /// Processes the given files, as if imported, without stopping at the first failing command:
+    /// failing commands are skipped, and bind nothing.
+    ///
+    /// Returns the errors raised in the files and in the files they import, in order.
+    #[inline]
+    pub fn check_files(&mut self, arena: &mut Arena<'arena>, files: &[&str]) -> Vec<OwnedError> {
+        self.diagnostics = Some(Vec::new());
+
+        for &file in files {
+            let result = self
+                .create_path(Location::default(), file.to_owned(), &[])
+                .and_then(|file_path| self.import_file(arena, Location::default(), &file_path, &mut Vec::new()));
+
+            if let Err(err) = result {
+                // the files are not imported by a command, the location of which would be reported
+                let err = OwnedError {
+                    location: None,
+                    ..err.into_owned(None)
+                };
+                self.diagnostics.get_or_insert_with(Vec::new).push(err);
+            }
+        }
+
+        self.diagnostics.take().unwrap_or_default()
+    }
+
+    /// Checks that none of the given names is already bound in `arena`, nor appears twice.
+    fn check_unbound<'build, I>(names: I, arena: &Arena<'arena>) -> Result<'arena, 'build, ()>
+    where
+        I: IntoIterator<Item = (Location, String)>,
+    {
+        let mut declared = HashSet::new();
+
+        for (location, name) in names {
+            if arena.is_bound(&name) || !declared.insert(name.clone()) {
+                return Err(TopLevel(Error {
+                    kind: ErrorKind::BoundVariable(name),
+                    location,
+                }));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Records the full names the term or declaration bound to the full name `name` refers to:
+    /// the given names, which it refers to in its input, and the axioms and opaque declarations
+    /// its term and its type refer to once elaborated. The given names are resolved (see
+    /// [`Scope::resolve`]), and ignored unless they refer to exactly one bound name.
+    fn record_dependencies(&mut self, arena: &mut Arena<'arena>, name: &str, names: &[&str]) {
+        let dependencies = arena.get_binding_decl(name).map(Declaration::constants);
+        let mut dependencies = dependencies.or_else(|| arena.get_binding(name).map(Term::constants)).unwrap_or_default();
+
+        dependencies.extend(arena.get_type(name).map(Term::constants).unwrap_or_default());
+
+        for name in names {
+            if let [ref name] = *self.scope.resolve(name, arena) {
+                dependencies.insert(name.clone());
+            }
+        }
+
+        dependencies.remove(name);
+        self.dependencies.insert(name.to_owned(), dependencies.into_iter().collect());
+    }
+
+    /// Records that the full name `name` is defined in the current section, if any.
+    fn record_definition(&mut self, name: &str) {
+        if let Some(section) = self.sections.last_mut() {
+            section.definitions.push(name.to_owned());
+        }
+    }
+
+    /// Indicates whether variables are declared in the sections currently entered.
+    fn has_variables(&self) -> bool {
+        self.sections.iter().any(|section| !section.variables.is_empty())
+    }
+
+    /// Exits the current section, and unbinds its variables.
+    ///
+    /// The terms and declarations defined in the section are bound again, abstracted over the
+    /// variables they use (see [`abstract_binding`]), and are then defined in the enclosing
+    /// section, if any.
+    fn exit_section(&mut self, arena: &mut Arena<'arena>) {
+        let Some(section) = self.sections.pop() else {
+            return;
+        };
+
+        let (variables, constants): (Vec<_>, Vec<_>) = section
+            .variables
             .iter()
-            .try_for_each(|command| {
-                if self.verbose {
-                    println!("{command}");
+            .filter_map(|variable| Some((variable, arena.get_binding(&variable.0)?)))
+            .unzip();
+
+        for definition in &section.definitions {
+            let Some(used) = abstract_binding(arena, definition, &constants) else {
+                continue;
+            };
+
+            let used: Vec<_> = variables.iter().zip(used).filter(|&(_, used)| used).map(|(&variable, _)| variable).collect();
+
+            // variables are no longer dependencies once abstracted
+            if let Some(dependencies) = self.dependencies.get_mut(definition) {
+                dependencies.retain(|dependency| !used.iter().any(|&(variable, _)| variable == dependency));
+            }
+
+            self.implicits.generalise(definition, used.iter().map(|&&(_, implicit)| implicit).collect());
+        }
+
+        for (variable, _) in &section.variables {
+            arena.unbind(variable);
+        }
+
+        if let Some(enclosing) = self.sections.last_mut() {
+            enclosing.definitions.extend(section.definitions);
+        }
+    }
+
+    /// Returns the full names of the terms and declarations the full name `name` depends on,
+    /// either directly or through its other dependencies.
+    fn dependencies(&self, name: &str) -> HashSet<&str> {
+        let mut visited = HashSet::new();
+        let mut stack = vec![name];
+
+        while let Some(name) = stack.pop() {
+            for dependency in self.dependencies.get(name).into_iter().flatten() {
+                if visited.insert(dependency.as_str()) {
+                    stack.push(dependency);
                 }
-                self.process(arena, command, importing).map(|_| ()).map_err(|err| {
-                    // if importation failed, display the associated errors now (the imported file is discarded
-                    // right after, and errors may depend on it), and return an error about the command itself.
-                    display(Err(err), false);
+            }
+        }
 
-                    Error {
-                        kind: ErrorKind::FileError(file_path.to_string_lossy().to_string()),
-                        location,
-                    }
-                    .into()
-                })
-            })
-            .map(|()| None)
+        visited.remove(name);
+        visited
+    }
+
+    /// Indicates whether `term` or `type_` need to be elaborated before being handed over to the
+    /// kernel.
+    fn involve(&self, term: &term::Builder, type_: Option<&term::Builder>, arena: &Arena<'arena>) -> bool {
+        self.implicits.involve(term, &self.scope, arena)
+            || type_.is_some_and(|type_| self.implicits.involve(type_, &self.scope, arena))
+    }
+
+    /// Elaborates `term_builder`, checks that it has type `type_builder` if provided, and returns
+    /// it along with its type and the flags indicating which of its arguments are implicit.
+    fn elaborate<'build>(
+        &self,
+        arena: &mut Arena<'arena>,
+        term_builder: &'build term::Builder<'build>,
+        type_builder: Option<&'build term::Builder<'build>>,
+    ) -> Result<'arena, 'build, (Term<'arena>, Term<'arena>, Vec<bool>)> {
+        let output = Elaborator::new(&self.implicits, &self.scope, &[]).elaborate(term_builder, type_builder, arena)?;
+        let term = output.term.realise(arena, &self.scope).map_err(|err| locate(&output.term, term_builder, err))?;
+
+        let type_ = match output.type_.as_ref().zip(type_builder) {
+            Some((type_, type_builder)) => {
+                let type_ = type_.realise(arena, &self.scope).map_err(|err| locate(type_, type_builder, err))?;
+                term.check(type_, arena).map_err(|err| locate(&output.term, term_builder, err))?;
+                type_
+            },
+            None => term.infer(arena).map_err(|err| locate(&output.term, term_builder, err))?,
+        };
+
+        Ok((term, type_, output.implicits))
+    }
+
+    /// Binds the declaration `decl_builder` to `name`, after checking that it has type
+    /// `type_builder` if provided. The declaration is opaque if `opaque` is set, as theorems are.
+    fn declare<'build>(
+        &mut self,
+        arena: &mut Arena<'arena>,
+        (location, s): (Location, &'build str),
+        type_builder: Option<&'build declaration::Builder<'build>>,
+        decl_builder: &'build declaration::Builder<'build>,
+        opaque: bool,
+    ) -> ResultProcess<'arena, 'build> {
+        let name = self.scope.qualify(s);
+
+        if arena.is_bound(&name) {
+            return Err(TopLevel(Error {
+                kind: ErrorKind::BoundVariable(s.to_owned()),
+                location,
+            }));
+        }
+
+        let declaration::Builder::Decl(ref term, _) = *decl_builder;
+        let type_ = type_builder.map(|declaration::Builder::Decl(ref type_, _)| &**type_);
+        let names = dependencies(term, type_);
+
+        let (decl, implicits) = if self.involve(term, type_, arena) {
+            let (decl, implicits) = self.elaborate_decl(arena, decl_builder, type_builder)?;
+            (decl, Some(implicits))
+        } else {
+            let decl = decl_builder.realise(arena, &self.scope).map_err(|err| Kernel(decl_builder, err))?;
+
+            if let Some(type_builder) = type_builder {
+                let type_ = type_builder.realise(arena, &self.scope).map_err(|err| Kernel(type_builder, err))?;
+
+                decl.check(type_, arena).map_err(|err| Kernel(decl_builder, err))?;
+            } else {
+                decl.infer(arena).map_err(|err| Kernel(decl_builder, err))?;
+            }
+
+            (decl, None)
+        };
+
+        if opaque {
+            // only proofs may be opaque, the statement being a proposition
+            arena.bind_opaque(&name, decl).map_err(|err| Kernel(type_builder.unwrap_or(decl_builder), err))?;
+        } else {
+            arena.bind_decl(&name, decl);
+        }
+
+        self.record_definition(&name);
+        self.record_dependencies(arena, &name, &names);
+
+        if let Some(implicits) = implicits {
+            self.implicits.insert(&name, implicits);
+        }
+
+        Ok(None)
+    }
+
+    /// Elaborates `decl_builder`, checks that it has type `type_builder` if provided, and returns
+    /// it along with the flags indicating which of its arguments are implicit.
+    fn elaborate_decl<'build>(
+        &self,
+        arena: &mut Arena<'arena>,
+        decl_builder: &'build declaration::Builder<'build>,
+        type_builder: Option<&'build declaration::Builder<'build>>,
+    ) -> Result<'arena, 'build, (Declaration<'arena>, Vec<bool>)> {
+        let declaration::Builder::Decl(ref term_builder, ref vars) = *decl_builder;
+        let type_builder = type_builder.map(|declaration::Builder::Decl(ref type_builder, _)| &**type_builder);
+
+        let output = Elaborator::new(&self.implicits, &self.scope, vars).elaborate(term_builder, type_builder, arena)?;
+        let decl = arena
+            .build_declaration(build_declaration(output.term.as_closure(&self.scope), vars))
+            .map_err(|err| locate(&output.term, term_builder, err))?;
+
+        match output.type_.as_ref().zip(type_builder) {
+            Some((type_, type_builder)) => {
+                let type_ = arena
+                    .build_declaration(build_declaration(type_.as_closure(&self.scope), vars))
+                    .map_err(|err| locate(type_, type_builder, err))?;
+                decl.check(type_, arena).map_err(|err| locate(&output.term, term_builder, err))?;
+            },
+            None => decl.infer(arena).map_err(|err| locate(&output.term, term_builder, err))?,
+        }
+
+        Ok((decl, output.implicits))
+    }
+
+    /// Elaborates the tactic block `block` against the statement `statement`, and returns the goals
+    /// left by its tactics.
+    ///
+    /// # Errors
+    /// Transmits any error raised while running the tactics, except for the goals they leave.
+    pub fn goals<'build>(
+        &self,
+        arena: &mut Arena<'arena>,
+        statement: &'build term::Builder<'build>,
+        block: &'build term::Builder<'build>,
+    ) -> Result<'arena, 'build, Vec<Goal>> {
+        match Elaborator::new(&self.implicits, &self.scope, &[]).elaborate(block, Some(statement), arena) {
+            Ok(_) => Ok(Vec::new()),
+            Err(elaborator::Error::Elaboration(_, elaborator::ErrorKind::UnsolvedGoals(goals))) => Ok(goals),
+            Err(err) => Err(err.into()),
+        }
     }
 
     /// Processes a command.
@@ -201,72 +588,368 @@ impl<'arena> Evaluator {
     ) -> ResultProcess<'arena, 'build> {
         match *command {
             Command::Define((location, s), ref type_builder, ref term_builder) => {
-                if arena.get_binding(s).is_some() || arena.get_binding_decl(s).is_some() {
+                let name = self.scope.qualify(s);
+
+                if arena.is_bound(&name) {
                     return Err(TopLevel(Error {
                         kind: ErrorKind::BoundVariable(s.to_owned()),
                         location,
                     }));
                 }
 
-                let term = term_builder.realise(arena).map_err(|err| Kernel(term_builder, err))?;
+                let names = dependencies(term_builder, type_builder.as_ref());
+
+                if self.involve(term_builder, type_builder.as_ref(), arena) {
+                    let (term, _, implicits) = self.elaborate(arena, term_builder, type_builder.as_ref())?;
+
+                    arena.bind(&name, term);
+                    self.record_definition(&name);
+                    self.record_dependencies(arena, &name, &names);
+                    self.implicits.insert(&name, implicits);
+                    return Ok(None);
+                }
+
+                let term = term_builder.realise(arena, &self.scope).map_err(|err| Kernel(term_builder, err))?;
 
                 if let Some(ref type_builder) = *type_builder {
-                    let type_ = type_builder.realise(arena).map_err(|err| Kernel(type_builder, err))?;
+                    let type_ = type_builder.realise(arena, &self.scope).map_err(|err| Kernel(type_builder, err))?;
 
                     term.check(type_, arena).map_err(|err| Kernel(term_builder, err))?;
                 } else {
                     term.infer(arena).map_err(|err| Kernel(term_builder, err))?;
                 }
 
-                arena.bind(s, term);
+                arena.bind(&name, term);
+                self.record_definition(&name);
+                self.record_dependencies(arena, &name, &names);
                 Ok(None)
             },
 
-            Command::Declaration((location, s), ref type_builder, ref decl_builder) => {
-                if arena.get_binding_decl(s).is_some() || arena.get_binding(s).is_some() {
+            Command::Declaration(name, ref type_builder, ref decl_builder) => {
+                self.declare(arena, name, type_builder.as_ref(), decl_builder, false)
+            },
+
+            Command::Theorem(name, ref type_builder, ref decl_builder) => {
+                self.declare(arena, name, Some(type_builder), decl_builder, true)
+            },
+
+            Command::Axiom((location, s), ref type_builder) => {
+                if !self.axioms {
+                    return Err(TopLevel(Error {
+                        kind: ErrorKind::ForbiddenAxiom(s.to_owned()),
+                        location,
+                    }));
+                }
+
+                let name = self.scope.qualify(s);
+
+                if arena.is_bound(&name) {
                     return Err(TopLevel(Error {
                         kind: ErrorKind::BoundVariable(s.to_owned()),
                         location,
                     }));
                 }
 
-                let decl = decl_builder.realise(arena).map_err(|err| Kernel(decl_builder, err))?;
+                if self.has_variables() {
+                    return Err(TopLevel(Error {
+                        kind: ErrorKind::AxiomInSection,
+                        location,
+                    }));
+                }
 
-                if let Some(ref type_builder) = *type_builder {
-                    let type_ = type_builder.realise(arena).map_err(|err| Kernel(type_builder, err))?;
+                let declaration::Builder::Decl(ref type_, _) = *type_builder;
+                let involved = self.implicits.involve(type_, &self.scope, arena);
 
-                    decl.check(type_, arena).map_err(|err| Kernel(decl_builder, err))?;
+                let decl = if involved {
+                    self.elaborate_decl(arena, type_builder, None)?.0
                 } else {
-                    decl.infer(arena).map_err(|err| Kernel(decl_builder, err))?;
+                    type_builder.realise(arena, &self.scope).map_err(|err| Kernel(type_builder, err))?
+                };
+
+                arena.declare_axiom(&name, decl).map_err(|err| Kernel(type_builder, err))?;
+                self.record_dependencies(arena, &name, &type_.free_names());
+
+                if involved {
+                    self.implicits.insert(&name, implicit_binders(type_));
                 }
 
-                arena.bind_decl(s, decl);
+                self.assumptions.insert(name);
+
                 Ok(None)
             },
 
             Command::CheckType(ref term_builder, ref type_builder) => {
-                let term = term_builder.realise(arena).map_err(|err| Kernel(term_builder, err))?;
-                let type_ = type_builder.realise(arena).map_err(|err| Kernel(type_builder, err))?;
+                if self.involve(term_builder, Some(type_builder), arena) {
+                    self.elaborate(arena, term_builder, Some(type_builder))?;
+                    return Ok(None);
+                }
+
+                let term = term_builder.realise(arena, &self.scope).map_err(|err| Kernel(term_builder, err))?;
+                let type_ = type_builder.realise(arena, &self.scope).map_err(|err| Kernel(type_builder, err))?;
 
                 term.check(type_, arena).map_err(|err| Kernel(term_builder, err))?;
                 Ok(None)
             },
 
             Command::GetType(ref term_builder) => {
-                let term = term_builder.realise(arena).map_err(|err| Kernel(term_builder, err))?;
+                if self.involve(term_builder, None, arena) {
+                    let (_, type_, _) = self.elaborate(arena, term_builder, None)?;
+                    return Ok(Some(Outcome::Term(type_)));
+                }
 
-                Ok(term.infer(arena).map(Some).map_err(|err| Kernel(term_builder, err))?)
+                let term = term_builder.realise(arena, &self.scope).map_err(|err| Kernel(term_builder, err))?;
+
+                Ok(term
+                    .infer(arena)
+                    .map(|type_| Some(Outcome::Term(type_)))
+                    .map_err(|err| Kernel(term_builder, err))?)
             },
 
             #[allow(clippy::let_underscore_untyped)]
             Command::Eval(ref term_builder) => {
-                let term = term_builder.realise(arena).map_err(|err| Kernel(term_builder, err))?;
+                if self.involve(term_builder, None, arena) {
+                    let (term, _, _) = self.elaborate(arena, term_builder, None)?;
+                    return Ok(Some(Outcome::Term(term.normal_form(arena))));
+                }
+
+                let term = term_builder.realise(arena, &self.scope).map_err(|err| Kernel(term_builder, err))?;
                 let _ = term.infer(arena).map_err(|err| Kernel(term_builder, err))?;
 
-                Ok(Some(term.normal_form(arena)))
+                Ok(Some(Outcome::Term(term.normal_form(arena))))
+            },
+
+            Command::Inductive(ref builder) => {
+                let (location, s) = builder.name;
+
+                if self.has_variables() {
+                    return Err(TopLevel(Error {
+                        kind: ErrorKind::InductiveInSection,
+                        location,
+                    }));
+                }
+
+                // the inductive type and its constructors are declared under their full names
+                let full_names: Vec<_> = core::iter::once(s)
+                    .chain(builder.constructors.iter().map(|&((_, name), _)| name))
+                    .map(|name| self.scope.qualify(name))
+                    .collect();
+
+                let mut qualified = builder.clone();
+                qualified.name.1 = &full_names[0];
+
+                for (&mut ((_, ref mut name), _), full_name) in qualified.constructors.iter_mut().zip(&full_names[1..]) {
+                    *name = full_name;
+                }
+
+                let constructors = builder.constructors.iter().zip(&full_names[1..]);
+
+                let names = core::iter::once((location, full_names[0].clone()))
+                    .chain(constructors.map(|(&((location, _), _), name)| (location, name.clone())))
+                    .chain(core::iter::once((location, format!("{}_rec", full_names[0]))));
+
+                Self::check_unbound(names, arena)?;
+
+                let inductive = qualified.realise(arena, &self.scope).map_err(|err| Kernel(builder, err))?;
+
+                arena.bind_inductive(inductive).map_err(|err| Kernel(builder, err))?;
+
+                let recursor = format!("{}_rec", full_names[0]);
+                let inputs = builder.free_names();
+
+                for name in full_names.iter().chain(core::iter::once(&recursor)) {
+                    self.record_definition(name);
+                    self.record_dependencies(arena, name, &inputs);
+                }
+
+                Ok(None)
+            },
+
+            Command::Structure(ref builder) => {
+                let (location, s) = builder.name;
+
+                if self.has_variables() {
+                    return Err(TopLevel(Error {
+                        kind: ErrorKind::InductiveInSection,
+                        location,
+                    }));
+                }
+
+                // the structure is declared under its full name, as are its constructor and projections
+                let full_name = self.scope.qualify(s);
+
+                let mut qualified = builder.clone();
+                qualified.name.1 = &full_name;
+
+                let names: Vec<_> = core::iter::once((location, full_name.clone()))
+                    .chain(core::iter::once((location, format!("{full_name}.mk"))))
+                    .chain(core::iter::once((location, format!("{full_name}_rec"))))
+                    .chain(builder.fields.iter().map(|&((location, name), _)| (location, format!("{full_name}.{name}"))))
+                    .collect();
+
+                Self::check_unbound(names.iter().cloned(), arena)?;
+
+                let inductive = qualified.realise(arena, &self.scope).map_err(|err| Kernel(builder, err))?;
+
+                let fields = builder.field_names().collect::<Vec<_>>();
+                arena.bind_structure(inductive, &fields).map_err(|err| Kernel(builder, err))?;
+
+                let inputs = builder.free_names();
+
+                for (_, name) in &names {
+                    self.record_definition(name);
+                    self.record_dependencies(arena, name, &inputs);
+                }
+
+                Ok(None)
+            },
+
+            Command::Notation {
+                fixity,
+                symbol,
+                precedence,
+                name: (location, name),
+                hidden,
+            } => {
+                let added = match *self.scope.resolve(name, arena) {
+                    [ref name] => arena.add_notation(symbol, fixity, precedence, name, hidden),
+                    _ => false,
+                };
+
+                if !added {
+                    return Err(TopLevel(Error {
+                        kind: ErrorKind::UnboundVariable(name.to_owned()),
+                        location,
+                    }));
+                }
+
+                Ok(None)
+            },
+
+            Command::Namespace((_, name)) => {
+                self.scope.enter(name);
+                Ok(None)
+            },
+
+            Command::Section(name) => {
+                self.sections.push(Section {
+                    name: name.map(ToOwned::to_owned),
+                    depth: self.scope.depth(),
+                    variables: Vec::new(),
+                    definitions: Vec::new(),
+                });
+                Ok(None)
+            },
+
+            Command::Variable(ref variables) => {
+                for &((location, s), ref type_builder, implicit) in variables {
+                    if self.sections.is_empty() {
+                        return Err(TopLevel(Error {
+                            kind: ErrorKind::VariableOutsideSection(s.to_owned()),
+                            location,
+                        }));
+                    }
+
+                    let name = self.scope.qualify(s);
+
+                    if arena.is_bound(&name) {
+                        return Err(TopLevel(Error {
+                            kind: ErrorKind::BoundVariable(s.to_owned()),
+                            location,
+                        }));
+                    }
+
+                    let type_ = if self.implicits.involve(type_builder, &self.scope, arena) {
+                        self.elaborate(arena, type_builder, None)?.0
+                    } else {
+                        type_builder.realise(arena, &self.scope).map_err(|err| Kernel(type_builder, err))?
+                    };
+
+                    arena.declare_variable(&name, type_).map_err(|err| Kernel(type_builder, err))?;
+
+                    if let Some(section) = self.sections.last_mut() {
+                        section.variables.push((name, implicit));
+                    }
+                }
+
+                Ok(None)
             },
 
-            Command::Search(s) => Ok(arena.get_binding(s)), // TODO (see #49)
+            Command::End(location, name) => {
+                // namespaces and sections are exited in the order they are entered in
+                let section = self.sections.last().filter(|section| section.depth == self.scope.depth());
+
+                match (section, name) {
+                    (Some(section), _) if section.name.as_deref() == name => {
+                        self.exit_section(arena);
+                        Ok(None)
+                    },
+                    (None, Some(name)) if self.scope.exit(name) => Ok(None),
+                    (_, Some(name)) => Err(TopLevel(Error {
+                        kind: ErrorKind::NotCurrentScope(name.to_owned()),
+                        location,
+                    })),
+                    (_, None) => Err(TopLevel(Error {
+                        kind: ErrorKind::NoSection,
+                        location,
+                    })),
+                }
+            },
+
+            Command::Open(ref namespaces) => {
+                for &(location, name) in namespaces {
+                    if !self.scope.open(name, arena) {
+                        return Err(TopLevel(Error {
+                            kind: ErrorKind::UnknownNamespace(name.to_owned()),
+                            location,
+                        }));
+                    }
+                }
+
+                Ok(None)
+            },
+
+            Command::Search(pattern) => {
+                let names = arena.names().into_iter().filter(|name| matches_name(pattern, name));
+                let matches = names.filter_map(|name| Some((name, arena.get_type(name)?))).collect();
+
+                Ok(Some(Outcome::Matches(matches)))
+            },
+
+            Command::SearchType(ref pattern) => {
+                let mut matches = Vec::new();
+
+                for name in arena.names() {
+                    let builder = term::Builder::new(pattern.location(), term::Payload::Var(name));
+
+                    if Elaborator::new(&self.implicits, &self.scope, &[]).matches(&builder, pattern, arena) {
+                        matches.extend(arena.get_type(name).map(|type_| (name, type_)));
+                    }
+                }
+
+                Ok(Some(Outcome::Matches(matches)))
+            },
+
+            Command::PrintAxioms((location, s)) | Command::PrintDeps((location, s)) => {
+                let names = self.scope.resolve(s, arena);
+
+                let [ref name] = *names else {
+                    return Err(TopLevel(Error {
+                        kind: ErrorKind::UnboundVariable(s.to_owned()),
+                        location,
+                    }));
+                };
+
+                let mut dependencies = self.dependencies(name);
+
+                if matches!(*command, Command::PrintAxioms(_)) {
+                    dependencies.retain(|name| self.assumptions.contains(*name));
+                }
+
+                let names = arena.names().into_iter().filter(|name| dependencies.contains(*name));
+                let matches = names.filter_map(|name| Some((name, arena.get_type(name)?))).collect();
+
+                Ok(Some(Outcome::Matches(matches)))
+            },
 
             Command::Import(ref files) => files
                 .iter()
@@ -279,3 +962,120 @@ impl<'arena> Evaluator {
         }
     }
 }
+
+/// Returns the names a definition refers to, in its term or in its type.
+fn dependencies<'build>(term: &term::Builder<'build>, type_: Option<&term::Builder<'build>>) -> Vec<&'build str> {
+    let mut names = term.free_names();
+
+    for name in type_.map(term::Builder::free_names).unwrap_or_default() {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+
+    names
+}
+
+/// Binds again the term or declaration bound to the full name `name`, abstracted over the constants
+/// of `constants` it uses, either directly or through the types of the other constants it uses.
+///
+/// Returns, for each term of `constants`, whether it has been abstracted over, or `None` if nothing
+/// is bound to `name`.
+fn abstract_binding<'arena>(arena: &mut Arena<'arena>, name: &str, constants: &[Term<'arena>]) -> Option<Vec<bool>> {
+    let decl = arena.get_binding_decl(name);
+    let term = arena.get_binding(name);
+    let type_ = arena.get_type(name);
+    let occurs = |constant| {
+        decl.map_or_else(|| term.is_some_and(|term| term.has_constant(constant)), |decl| decl.has_constant(constant))
+    };
+
+    // the types of the constants used, from the last one
+    let mut used_types = Vec::new();
+    let mut used = Vec::new();
+
+    for &constant in constants.iter().rev() {
+        let is_used = used_types.iter().any(|type_: &Term| type_.has_constant(constant))
+            || occurs(constant)
+            || type_.is_some_and(|type_| type_.has_constant(constant));
+
+        if is_used {
+            used_types.extend(constant.infer(arena).ok());
+        }
+
+        used.push(is_used);
+    }
+
+    used.reverse();
+
+    let abstracted = constants.iter().zip(&used).rev().filter(|&(_, &used)| used).map(|(&constant, _)| constant);
+
+    match (decl, term) {
+        (Some(decl), _) => {
+            let decl = abstracted.fold(decl, |decl, constant| decl.abs_constant(constant, arena));
+            arena.bind_decl(name, decl);
+        },
+        (None, Some(term)) => {
+            let term = abstracted.fold(term, |term, constant| term.abs_constant(constant, arena));
+            arena.bind(name, term);
+        },
+        (None, None) => return None,
+    }
+
+    Some(used)
+}
+
+/// Returns the flags indicating which arguments of an inhabitant of `type_` are implicit, as given
+/// by the binders of `type_`.
+fn implicit_binders(mut type_: &term::Builder) -> Vec<bool> {
+    let mut flags = Vec::new();
+
+    loop {
+        match **type_ {
+            term::Payload::Prod(_, _, ref body) => {
+                flags.push(false);
+                type_ = body;
+            },
+            term::Payload::ImplicitProd(_, _, ref body) => {
+                flags.push(true);
+                type_ = body;
+            },
+            _ => return flags,
+        }
+    }
+}
+
+/// Returns the error raised by the importation of the file `file_path` at `location`, because of
+/// the error `err`.
+fn file_error(location: Location, file_path: &Path, err: crate::error::Error) -> Error {
+    Error {
+        kind: ErrorKind::FileError(file_path.to_string_lossy().to_string(), Box::new(err.into_owned(Some(file_path)))),
+        location,
+    }
+}
+
+/// Indicates whether the full name `name` matches `pattern`: if `pattern` contains the wildcards
+/// `*` (any sequence of characters) or `?` (any character), it must match the whole name,
+/// otherwise it must be a substring of the name.
+fn matches_name(pattern: &str, name: &str) -> bool {
+    /// Indicates whether `name` matches the glob `pattern`.
+    fn glob(pattern: &[u8], name: &[u8]) -> bool {
+        match pattern.split_first() {
+            None => name.is_empty(),
+            Some((b'*', pattern)) => (0..=name.len()).any(|index| glob(pattern, &name[index..])),
+            Some((b'?', pattern)) => name.split_first().is_some_and(|(_, name)| glob(pattern, name)),
+            Some((byte, pattern)) => name.split_first().is_some_and(|(other, name)| byte == other && glob(pattern, name)),
+        }
+    }
+
+    if pattern.contains(['*', '?']) { glob(pattern.as_bytes(), name.as_bytes()) } else { name.contains(pattern) }
+}
+
+/// Attributes the error `err`, raised by the kernel on the elaborated term `term`, to the part of
+/// `builder` it stems from.
+fn locate<'arena, 'build>(
+    term: &Elaborated<'build>,
+    builder: &'build term::Builder<'build>,
+    err: kernel::error::Error<'arena>,
+) -> crate::error::Error<'arena, 'build> {
+    Kernel(term.origin(&err.trace, builder), kernel::error::Error::new(err.kind))
+}
@@ -62,56 +62,68 @@
 extern crate alloc;
 pub mod error;
 pub mod evaluator;
+pub mod proof;
 
 use std::{cmp::max};
 use elaboration::location::Location;
-use error::{Error, ResultProcess};
+use error::{Error, Outcome, OwnedError, ResultProcess};
 use evaluator::{ErrorKind, Evaluator};
-use kernel::memory::term::pretty;
+use kernel::memory::term::pretty::{self, Notation};
 use parser::command::{parse};
 
-pub fn process_input(input: &str) -> ResultProcess {
+/// Processes the commands of `input` in a new environment, files being imported relatively to the
+/// current directory.
+///
+/// # Errors
+/// Returns the first error raised, along with its location in `input` or in the imported file it
+/// comes from.
+pub fn process_input(input: &str) -> Result<(), OwnedError> {
     let mut evaluator = Evaluator::new("".into(), false);
 
     kernel::memory::arena::use_arena_with_axioms(|arena| {
-        let commands = parse::file(input)?;
-
-        commands
-            .iter()
-            .try_for_each(|command| {
-                evaluator.process(arena, command, &mut vec![]).map(|_| ()).map_err(|err| {
-                    evaluator::Error {
-                        kind: ErrorKind::MiscError(err.to_string()),
-                        location: Location::new((0,0), (0,0)),
-                    }
-                    .into()                
-                })
-            })
+        let mut commands = parse::file(input).map_err(|err| Error::Parser(err).into_owned(None))?;
+
+        while let Some(command) = commands.next(arena.notations()) {
+            let command = command.map_err(|err| Error::Parser(err).into_owned(None))?;
+
+            evaluator.process(arena, &command, &mut vec![]).map_err(|err| err.into_owned(None))?;
+        }
+
+        Ok(())
     })
-    .map(|()| None)
 }
 
 /// Toplevel function to display a result, as yielded by the toplevel processing of a command
 ///
-/// The `toggle_location` indicates whether or not to display a hint for the location of the error
-pub fn display(res: ResultProcess, toggle_location: bool) {
+/// The `toggle_location` indicates whether or not to display a hint for the location of the error,
+/// and terms are printed with the given `notations`
+pub fn display<'arena>(res: ResultProcess<'arena, '_>, toggle_location: bool, notations: &[Notation<'arena>]) {
     match res {
         Ok(None) => println!("{}", "\u{2713}"),
 
-        Ok(Some(t)) => println!("{} {}", "\u{2713}", pretty::Term(t)),
+        Ok(Some(Outcome::Term(t))) => println!("{} {}", "\u{2713}", pretty::Notated(t, notations)),
+
+        Ok(Some(Outcome::Matches(matches))) => {
+            if matches.is_empty() {
+                println!("{} nothing found", "\u{2713}");
+            }
+
+            for (name, type_) in matches {
+                println!("{} {name}: {}", "\u{2713}", pretty::Notated(type_, notations));
+            }
+        },
 
         Err(err) => {
             let location = match err {
-                Error::Kernel(builder, ref err) => Some(builder.apply_trace(&err.trace)),
-                Error::Parser(ref err) => Some(err.location),
-
                 Error::TopLevel(evaluator::Error {
-                    kind: evaluator::ErrorKind::FileError(_),
+                    kind: ErrorKind::FileError(_, ref cause),
                     ..
-                }) => None,
-                Error::TopLevel(ref err) => Some(err.location),
+                }) => {
+                    display_cause(cause);
+                    None
+                },
 
-                _ => None,
+                _ => err.location(),
             };
 
             if toggle_location && let Some(loc) = location {
@@ -123,6 +135,15 @@ pub fn display(res: ResultProcess, toggle_location: bool) {
     }
 }
 
+/// Displays the error `err` raised in an imported file, after the errors it stems from.
+fn display_cause(err: &OwnedError) {
+    if let Some(ref cause) = err.cause {
+        display_cause(cause);
+    }
+
+    println!("{} {err}", "\u{2717}");
+}
+
 /// Pretty print a location as underscores
 fn pretty_print_loc(loc: Location) -> String {
     if loc.start.line == loc.end.line {
@@ -135,3 +156,25 @@ fn pretty_print_loc(loc: Location) -> String {
         format!(" {:-<w$}^", "", w = max(loc.start.column, loc.end.column) - 1)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn correct_pretty_print_loc() {
+        assert_eq!(pretty_print_loc(Location::new((1, 3), (1, 3))), "  ^".to_owned());
+        assert_eq!(pretty_print_loc(Location::new((1, 3), (1, 4))), "  ^".to_owned());
+        assert_eq!(pretty_print_loc(Location::new((1, 3), (1, 5))), "  ^^".to_owned());
+        assert_eq!(pretty_print_loc(Location::new((1, 3), (1, 6))), "  ^-^".to_owned());
+        assert_eq!(pretty_print_loc(Location::new((1, 3), (1, 7))), "  ^--^".to_owned());
+    }
+
+    /// Robustness against multilines
+    #[test]
+    fn robust_pretty_print_loc() {
+        pretty_print_loc(Location::new((2, 3), (2, 3)));
+        pretty_print_loc(Location::new((1, 3), (2, 3)));
+        pretty_print_loc(Location::new((1, 3), (2, 1)));
+    }
+}
@@ -0,0 +1,168 @@
+//! Interactive proofs, built by the toplevel one tactic at a time.
+
+use elaboration::builder::{declaration, tactic};
+use elaboration::builder::term::{Builder, Payload};
+use elaboration::elaborator::Goal;
+use elaboration::location::Location;
+use kernel::memory::arena::Arena;
+use kernel::memory::term::pretty::Notation;
+use parser::command::{parse, Command};
+
+use crate::display;
+use crate::error::Error::TopLevel;
+use crate::evaluator::{self, ErrorKind, Evaluator};
+
+/// A proof in progress, started by a line of the form `theorem name : statement := by`, each of
+/// the following lines being a tactic.
+///
+/// Builders borrow the lines they are parsed from: the proof only remembers these lines, and
+/// replays all its tactics at each step. Undoing a tactic thus amounts to forgetting its line.
+pub struct Proof {
+    /// The line which started the proof.
+    theorem: String,
+
+    /// The tactics applied so far, one per line.
+    tactics: Vec<String>,
+}
+
+impl Proof {
+    /// Starts the proof of the theorem stated by `line`, and displays its goal.
+    ///
+    /// Returns `None`, after displaying the error, if the statement is invalid or if the name of
+    /// the theorem is already bound.
+    #[inline]
+    pub fn start(line: String, evaluator: &Evaluator, arena: &mut Arena<'_>) -> Option<Self> {
+        if let Ok(((location, name), _)) = parse::theorem(&line, arena.notations()) {
+            if evaluator.is_bound(arena, name) {
+                let kind = ErrorKind::BoundVariable(name.to_owned());
+
+                display(Err(TopLevel(evaluator::Error { kind, location })), true, arena.notations());
+                return None;
+            }
+        }
+
+        let proof = Self {
+            theorem: line,
+            tactics: Vec::new(),
+        };
+
+        proof.replay(evaluator, arena).then_some(proof)
+    }
+
+    /// Returns the tactics applied so far, one per line.
+    #[inline]
+    #[must_use]
+    pub fn tactics(&self) -> &[String] {
+        &self.tactics
+    }
+
+    /// Runs the tactic given by `line`, and displays the goals left.
+    ///
+    /// The tactic is discarded, after displaying the error, if it cannot be run.
+    #[inline]
+    pub fn step(&mut self, line: String, evaluator: &Evaluator, arena: &mut Arena<'_>) {
+        self.tactics.push(line);
+
+        if !self.replay(evaluator, arena) {
+            self.tactics.pop();
+        }
+    }
+
+    /// Discards the last tactic, and displays the goals left.
+    #[inline]
+    pub fn undo(&mut self, evaluator: &Evaluator, arena: &mut Arena<'_>) {
+        self.tactics.pop();
+        self.replay(evaluator, arena);
+    }
+
+    /// Ends the proof: checks the term built by the tactics against the statement with the
+    /// kernel, and binds it to the name of the theorem as an opaque declaration.
+    ///
+    /// Returns whether the theorem has been bound, after displaying the outcome.
+    #[inline]
+    pub fn qed(&self, evaluator: &mut Evaluator, arena: &mut Arena<'_>) -> bool {
+        let (name, statement, tactics) = match self.parse(arena.notations()) {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                display(Err(err.into()), false, arena.notations());
+                return false;
+            },
+        };
+
+        let block = Builder::new(Location::default(), Payload::By(tactics));
+        let command = Command::Theorem(
+            name,
+            declaration::Builder::Decl(Box::new(statement), Vec::new()),
+            declaration::Builder::Decl(Box::new(block), Vec::new()),
+        );
+
+        let result = evaluator.process_line(arena, &command);
+        let bound = result.is_ok();
+
+        display(result, false, arena.notations());
+        bound
+    }
+
+    /// Runs the tactics of the proof from the start, and displays the goals left or the error
+    /// raised.
+    ///
+    /// Returns whether the tactics have been run successfully.
+    fn replay(&self, evaluator: &Evaluator, arena: &mut Arena<'_>) -> bool {
+        let (_, statement, tactics) = match self.parse(arena.notations()) {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                display(Err(err.into()), true, arena.notations());
+                return false;
+            },
+        };
+
+        let block = Builder::new(Location::default(), Payload::By(tactics));
+
+        match evaluator.goals(arena, &statement, &block) {
+            Ok(goals) => {
+                display_goals(&goals);
+                true
+            },
+            Err(err) => {
+                display(Err(err), true, arena.notations());
+                false
+            },
+        }
+    }
+
+    /// Parses the statement and the tactics of the proof, with the given notations.
+    fn parse<'proof, 'arena: 'proof>(
+        &'proof self,
+        notations: &[Notation<'arena>],
+    ) -> parser::error::Result<((Location, &'proof str), Builder<'proof>, Vec<tactic::Builder<'proof>>)> {
+        let (name, statement) = parse::theorem(&self.theorem, notations)?;
+        let tactics = self
+            .tactics
+            .iter()
+            .map(|line| parse::tactic(line, notations))
+            .collect::<parser::error::Result<_>>()?;
+
+        Ok((name, statement, tactics))
+    }
+}
+
+/// Displays the goals left in a proof.
+fn display_goals(goals: &[Goal]) {
+    if goals.is_empty() {
+        println!("no goals left, the proof can be ended with qed");
+        return;
+    }
+
+    for (index, goal) in goals.iter().enumerate() {
+        println!("goal {}/{}", index + 1, goals.len());
+
+        for hypothesis in &goal.hypotheses {
+            match hypothesis.value {
+                Some(ref value) => println!("  {}: {} := {value}", hypothesis.name, hypothesis.type_),
+                None => println!("  {}: {}", hypothesis.name, hypothesis.type_),
+            }
+        }
+
+        println!("  \u{22A2} {}", goal.type_);
+    }
+}
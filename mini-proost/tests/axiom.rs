@@ -0,0 +1,56 @@
+use kernel::memory::arena::{use_arena_with_axioms, Arena};
+use mini_proost::error::Outcome;
+use mini_proost::evaluator::Evaluator;
+use parser::command::parse;
+
+/// Parses and processes `line`, returning the message of the error raised, if any.
+fn run<'arena>(evaluator: &mut Evaluator, arena: &mut Arena<'arena>, line: &str) -> Result<Option<Outcome<'arena>>, String> {
+    let command = parse::line(line, arena.notations()).map_err(|err| err.to_string())?;
+
+    evaluator.process_line(arena, &command).map_err(|err| err.to_string())
+}
+
+#[test]
+fn forbidden_axioms() {
+    let mut evaluator = Evaluator::new("".into(), false);
+    evaluator.forbid_axioms();
+
+    use_arena_with_axioms(|arena| {
+        assert_eq!(
+            run(&mut evaluator, arena, "axiom lem (P : Prop) : P"),
+            Err("axiom lem cannot be assumed, as axioms are forbidden".to_owned())
+        );
+        assert!(!arena.is_bound("lem"));
+
+        // definitions are still allowed
+        assert_eq!(run(&mut evaluator, arena, "def id (P : Prop) (h : P) : P := h"), Ok(None));
+    });
+}
+
+#[test]
+fn user_axioms() {
+    let mut evaluator = Evaluator::new("".into(), false);
+
+    use_arena_with_axioms(|arena| {
+        assert_eq!(run(&mut evaluator, arena, "axiom lem (P : Prop) : P"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "axiom choice.{u} (A : Sort u) : A"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "axiom lem (P : Prop) : P"), Err("identifier lem already defined".to_owned()));
+
+        // universe variables are substituted in the type of the axiom
+        assert_eq!(run(&mut evaluator, arena, "check choice.{1} Nat : Nat"), Ok(None));
+
+        // axioms never reduce
+        assert_eq!(run(&mut evaluator, arena, "def some_nat := choice.{1} Nat"), Ok(None));
+        assert!(run(&mut evaluator, arena, "check Refl Nat some_nat : Eq Nat some_nat Zero").is_err());
+    });
+}
+
+#[test]
+fn axioms_of_non_types() {
+    let mut evaluator = Evaluator::new("".into(), false);
+
+    use_arena_with_axioms(|arena| {
+        assert!(run(&mut evaluator, arena, "axiom zero : Zero").is_err());
+        assert!(!arena.is_bound("zero"));
+    });
+}
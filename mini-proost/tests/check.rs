@@ -0,0 +1,72 @@
+use std::path::Path;
+
+use elaboration::location::Position;
+use kernel::memory::arena::use_arena_with_axioms;
+use mini_proost::error::{Kind, OwnedError};
+use mini_proost::evaluator::Evaluator;
+
+/// Checks `files`, returning the kind, the file name and the start position of each error raised.
+fn check(files: &[&str], bound: &[&str], unbound: &[&str]) -> Vec<(Kind, String, Option<Position>)> {
+    let mut evaluator = Evaluator::new(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/files"), false);
+
+    use_arena_with_axioms(|arena| {
+        let diagnostics = evaluator.check_files(arena, files);
+
+        for name in bound {
+            assert!(arena.is_bound(name), "{name} should be bound");
+        }
+
+        for name in unbound {
+            assert!(!arena.is_bound(name), "{name} should not be bound");
+        }
+
+        diagnostics.into_iter().map(summary).collect()
+    })
+}
+
+/// The kind, the file name and the start position of `err`.
+fn summary(err: OwnedError) -> (Kind, String, Option<Position>) {
+    let file = err.file.and_then(|file| file.file_name().map(|name| name.to_string_lossy().into_owned()));
+
+    (err.kind, file.unwrap_or_default(), err.location.map(|location| location.start))
+}
+
+#[test]
+fn failing_commands_are_skipped() {
+    let diagnostics = check(&["recovery.mdln"], &["x", "w"], &["y", "z"]);
+
+    assert_eq!(diagnostics.len(), 2);
+
+    // the definition of y fails, and binds nothing
+    assert_eq!(diagnostics[0].1, "recovery.mdln");
+    assert_eq!(diagnostics[0].2.map(|position| position.line), Some(2));
+
+    // the parenthesis left open is reported where the next command starts, and the parser
+    // resynchronises there
+    assert_eq!(diagnostics[1], (Kind::Parser, "recovery.mdln".to_owned(), Some(Position::new(4, 1))));
+}
+
+#[test]
+fn errors_of_imported_files() {
+    let diagnostics = check(&["recovery_import.mdln"], &["x", "w", "u"], &["y", "z", "v"]);
+
+    assert_eq!(diagnostics.len(), 3);
+
+    // errors in the imported file come first, with their own file and location
+    assert_eq!(diagnostics[0].1, "recovery.mdln");
+    assert_eq!(diagnostics[0].2.map(|position| position.line), Some(2));
+    assert_eq!(diagnostics[1].1, "recovery.mdln");
+    assert_eq!(diagnostics[1].2.map(|position| position.line), Some(4));
+
+    // y being unbound, the definition of v fails in turn
+    assert_eq!(diagnostics[2].1, "recovery_import.mdln");
+    assert_eq!(diagnostics[2].2.map(|position| position.line), Some(2));
+}
+
+#[test]
+fn missing_files() {
+    let diagnostics = check(&["missing.mdln", "recovery.mdln"], &["x"], &[]);
+
+    assert_eq!(diagnostics.len(), 3);
+    assert_eq!(diagnostics[0], (Kind::TopLevel, String::new(), None));
+}
@@ -0,0 +1,114 @@
+use kernel::memory::arena::{use_arena_with_axioms, Arena};
+use mini_proost::error::Outcome;
+use mini_proost::evaluator::Evaluator;
+use parser::command::parse;
+
+/// Parses and processes `line`, returning the message of the error raised, if any.
+fn run<'arena>(evaluator: &mut Evaluator, arena: &mut Arena<'arena>, line: &str) -> Result<Option<Outcome<'arena>>, String> {
+    let command = parse::line(line, arena.notations()).map_err(|err| err.to_string())?;
+
+    evaluator.process_line(arena, &command).map_err(|err| err.to_string())
+}
+
+/// Processes the search `line`, and returns the names found.
+fn names<'arena>(evaluator: &mut Evaluator, arena: &mut Arena<'arena>, line: &str) -> Vec<&'arena str> {
+    match run(evaluator, arena, line) {
+        Ok(Some(Outcome::Matches(matches))) => matches.into_iter().map(|(name, _)| name).collect(),
+        result => panic!("unexpected result {result:?}"),
+    }
+}
+
+#[test]
+fn transitive_dependencies() {
+    let mut evaluator = Evaluator::new("".into(), false);
+
+    use_arena_with_axioms(|arena| {
+        assert_eq!(run(&mut evaluator, arena, "def one := Succ Zero"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "def two := Succ one"), Ok(None));
+
+        assert_eq!(names(&mut evaluator, arena, "print deps one"), ["Nat", "Succ", "Zero"]);
+        assert_eq!(names(&mut evaluator, arena, "print deps two"), ["Nat", "Succ", "Zero", "one"]);
+        assert_eq!(names(&mut evaluator, arena, "print deps Zero"), Vec::<&str>::new());
+
+        // primitives are not assumptions
+        assert_eq!(names(&mut evaluator, arena, "print axioms two"), Vec::<&str>::new());
+
+        assert_eq!(run(&mut evaluator, arena, "print deps three"), Err("unknown identifier three".to_owned()));
+    });
+}
+
+#[test]
+fn elaborated_dependencies() {
+    let mut evaluator = Evaluator::new("".into(), false);
+
+    use_arena_with_axioms(|arena| {
+        let symm = "theorem symm : (a b : Nat) -> Eq Nat a b -> Eq Nat b a := \
+            by intro a; intro b; intro h; rewrite h; exact Refl Nat b";
+        assert_eq!(run(&mut evaluator, arena, symm), Ok(None));
+        assert!(names(&mut evaluator, arena, "print deps symm").contains(&"Eq_rec"));
+        assert_eq!(names(&mut evaluator, arena, "print axioms symm"), Vec::<&str>::new());
+
+        let refl = "theorem refl : (n : Nat) -> Eq Nat n n := \
+            by intro n; induction n; exact Refl Nat Zero; exact Refl Nat (Succ n)";
+        assert_eq!(run(&mut evaluator, arena, refl), Ok(None));
+        assert!(names(&mut evaluator, arena, "print deps refl").contains(&"Nat_rec"));
+        assert_eq!(names(&mut evaluator, arena, "print axioms refl"), Vec::<&str>::new());
+    });
+}
+
+#[test]
+fn assumptions() {
+    let mut evaluator = Evaluator::new("".into(), false);
+
+    use_arena_with_axioms(|arena| {
+        assert_eq!(run(&mut evaluator, arena, "axiom A : Type"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "axiom a : A"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "axiom absurd (P : Prop) : P"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "theorem contradiction : False := absurd False"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "def b := a"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "def c (x : Nat) := contradiction"), Ok(None));
+
+        // the axioms used by opaque declarations or in the types of other axioms are found too
+        assert_eq!(names(&mut evaluator, arena, "print axioms b"), ["A", "a"]);
+        assert_eq!(names(&mut evaluator, arena, "print axioms c"), ["absurd"]);
+        assert_eq!(names(&mut evaluator, arena, "print deps c"), ["False", "Nat", "absurd", "contradiction"]);
+    });
+}
+
+#[test]
+fn section_variables() {
+    let mut evaluator = Evaluator::new("".into(), false);
+
+    use_arena_with_axioms(|arena| {
+        assert_eq!(run(&mut evaluator, arena, "section"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "variable (n : Nat)"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "def succ := Succ n"), Ok(None));
+        assert_eq!(names(&mut evaluator, arena, "print deps succ"), ["Nat", "Succ", "n"]);
+
+        // variables are no longer dependencies once abstracted
+        assert_eq!(run(&mut evaluator, arena, "end"), Ok(None));
+        assert_eq!(names(&mut evaluator, arena, "print deps succ"), ["Nat", "Succ"]);
+    });
+}
+
+#[test]
+fn inductive_dependencies() {
+    let mut evaluator = Evaluator::new("".into(), false);
+
+    use_arena_with_axioms(|arena| {
+        assert_eq!(run(&mut evaluator, arena, "axiom A : Type"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "inductive T : Type := | leaf : T | node : A -> T -> T"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "structure S where x : A; n : Nat"), Ok(None));
+
+        // every name bound by an inductive type or a structure depends on the whole declaration
+        assert_eq!(names(&mut evaluator, arena, "print deps leaf"), ["A", "T"]);
+        assert_eq!(names(&mut evaluator, arena, "print axioms leaf"), ["A"]);
+        assert_eq!(names(&mut evaluator, arena, "print axioms T_rec"), ["A"]);
+        assert_eq!(names(&mut evaluator, arena, "print axioms S.mk"), ["A"]);
+
+        // projections are seen through
+        assert_eq!(run(&mut evaluator, arena, "def n (s : S) := S.n s"), Ok(None));
+        assert!(names(&mut evaluator, arena, "print deps n").contains(&"S.n"));
+        assert_eq!(names(&mut evaluator, arena, "print axioms n"), ["A"]);
+    });
+}
@@ -0,0 +1,127 @@
+use kernel::memory::arena::{use_arena_with_axioms, Arena};
+use mini_proost::error::Outcome;
+use mini_proost::evaluator::Evaluator;
+use parser::command::parse;
+
+/// Parses and processes `line`, returning the message of the error raised, if any.
+fn run<'arena>(evaluator: &mut Evaluator, arena: &mut Arena<'arena>, line: &str) -> Result<Option<Outcome<'arena>>, String> {
+    let command = parse::line(line, arena.notations()).map_err(|err| err.to_string())?;
+
+    evaluator.process_line(arena, &command).map_err(|err| err.to_string())
+}
+
+#[test]
+fn qualified_names() {
+    let mut evaluator = Evaluator::new("".into(), false);
+
+    use_arena_with_axioms(|arena| {
+        assert_eq!(run(&mut evaluator, arena, "namespace Foo"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "def x := Zero"), Ok(None));
+        assert!(arena.is_bound("Foo.x"));
+        assert!(!arena.is_bound("x"));
+
+        // names of the current namespace are found with or without their prefix
+        assert_eq!(run(&mut evaluator, arena, "def y := x"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "def z := Foo.x"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "def x := Zero"), Err("identifier x already defined".to_owned()));
+
+        assert_eq!(run(&mut evaluator, arena, "end Foo"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "end Foo"), Err("Foo is not the current namespace or section".to_owned()));
+
+        assert_eq!(run(&mut evaluator, arena, "def y := x"), Err("unknown identifier x".to_owned()));
+        assert_eq!(run(&mut evaluator, arena, "def y := Foo.x"), Ok(None));
+    });
+}
+
+#[test]
+fn nested_namespaces() {
+    let mut evaluator = Evaluator::new("".into(), false);
+
+    use_arena_with_axioms(|arena| {
+        assert_eq!(run(&mut evaluator, arena, "def x := Zero"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "namespace Foo"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "namespace Bar"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "def x := Succ Zero"), Ok(None));
+
+        // the innermost namespace shadows the others
+        assert_eq!(run(&mut evaluator, arena, "def y := x"), Ok(None));
+        assert_eq!(arena.get_binding("Foo.Bar.y"), arena.get_binding("Foo.Bar.x"));
+
+        assert_eq!(run(&mut evaluator, arena, "end Foo"), Err("Foo is not the current namespace or section".to_owned()));
+        assert_eq!(run(&mut evaluator, arena, "end Bar"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "end Foo"), Ok(None));
+
+        assert_eq!(run(&mut evaluator, arena, "def y := x"), Ok(None));
+        assert_eq!(arena.get_binding("y"), arena.get_binding("x"));
+    });
+}
+
+#[test]
+fn opened_namespaces() {
+    let mut evaluator = Evaluator::new("".into(), false);
+
+    use_arena_with_axioms(|arena| {
+        assert_eq!(run(&mut evaluator, arena, "namespace Foo"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "def x := Zero"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "def y := Zero"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "end Foo"), Ok(None));
+
+        assert_eq!(run(&mut evaluator, arena, "namespace Bar"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "def x := Succ Zero"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "end Bar"), Ok(None));
+
+        assert_eq!(run(&mut evaluator, arena, "open Baz"), Err("unknown namespace Baz".to_owned()));
+
+        assert_eq!(run(&mut evaluator, arena, "namespace Baz"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "open Foo Bar"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "def z := y"), Ok(None));
+
+        // names found in several opened namespaces are ambiguous
+        assert_eq!(
+            run(&mut evaluator, arena, "def w := x"),
+            Err("ambiguous identifier x, which may refer to Foo.x, Bar.x".to_owned())
+        );
+
+        // opened namespaces are closed with the namespace they are opened in
+        assert_eq!(run(&mut evaluator, arena, "end Baz"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "def z := y"), Err("unknown identifier y".to_owned()));
+    });
+}
+
+#[test]
+fn inductive_in_namespace() {
+    let mut evaluator = Evaluator::new("".into(), false);
+
+    use_arena_with_axioms(|arena| {
+        assert_eq!(run(&mut evaluator, arena, "namespace Foo"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "inductive T : Type := | leaf : T | node : T -> T -> T"), Ok(None));
+        assert!(arena.is_bound("Foo.T"));
+        assert!(arena.is_bound("Foo.leaf"));
+        assert!(arena.is_bound("Foo.T_rec"));
+
+        assert_eq!(run(&mut evaluator, arena, "def t : T := node leaf leaf"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "end Foo"), Ok(None));
+
+        assert_eq!(run(&mut evaluator, arena, "def t : Foo.T := Foo.node Foo.leaf Foo.t"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "print axioms t"), Ok(Some(Outcome::Matches(vec![]))));
+    });
+}
+
+#[test]
+fn namespaces_and_sections() {
+    let mut evaluator = Evaluator::new("".into(), false);
+
+    use_arena_with_axioms(|arena| {
+        assert_eq!(run(&mut evaluator, arena, "namespace Foo"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "section"), Ok(None));
+
+        // namespaces and sections must be exited in the order they are entered in
+        assert_eq!(run(&mut evaluator, arena, "end Foo"), Err("Foo is not the current namespace or section".to_owned()));
+        assert_eq!(run(&mut evaluator, arena, "namespace Bar"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "end"), Err("no section to end".to_owned()));
+
+        assert_eq!(run(&mut evaluator, arena, "end Bar"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "end"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "end Foo"), Ok(None));
+    });
+}
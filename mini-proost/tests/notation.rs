@@ -0,0 +1,27 @@
+use std::path::Path;
+
+use kernel::memory::arena::{use_arena_with_axioms, Arena};
+use mini_proost::error::Outcome;
+use mini_proost::evaluator::Evaluator;
+use parser::command::parse;
+
+/// Parses and processes `line`, returning the message of the error raised, if any.
+fn run<'arena>(evaluator: &mut Evaluator, arena: &mut Arena<'arena>, line: &str) -> Result<Option<Outcome<'arena>>, String> {
+    let command = parse::line(line, arena.notations()).map_err(|err| err.to_string())?;
+
+    evaluator.process_line(arena, &command).map_err(|err| err.to_string())
+}
+
+#[test]
+fn imported_notations() {
+    let mut evaluator = Evaluator::new(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/files"), false);
+
+    use_arena_with_axioms(|arena| {
+        assert_eq!(run(&mut evaluator, arena, "import notation.mdln"), Ok(None));
+        assert!(arena.is_bound("zero_eq"));
+
+        // the notations of an imported file outlive it
+        assert!(run(&mut evaluator, arena, "check zero_eq : Zero = Zero").is_ok());
+        assert!(run(&mut evaluator, arena, "check zero_eq : Zero = Succ Zero").is_err());
+    });
+}
@@ -0,0 +1,115 @@
+use kernel::memory::arena::{use_arena_with_axioms, Arena};
+use mini_proost::error::Outcome;
+use mini_proost::evaluator::Evaluator;
+use parser::command::parse;
+
+/// Parses and processes `line`, returning the message of the error raised, if any.
+fn run<'arena>(evaluator: &mut Evaluator, arena: &mut Arena<'arena>, line: &str) -> Result<Option<Outcome<'arena>>, String> {
+    let command = parse::line(line, arena.notations()).map_err(|err| err.to_string())?;
+
+    evaluator.process_line(arena, &command).map_err(|err| err.to_string())
+}
+
+#[test]
+fn abstracted_variables() {
+    let mut evaluator = Evaluator::new("".into(), false);
+
+    use_arena_with_axioms(|arena| {
+        assert_eq!(run(&mut evaluator, arena, "variable (n : Nat)"), Err("variable n must be declared in a section".to_owned()));
+        assert_eq!(run(&mut evaluator, arena, "variable (x : Zero)"), Err("variable x must be declared in a section".to_owned()));
+
+        assert_eq!(run(&mut evaluator, arena, "section Foo"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "variable (n m : Nat) (P : Prop)"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "def one := Succ m"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "def zero := Zero"), Ok(None));
+
+        // sections must be exited under their own name
+        assert_eq!(run(&mut evaluator, arena, "end"), Err("no section to end".to_owned()));
+        assert_eq!(run(&mut evaluator, arena, "end Bar"), Err("Bar is not the current namespace or section".to_owned()));
+        assert_eq!(run(&mut evaluator, arena, "end Foo"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "end"), Err("no section to end".to_owned()));
+
+        assert!(!arena.is_bound("m"));
+        assert_eq!(run(&mut evaluator, arena, "def succ := fun m : Nat => Succ m"), Ok(None));
+        assert_eq!(arena.get_binding("one"), arena.get_binding("succ"));
+        assert_eq!(run(&mut evaluator, arena, "def two := one (one Zero)"), Ok(None));
+    });
+}
+
+#[test]
+fn variables_of_types() {
+    let mut evaluator = Evaluator::new("".into(), false);
+
+    use_arena_with_axioms(|arena| {
+        assert_eq!(run(&mut evaluator, arena, "section"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "variable (A : Prop) (h : A)"), Ok(None));
+        assert!(run(&mut evaluator, arena, "variable (x : h)").is_err());
+
+        // variables used by the types of the variables used are abstracted too
+        assert_eq!(run(&mut evaluator, arena, "def id := h"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "end"), Ok(None));
+
+        assert_eq!(run(&mut evaluator, arena, "def expected := fun (A : Prop) (h : A) => h"), Ok(None));
+        assert_eq!(arena.get_binding("id"), arena.get_binding("expected"));
+    });
+}
+
+#[test]
+fn implicit_variables() {
+    let mut evaluator = Evaluator::new("".into(), false);
+
+    use_arena_with_axioms(|arena| {
+        assert_eq!(run(&mut evaluator, arena, "section"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "variable {A : Type} (a : A)"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "def id := a"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "end"), Ok(None));
+
+        // the implicit variables abstracted over are implicit arguments
+        assert_eq!(run(&mut evaluator, arena, "def zero : Nat := id Zero"), Ok(None));
+        assert!(run(&mut evaluator, arena, "def one : Nat := id Nat (Succ Zero)").is_err());
+    });
+}
+
+#[test]
+fn nested_sections() {
+    let mut evaluator = Evaluator::new("".into(), false);
+
+    use_arena_with_axioms(|arena| {
+        assert_eq!(run(&mut evaluator, arena, "section"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "variable (n : Nat)"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "section Inner"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "variable (m : Nat)"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "def same := Eq Nat n m"), Ok(None));
+
+        // definitions are abstracted over the variables of each section they are defined in
+        assert_eq!(run(&mut evaluator, arena, "end Inner"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "def expected := fun m : Nat => Eq Nat n m"), Ok(None));
+        assert_eq!(arena.get_binding("same"), arena.get_binding("expected"));
+
+        assert_eq!(run(&mut evaluator, arena, "end"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "def all := fun (n m : Nat) => Eq Nat n m"), Ok(None));
+        assert_eq!(arena.get_binding("same"), arena.get_binding("all"));
+    });
+}
+
+#[test]
+fn assumptions_in_sections() {
+    let mut evaluator = Evaluator::new("".into(), false);
+
+    use_arena_with_axioms(|arena| {
+        assert_eq!(run(&mut evaluator, arena, "section"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "axiom A : Type"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "variable (a : A)"), Ok(None));
+
+        // axioms and inductive types cannot be abstracted over variables
+        assert_eq!(
+            run(&mut evaluator, arena, "axiom B : Type"),
+            Err("axioms cannot be declared in a section with variables".to_owned())
+        );
+        assert!(run(&mut evaluator, arena, "inductive T : Type := | t : T").is_err());
+
+        assert_eq!(run(&mut evaluator, arena, "end"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "axiom B : Type"), Ok(None));
+        assert_eq!(run(&mut evaluator, arena, "inductive T : Type := | t : T"), Ok(None));
+    });
+}
@@ -0,0 +1,27 @@
+use kernel::memory::arena::{use_arena_with_axioms, Arena};
+use mini_proost::error::Outcome;
+use mini_proost::evaluator::Evaluator;
+use parser::command::parse;
+
+/// Parses and processes `line`, returning the message of the error raised, if any.
+fn run<'arena>(evaluator: &mut Evaluator, arena: &mut Arena<'arena>, line: &str) -> Result<Option<Outcome<'arena>>, String> {
+    let command = parse::line(line, arena.notations()).map_err(|err| err.to_string())?;
+
+    evaluator.process_line(arena, &command).map_err(|err| err.to_string())
+}
+
+#[test]
+fn theorems_are_proofs() {
+    let mut evaluator = Evaluator::new("".into(), false);
+
+    use_arena_with_axioms(|arena| {
+        assert_eq!(run(&mut evaluator, arena, "theorem two : Nat := 2"), Err("Nat is not a proposition".to_owned()));
+        assert!(!arena.is_bound("two"));
+
+        assert_eq!(run(&mut evaluator, arena, "theorem refl.{u} (A : Sort u) (x : A) : Eq A x x := Refl A x"), Ok(None));
+        // statements quantifying over propositions are propositions
+        assert_eq!(run(&mut evaluator, arena, "theorem id (P : Prop) : P -> P := fun h : P => h"), Ok(None));
+        assert!(run(&mut evaluator, arena, "theorem tid (A : Type) : A -> A := fun x : A => x").is_err());
+        assert!(!arena.is_bound("tid"));
+    });
+}
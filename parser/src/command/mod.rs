@@ -4,9 +4,10 @@ pub mod parse;
 
 use core::fmt;
 
-use elaboration::builder::declaration;
 use elaboration::builder::term::Builder;
+use elaboration::builder::{declaration, inductive, structure};
 use elaboration::location::Location;
+use kernel::memory::term::pretty::Fixity;
 
 /// The type of commands that can be received by the kernel.
 #[derive(Debug, Eq, PartialEq)]
@@ -17,6 +18,12 @@ pub enum Command<'build> {
     /// Define the given declaration
     Declaration((Location, &'build str), Option<declaration::Builder<'build>>, declaration::Builder<'build>),
 
+    /// Assume an axiom of the given type
+    Axiom((Location, &'build str), declaration::Builder<'build>),
+
+    /// Define the given declaration as an opaque theorem, whose statement is given
+    Theorem((Location, &'build str), declaration::Builder<'build>, declaration::Builder<'build>),
+
     /// Infer the type of a term and check that it matches the given one.
     CheckType(Builder<'build>, Builder<'build>),
 
@@ -29,14 +36,67 @@ pub enum Command<'build> {
     /// Import a (series of) file(s).
     Import(Vec<(Location, &'build str)>),
 
-    /// Search for a variable
+    /// Search for the names matching a pattern, either a substring or a glob.
     Search(&'build str),
+
+    /// Search for the names whose types match a pattern.
+    SearchType(Builder<'build>),
+
+    /// Print the axioms a name depends on.
+    PrintAxioms((Location, &'build str)),
+
+    /// Print the terms and declarations a name depends on.
+    PrintDeps((Location, &'build str)),
+
+    /// Declare an inductive type.
+    Inductive(inductive::Builder<'build>),
+
+    /// Declare a structure.
+    Structure(structure::Builder<'build>),
+
+    /// Declare a notation, under which `name` applied to `hidden` holes, then to the operands, is
+    /// written with `symbol`.
+    Notation {
+        /// The position of the operands.
+        fixity: Fixity,
+
+        /// The symbol of the notation.
+        symbol: &'build str,
+
+        /// The precedence of the notation.
+        precedence: usize,
+
+        /// The name of the constant the notation stands for.
+        name: (Location, &'build str),
+
+        /// The number of leading arguments of the constant which do not appear in the notation.
+        hidden: usize,
+    },
+
+    /// Enter a namespace.
+    Namespace((Location, &'build str)),
+
+    /// Enter a section, named or not.
+    Section(Option<&'build str>),
+
+    /// Declare a (series of) variable(s) in the current section, along with their types and
+    /// whether they are implicit.
+    Variable(Vec<((Location, &'build str), Builder<'build>, bool)>),
+
+    /// Exit a namespace or a section.
+    End(Location, Option<&'build str>),
+
+    /// Open a (series of) namespace(s).
+    Open(Vec<(Location, &'build str)>),
 }
 
 impl<'build> fmt::Display for Command<'build> {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        use Command::{CheckType, Declaration, Define, Eval, GetType, Import, Search};
+        use Command::{
+            Axiom, CheckType, Declaration, Define, End, Eval, GetType, Import, Inductive, Namespace, Notation, Open, PrintAxioms,
+            PrintDeps, Search, SearchType, Section, Structure, Theorem, Variable,
+        };
 
         match *self {
             Define((_, name), None, ref t) => write!(f, "def {name} := {t}"),
@@ -47,6 +107,10 @@ impl<'build> fmt::Display for Command<'build> {
 
             Declaration((_, name), Some(ref ty), ref t) => write!(f, "def {name}: {ty} := {t}"),
 
+            Axiom((_, name), ref ty) => write!(f, "axiom {name}: {ty}"),
+
+            Theorem((_, name), ref ty, ref t) => write!(f, "theorem {name}: {ty} := {t}"),
+
             CheckType(ref t, ref ty) => write!(f, "check {t}: {ty}"),
 
             GetType(ref t) => write!(f, "check {t}"),
@@ -58,7 +122,57 @@ impl<'build> fmt::Display for Command<'build> {
                 files.iter().try_for_each(|&(_, file)| write!(f, " {file}"))
             },
 
-            Search(name) => write!(f, "search {name}"),
+            Search(pattern) => write!(f, "search {pattern}"),
+
+            SearchType(ref pattern) => write!(f, "search : {pattern}"),
+
+            PrintAxioms((_, name)) => write!(f, "print axioms {name}"),
+
+            PrintDeps((_, name)) => write!(f, "print deps {name}"),
+
+            Inductive(ref builder) => write!(f, "inductive {builder}"),
+
+            Structure(ref builder) => write!(f, "structure {builder}"),
+
+            Notation {
+                fixity,
+                symbol,
+                precedence,
+                name: (_, name),
+                hidden,
+            } => {
+                let keyword = match fixity {
+                    Fixity::Prefix => "prefix",
+                    Fixity::Infix => "infix",
+                    Fixity::InfixLeft => "infixl",
+                    Fixity::InfixRight => "infixr",
+                };
+
+                write!(f, "{keyword} \"{symbol}\" {precedence} := {name}")?;
+                (0..hidden).try_for_each(|_| write!(f, " _"))
+            },
+
+            Namespace((_, name)) => write!(f, "namespace {name}"),
+
+            Section(None) => write!(f, "section"),
+
+            Section(Some(name)) => write!(f, "section {name}"),
+
+            Variable(ref variables) => {
+                write!(f, "variable")?;
+                variables.iter().try_for_each(|&((_, name), ref type_, implicit)| {
+                    if implicit { write!(f, " {{{name}: {type_}}}") } else { write!(f, " ({name}: {type_})") }
+                })
+            },
+
+            End(_, None) => write!(f, "end"),
+
+            End(_, Some(name)) => write!(f, "end {name}"),
+
+            Open(ref namespaces) => {
+                write!(f, "open")?;
+                namespaces.iter().try_for_each(|&(_, namespace)| write!(f, " {namespace}"))
+            },
         }
     }
 }
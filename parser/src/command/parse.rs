@@ -1,10 +1,13 @@
 //! Parsing functions, from text to [commands](Command).
 
+use core::iter::Peekable;
 use core::result;
 
-use elaboration::builder::{declaration, level, term};
-use elaboration::location::Location;
-use pest::iterators::Pair;
+use elaboration::builder::{declaration, inductive, level, structure, tactic, term};
+use elaboration::location::{Location, Position};
+use kernel::memory::term::pretty::{Fixity, Notation};
+use num_bigint::BigUint;
+use pest::iterators::{Pair, Pairs};
 use pest::{Parser, Span};
 
 use crate::command::Command;
@@ -73,10 +76,11 @@ fn parse_level(pair: Pair<Rule>) -> Result<level::Builder> {
     }
 }
 
-/// Builds [`kernel`] [terms](term::Builder) from errorless pest output
-fn parse_term(pair: Pair<Rule>) -> Result<term::Builder> {
+/// Builds [`kernel`] [terms](term::Builder) from errorless pest output, where operators are those
+/// of the given notations
+fn parse_term<'build, 'arena: 'build>(pair: Pair<'build, Rule>, notations: &[Notation<'arena>]) -> Result<term::Builder<'build>> {
     use term::Builder;
-    use term::Payload::{Abs, App, Prod, Prop, Sort, Type, Var, VarInstance};
+    use term::Payload::{App, By, Explicit, Hole, Let, NatLit, Prod, Prop, Sort, Type, Var, VarInstance};
 
     let loc = convert_span(pair.as_span());
 
@@ -85,6 +89,25 @@ fn parse_term(pair: Pair<Rule>) -> Result<term::Builder> {
 
         Rule::Var => Ok(Builder::new(loc, Var(pair.into_inner().as_str()))),
 
+        Rule::Explicit => {
+            let term = parse_term(pair.into_inner().next().unwrap(), notations)?;
+
+            Ok(Builder::new(loc, Explicit(Box::new(term))))
+        },
+
+        Rule::Hole => Ok(Builder::new(loc, Hole(pair.as_str().strip_prefix('?')))),
+
+        Rule::By => Ok(Builder::new(loc, By(pair.into_inner().map(|pair| parse_tactic(pair, notations)).collect::<Result<_>>()?))),
+
+        Rule::NatLit => {
+            let n = pair.as_str().parse::<BigUint>().map_err(|err| Error {
+                location: loc,
+                kind: Kind::TransformError(err.to_string()),
+            })?;
+
+            Ok(Builder::new(loc, NatLit(n)))
+        },
+
         Rule::VarDecl => {
             let mut iter = pair.into_inner();
             let name = iter.next().unwrap().as_str();
@@ -104,7 +127,7 @@ fn parse_term(pair: Pair<Rule>) -> Result<term::Builder> {
         ),
 
         Rule::App => {
-            let mut iter = pair.into_inner().map(parse_term);
+            let mut iter = pair.into_inner().map(|pair| parse_term(pair, notations));
             let term = iter.next().unwrap()?;
 
             iter.try_fold(term, |acc, x| x.map(|x| Builder::new(loc, App(Box::new(acc), Box::new(x)))))
@@ -112,51 +135,171 @@ fn parse_term(pair: Pair<Rule>) -> Result<term::Builder> {
 
         Rule::Abs => {
             let mut iter = pair.into_inner();
-            let body = parse_term(iter.next_back().unwrap())?;
+            let body = parse_term(iter.next_back().unwrap(), notations)?;
 
-            iter.flat_map(|pair| {
-                let mut pair = pair.into_inner();
-                let type_ = parse_term(pair.next_back().unwrap());
+            Ok(parse_binders(iter, notations)?.into_iter().fold(body, |acc, binder| abs(loc, binder, acc)))
+        },
 
-                pair.map(move |var| (var.as_str(), type_.clone()))
-            })
-            .rev()
-            .try_fold(body, |acc, (var, type_)| type_.map(|type_| Builder::new(loc, Abs(var, Box::new(type_), Box::new(acc)))))
+        Rule::Let => {
+            let mut iter = pair.into_inner();
+            let name = iter.next().unwrap().as_str();
+            let type_ = parse_term(iter.next().unwrap(), notations)?;
+            let value = parse_term(iter.next().unwrap(), notations)?;
+            let body = parse_term(iter.next().unwrap(), notations)?;
+
+            Ok(Builder::new(loc, Let(name, Box::new(type_), Box::new(value), Box::new(body))))
         },
 
         Rule::dProd => {
             let mut iter = pair.into_inner();
-            let body = parse_term(iter.next_back().unwrap())?;
+            let body = parse_term(iter.next_back().unwrap(), notations)?;
 
-            iter.flat_map(|pair| {
-                let mut pair = pair.into_inner();
-                let type_ = parse_term(pair.next_back().unwrap());
-
-                pair.map(move |var| (var.as_str(), type_.clone()))
-            })
-            .rev()
-            .try_fold(body, |acc, (var, type_)| type_.map(|type_| Builder::new(loc, Prod(var, Box::new(type_), Box::new(acc)))))
+            Ok(parse_binders(iter, notations)?.into_iter().fold(body, |acc, binder| prod(loc, binder, acc)))
         },
 
         Rule::Prod => {
             let mut iter = pair.into_inner();
-            let ret = parse_term(iter.next_back().unwrap())?;
+            let ret = parse_term(iter.next_back().unwrap(), notations)?;
 
-            iter.map(parse_term)
+            iter.map(|pair| parse_term(pair, notations))
                 .rev()
                 .try_fold(ret, |acc, argtype| argtype.map(|argtype| Builder::new(loc, Prod("_", Box::new(argtype), Box::new(acc)))))
         },
 
+        Rule::Notation => {
+            let mut items = pair.into_inner().peekable();
+
+            if items.len() == 1 {
+                return parse_term(items.next().unwrap(), notations);
+            }
+
+            parse_notation(&mut items, 0, notations)
+        },
+
         term => unreachable!("unexpected term: {term:?}"),
     }
 }
 
+/// Builds the term written by a sequence of operators and operands, by precedence climbing.
+///
+/// Only the operators of precedence at least `precedence` are consumed, the remaining ones being
+/// left to the caller.
+fn parse_notation<'build, 'arena: 'build>(
+    items: &mut Peekable<Pairs<'build, Rule>>,
+    precedence: usize,
+    notations: &[Notation<'arena>],
+) -> Result<term::Builder<'build>> {
+    let item = items.next().unwrap_or_else(|| unreachable!());
+
+    let mut lhs = if item.as_rule() == Rule::operator {
+        let notation = find_notation(&item, 1, notations)?;
+        let operand = parse_notation(items, notation.precedence, notations)?;
+
+        apply_notation(notation, convert_span(item.as_span()), vec![operand])
+    } else {
+        parse_term(item, notations)?
+    };
+
+    // the precedence of the last non-associative operator consumed, which cannot be chained
+    let mut non_associative = None;
+
+    while let Some(operator) = items.peek() {
+        let notation = find_notation(operator, 2, notations)?;
+
+        if notation.precedence < precedence {
+            break;
+        }
+
+        if non_associative == Some(notation.precedence) {
+            return Err(Error {
+                kind: Kind::NonAssociativeOperator(notation.symbol.to_owned()),
+                location: convert_span(operator.as_span()),
+            });
+        }
+
+        let loc = convert_span(items.next().unwrap_or_else(|| unreachable!()).as_span());
+        let next_precedence = if notation.fixity == Fixity::InfixRight { notation.precedence } else { notation.precedence + 1 };
+        let rhs = parse_notation(items, next_precedence, notations)?;
+
+        lhs = apply_notation(notation, loc, vec![lhs, rhs]);
+        non_associative = (notation.fixity == Fixity::Infix).then_some(notation.precedence);
+    }
+
+    Ok(lhs)
+}
+
+/// Returns the most recently declared notation with the symbol of `operator` and with `arity`
+/// operands.
+fn find_notation<'notations, 'arena>(
+    operator: &Pair<Rule>,
+    arity: usize,
+    notations: &'notations [Notation<'arena>],
+) -> Result<&'notations Notation<'arena>> {
+    notations
+        .iter()
+        .rev()
+        .find(|notation| notation.symbol == operator.as_str() && notation.arity() == arity)
+        .ok_or_else(|| Error {
+            kind: Kind::UnknownOperator(operator.as_str().to_owned()),
+            location: convert_span(operator.as_span()),
+        })
+}
+
+/// Returns the application of the constant `notation` stands for to its hidden arguments, which
+/// are holes, then to `operands`.
+///
+/// The constant and its hidden arguments are located at the operator, at `loc`.
+fn apply_notation<'build, 'arena: 'build>(
+    notation: &Notation<'arena>,
+    loc: Location,
+    operands: Vec<term::Builder<'build>>,
+) -> term::Builder<'build> {
+    use term::Builder;
+    use term::Payload::{App, Hole, Var};
+
+    let head = Builder::new(loc, Var(notation.name));
+    let head = (0..notation.hidden).fold(head, |acc, _| Builder::new(loc, App(Box::new(acc), Box::new(Builder::new(loc, Hole(None))))));
+
+    operands.into_iter().fold(head, |acc, operand| {
+        let loc = Location {
+            start: acc.location().start.min(operand.location().start),
+            end: operand.location().end,
+        };
+
+        Builder::new(loc, App(Box::new(acc), Box::new(operand)))
+    })
+}
+
+/// Builds [tactics](tactic::Builder) from errorless pest output
+fn parse_tactic<'build, 'arena: 'build>(pair: Pair<'build, Rule>, notations: &[Notation<'arena>]) -> Result<tactic::Builder<'build>> {
+    use tactic::Builder;
+    use tactic::Payload::{Apply, Assumption, Exact, Exfalso, Induction, Intro, Refine, Rewrite};
+
+    let loc = convert_span(pair.as_span());
+
+    match pair.as_rule() {
+        Rule::Intro => Ok(Builder::new(loc, Intro(pair.into_inner().map(|name| name.as_str()).collect()))),
+        Rule::Exact => Ok(Builder::new(loc, Exact(Box::new(parse_term(pair.into_inner().next().unwrap(), notations)?)))),
+        Rule::Apply => Ok(Builder::new(loc, Apply(Box::new(parse_term(pair.into_inner().next().unwrap(), notations)?)))),
+        Rule::Refine => Ok(Builder::new(loc, Refine(Box::new(parse_term(pair.into_inner().next().unwrap(), notations)?)))),
+        Rule::Rewrite => Ok(Builder::new(loc, Rewrite(false, Box::new(parse_term(pair.into_inner().next().unwrap(), notations)?)))),
+        Rule::RewriteRev => Ok(Builder::new(loc, Rewrite(true, Box::new(parse_term(pair.into_inner().next().unwrap(), notations)?)))),
+        Rule::Induction => Ok(Builder::new(loc, Induction(pair.into_inner().as_str()))),
+        Rule::Exfalso => Ok(Builder::new(loc, Exfalso)),
+        Rule::Assumption => Ok(Builder::new(loc, Assumption)),
+        rule => unreachable!("unexpected tactic: {rule:?}"),
+    }
+}
+
 /// Parses multiple left arguments.
-fn parse_args(pair: Pair<Rule>) -> Result<Vec<(&str, term::Builder)>> {
+fn parse_args<'build, 'arena: 'build>(
+    pair: Pair<'build, Rule>,
+    notations: &[Notation<'arena>],
+) -> Result<Vec<(&'build str, term::Builder<'build>)>> {
     pair.into_inner()
         .flat_map(|pair| {
             let mut pair = pair.into_inner();
-            let type_ = parse_term(pair.next_back().unwrap());
+            let type_ = parse_term(pair.next_back().unwrap(), notations);
 
             pair.map(move |var| Ok((var.as_str(), type_.clone()?)))
         })
@@ -164,25 +307,88 @@ fn parse_args(pair: Pair<Rule>) -> Result<Vec<(&str, term::Builder)>> {
         .collect()
 }
 
+/// Parses multiple left arguments, which may be implicit.
+///
+/// Each argument comes with a flag indicating whether it is implicit.
+fn parse_binders<'build, 'arena: 'build, I>(
+    pairs: I,
+    notations: &[Notation<'arena>],
+) -> Result<Vec<(&'build str, term::Builder<'build>, bool)>>
+where
+    I: DoubleEndedIterator<Item = Pair<'build, Rule>>,
+{
+    pairs
+        .flat_map(|pair| {
+            let implicit = pair.as_rule() == Rule::implicit_arg;
+            let mut pair = pair.into_inner();
+            let type_ = parse_term(pair.next_back().unwrap(), notations);
+
+            pair.map(move |var| Ok((var.as_str(), type_.clone()?, implicit)))
+        })
+        .rev()
+        .collect()
+}
+
+/// Parses a name, which may be followed by universe variables as in `name.{u, v}`.
+fn parse_decl_name<'build>(pair: Pair<'build, Rule>) -> ((Location, &'build str), Vec<&'build str>) {
+    if pair.as_rule() != Rule::stringDecl {
+        return ((convert_span(pair.as_span()), pair.as_str()), Vec::new());
+    }
+
+    let mut string_decl = pair.into_inner();
+    let s = string_decl.next().unwrap();
+    let vars = string_decl.next().unwrap().into_inner().map(|name| name.as_str()).collect();
+
+    ((convert_span(s.as_span()), s.as_str()), vars)
+}
+
+/// Returns the abstraction of `body` over the given left argument.
+fn abs<'build>(
+    loc: Location,
+    (var, type_, implicit): (&'build str, term::Builder<'build>, bool),
+    body: term::Builder<'build>,
+) -> term::Builder<'build> {
+    use term::Payload::{Abs, ImplicitAbs};
+
+    let payload =
+        if implicit { ImplicitAbs(var, Box::new(type_), Box::new(body)) } else { Abs(var, Box::new(type_), Box::new(body)) };
+
+    term::Builder::new(loc, payload)
+}
+
+/// Returns the dependent product of `body` over the given left argument.
+fn prod<'build>(
+    loc: Location,
+    (var, type_, implicit): (&'build str, term::Builder<'build>, bool),
+    body: term::Builder<'build>,
+) -> term::Builder<'build> {
+    use term::Payload::{ImplicitProd, Prod};
+
+    let payload =
+        if implicit { ImplicitProd(var, Box::new(type_), Box::new(body)) } else { Prod(var, Box::new(type_), Box::new(body)) };
+
+    term::Builder::new(loc, payload)
+}
+
 /// Builds a command from errorless pest output
-fn parse_expr(pair: Pair<Rule>) -> Result<Command> {
+fn parse_expr<'build, 'arena: 'build>(pair: Pair<'build, Rule>, notations: &[Notation<'arena>]) -> Result<Command<'build>> {
     use term::Builder;
-    use term::Payload::{Abs, Prod};
+    use term::Payload::Prod;
 
     let loc = convert_span(pair.as_span());
 
     match pair.as_rule() {
         Rule::GetType => {
             let mut iter = pair.into_inner();
-            let t = parse_term(iter.next().unwrap())?;
+            let t = parse_term(iter.next().unwrap(), notations)?;
 
             Ok(Command::GetType(t))
         },
 
         Rule::CheckType => {
             let mut iter = pair.into_inner();
-            let t1 = parse_term(iter.next().unwrap())?;
-            let t2 = parse_term(iter.next().unwrap())?;
+            let t1 = parse_term(iter.next().unwrap(), notations)?;
+            let t2 = parse_term(iter.next().unwrap(), notations)?;
 
             Ok(Command::CheckType(t1, t2))
         },
@@ -190,9 +396,9 @@ fn parse_expr(pair: Pair<Rule>) -> Result<Command> {
         Rule::Define => {
             let mut iter = pair.into_inner();
             let s = iter.next().unwrap();
-            let args = parse_args(iter.next().unwrap())?.into_iter();
-            let term = parse_term(iter.next_back().unwrap())?;
-            let term = args.fold(term, |acc, (var, type_)| Builder::new(loc, Abs(var, Box::new(type_), Box::new(acc))));
+            let args = parse_binders(iter.next().unwrap().into_inner(), notations)?.into_iter();
+            let term = parse_term(iter.next_back().unwrap(), notations)?;
+            let term = args.fold(term, |acc, binder| abs(loc, binder, acc));
 
             Ok(Command::Define((convert_span(s.as_span()), s.as_str()), None, term))
         },
@@ -200,13 +406,11 @@ fn parse_expr(pair: Pair<Rule>) -> Result<Command> {
         Rule::DefineCheckType => {
             let mut iter = pair.into_inner();
             let s = iter.next().unwrap();
-            let args = parse_args(iter.next().unwrap())?.into_iter();
-            let ty = parse_term(iter.next().unwrap())?;
-            let term = parse_term(iter.next().unwrap())?;
-            let ty = args
-                .clone()
-                .fold(ty, |acc, (var, type_)| Builder::new(loc, Prod(var, Box::new(type_), Box::new(acc))));
-            let term = args.fold(term, |acc, (var, type_)| Builder::new(loc, Abs(var, Box::new(type_), Box::new(acc))));
+            let args = parse_binders(iter.next().unwrap().into_inner(), notations)?.into_iter();
+            let ty = parse_term(iter.next().unwrap(), notations)?;
+            let term = parse_term(iter.next().unwrap(), notations)?;
+            let ty = args.clone().fold(ty, |acc, binder| prod(loc, binder, acc));
+            let term = args.fold(term, |acc, binder| abs(loc, binder, acc));
 
             Ok(Command::Define((convert_span(s.as_span()), s.as_str()), Some(ty), term))
         },
@@ -216,9 +420,9 @@ fn parse_expr(pair: Pair<Rule>) -> Result<Command> {
             let mut string_decl = iter.next().unwrap().into_inner();
             let s = string_decl.next().unwrap();
             let vars: Vec<&str> = string_decl.next().unwrap().into_inner().map(|name| name.as_str()).collect();
-            let args = parse_args(iter.next().unwrap())?.into_iter();
-            let decl = iter.next().map(parse_term).unwrap()?;
-            let decl = args.fold(decl, |acc, (var, type_)| Builder::new(loc, Abs(var, Box::new(type_), Box::new(acc))));
+            let args = parse_binders(iter.next().unwrap().into_inner(), notations)?.into_iter();
+            let decl = iter.next().map(|pair| parse_term(pair, notations)).unwrap()?;
+            let decl = args.fold(decl, |acc, binder| abs(loc, binder, acc));
 
             Ok(Command::Declaration(
                 (convert_span(s.as_span()), s.as_str()),
@@ -233,14 +437,12 @@ fn parse_expr(pair: Pair<Rule>) -> Result<Command> {
             let s = string_decl.next().unwrap();
             let vars: Vec<&str> = string_decl.next().unwrap().into_inner().map(|name| name.as_str()).collect();
 
-            let args = parse_args(iter.next().unwrap())?.into_iter();
-            let ty = parse_term(iter.next().unwrap())?;
-            let decl = iter.next().map(parse_term).unwrap()?;
+            let args = parse_binders(iter.next().unwrap().into_inner(), notations)?.into_iter();
+            let ty = parse_term(iter.next().unwrap(), notations)?;
+            let decl = iter.next().map(|pair| parse_term(pair, notations)).unwrap()?;
 
-            let ty = args
-                .clone()
-                .fold(ty, |acc, (var, type_)| Builder::new(loc, Prod(var, Box::new(type_), Box::new(acc))));
-            let decl = args.fold(decl, |acc, (var, type_)| Builder::new(loc, Abs(var, Box::new(type_), Box::new(acc))));
+            let ty = args.clone().fold(ty, |acc, binder| prod(loc, binder, acc));
+            let decl = args.fold(decl, |acc, binder| abs(loc, binder, acc));
 
             let ty = declaration::Builder::Decl(Box::new(ty), vars.clone());
             let decl = declaration::Builder::Decl(Box::new(decl), vars);
@@ -248,8 +450,36 @@ fn parse_expr(pair: Pair<Rule>) -> Result<Command> {
             Ok(Command::Declaration((convert_span(s.as_span()), s.as_str()), Some(ty), decl))
         },
 
+        Rule::Axiom => {
+            let mut iter = pair.into_inner();
+            let (name, vars) = parse_decl_name(iter.next().unwrap());
+
+            let args = parse_binders(iter.next().unwrap().into_inner(), notations)?.into_iter();
+            let ty = parse_term(iter.next().unwrap(), notations)?;
+            let ty = args.fold(ty, |acc, binder| prod(loc, binder, acc));
+
+            Ok(Command::Axiom(name, declaration::Builder::Decl(Box::new(ty), vars)))
+        },
+
+        Rule::TheoremDecl => {
+            let mut iter = pair.into_inner();
+            let (name, vars) = parse_decl_name(iter.next().unwrap());
+
+            let args = parse_binders(iter.next().unwrap().into_inner(), notations)?.into_iter();
+            let ty = parse_term(iter.next().unwrap(), notations)?;
+            let proof = parse_term(iter.next().unwrap(), notations)?;
+
+            let ty = args.clone().fold(ty, |acc, binder| prod(loc, binder, acc));
+            let proof = args.fold(proof, |acc, binder| abs(loc, binder, acc));
+
+            let ty = declaration::Builder::Decl(Box::new(ty), vars.clone());
+            let proof = declaration::Builder::Decl(Box::new(proof), vars);
+
+            Ok(Command::Theorem(name, ty, proof))
+        },
+
         Rule::Eval => {
-            let term = parse_term(pair.into_inner().next().unwrap())?;
+            let term = parse_term(pair.into_inner().next().unwrap(), notations)?;
 
             Ok(Command::Eval(term))
         },
@@ -260,36 +490,294 @@ fn parse_expr(pair: Pair<Rule>) -> Result<Command> {
             Ok(Command::Import(files))
         },
 
+        Rule::SearchType => {
+            let pattern = parse_term(pair.into_inner().next().unwrap(), notations)?;
+
+            Ok(Command::SearchType(pattern))
+        },
+
         Rule::Search => {
             let s = pair.into_inner().next().unwrap().as_str();
 
             Ok(Command::Search(s))
         },
 
+        Rule::PrintAxioms => {
+            let s = pair.into_inner().next().unwrap();
+
+            Ok(Command::PrintAxioms((convert_span(s.as_span()), s.as_str())))
+        },
+
+        Rule::PrintDeps => {
+            let s = pair.into_inner().next().unwrap();
+
+            Ok(Command::PrintDeps((convert_span(s.as_span()), s.as_str())))
+        },
+
+        Rule::Namespace => {
+            let s = pair.into_inner().next().unwrap();
+
+            Ok(Command::Namespace((convert_span(s.as_span()), s.as_str())))
+        },
+
+        Rule::Section => {
+            let s = pair.into_inner().next().map(|pair| pair.as_str());
+
+            Ok(Command::Section(s))
+        },
+
+        Rule::Variable => {
+            let mut variables = Vec::new();
+
+            for pair in pair.into_inner() {
+                let implicit = pair.as_rule() == Rule::implicit_arg;
+                let mut pair = pair.into_inner();
+                let type_ = parse_term(pair.next_back().unwrap(), notations)?;
+
+                variables.extend(pair.map(|var| ((convert_span(var.as_span()), var.as_str()), type_.clone(), implicit)));
+            }
+
+            Ok(Command::Variable(variables))
+        },
+
+        Rule::End => {
+            let s = pair.into_inner().next().map(|pair| pair.as_str());
+
+            Ok(Command::End(loc, s))
+        },
+
+        Rule::Open => {
+            let namespaces = pair.into_inner().map(|pair| (convert_span(pair.as_span()), pair.as_str())).collect();
+
+            Ok(Command::Open(namespaces))
+        },
+
+        Rule::Inductive => {
+            let mut iter = pair.into_inner();
+            let name = iter.next().unwrap();
+
+            let (s, vars) = match name.as_rule() {
+                Rule::stringDecl => {
+                    let mut string_decl = name.into_inner();
+                    let s = string_decl.next().unwrap();
+                    let vars = string_decl.next().unwrap().into_inner().map(|name| name.as_str()).collect();
+
+                    (s, vars)
+                },
+                _ => (name, Vec::new()),
+            };
+
+            let params = parse_args(iter.next().unwrap(), notations)?;
+            let arity = parse_term(iter.next().unwrap(), notations)?;
+            let arity = params
+                .iter()
+                .cloned()
+                .fold(arity, |acc, (var, type_)| Builder::new(loc, Prod(var, Box::new(type_), Box::new(acc))));
+
+            let constructors = iter
+                .map(|constructor| {
+                    let constructor_loc = convert_span(constructor.as_span());
+                    let mut iter = constructor.into_inner();
+                    let name = iter.next().unwrap();
+                    let args = parse_args(iter.next().unwrap(), notations)?.into_iter();
+                    let type_ = parse_term(iter.next().unwrap(), notations)?;
+
+                    let type_ = args
+                        .chain(params.iter().cloned())
+                        .fold(type_, |acc, (var, type_)| Builder::new(constructor_loc, Prod(var, Box::new(type_), Box::new(acc))));
+
+                    Ok(((convert_span(name.as_span()), name.as_str()), type_))
+                })
+                .collect::<Result<_>>()?;
+
+            Ok(Command::Inductive(inductive::Builder {
+                name: (convert_span(s.as_span()), s.as_str()),
+                vars,
+                params: params.len(),
+                arity: Box::new(arity),
+                constructors,
+            }))
+        },
+
+        Rule::Structure => {
+            let mut iter = pair.into_inner();
+            let name = iter.next().unwrap();
+
+            let (s, vars) = match name.as_rule() {
+                Rule::stringDecl => {
+                    let mut string_decl = name.into_inner();
+                    let s = string_decl.next().unwrap();
+                    let vars = string_decl.next().unwrap().into_inner().map(|name| name.as_str()).collect();
+
+                    (s, vars)
+                },
+                _ => (name, Vec::new()),
+            };
+
+            let mut params = parse_args(iter.next().unwrap(), notations)?;
+            params.reverse();
+
+            let mut iter = iter.peekable();
+            let sort = match iter.peek() {
+                Some(pair) if pair.as_rule() != Rule::Field => Some(Box::new(parse_term(iter.next().unwrap(), notations)?)),
+                _ => None,
+            };
+
+            let fields = iter
+                .map(|field| {
+                    let mut iter = field.into_inner();
+                    let name = iter.next().unwrap();
+                    let type_ = parse_term(iter.next().unwrap(), notations)?;
+
+                    Ok(((convert_span(name.as_span()), name.as_str()), type_))
+                })
+                .collect::<Result<_>>()?;
+
+            Ok(Command::Structure(structure::Builder {
+                name: (convert_span(s.as_span()), s.as_str()),
+                vars,
+                params,
+                sort,
+                fields,
+            }))
+        },
+
+        Rule::NotationDecl => {
+            let mut iter = pair.into_inner();
+
+            let fixity = match iter.next().unwrap().as_str() {
+                "infix" => Fixity::Infix,
+                "infixl" => Fixity::InfixLeft,
+                "infixr" => Fixity::InfixRight,
+                _ => Fixity::Prefix,
+            };
+
+            let symbol = iter.next().unwrap().as_str();
+            let precedence = iter.next().unwrap();
+            let precedence = precedence.as_str().parse().map_err(|err: core::num::ParseIntError| Error {
+                location: convert_span(precedence.as_span()),
+                kind: Kind::TransformError(err.to_string()),
+            })?;
+
+            let name = iter.next().unwrap();
+
+            Ok(Command::Notation {
+                fixity,
+                symbol,
+                precedence,
+                name: (convert_span(name.as_span()), name.as_str()),
+                hidden: iter.count(),
+            })
+        },
+
         cmd => unreachable!("Unexpected command: {:?}", cmd),
     }
 }
 
-/// Parse a text input and try to convert it into a command.
+/// Parse a text input and try to convert it into a command, where operators are those of the
+/// given notations.
 ///
 /// # Errors
 /// If unsuccessful, the first error that was encountered is returned.
 #[inline]
-pub fn line(line: &str) -> Result<Command> {
+pub fn line<'build, 'arena: 'build>(line: &'build str, notations: &[Notation<'arena>]) -> Result<Command<'build>> {
     CommandParser::parse(Rule::command, line)
         .map_err(std::convert::Into::into)
-        .and_then(|mut pairs| parse_expr(pairs.next().unwrap_or_else(|| unreachable!())))
+        .and_then(|mut pairs| parse_expr(pairs.next().unwrap_or_else(|| unreachable!()), notations))
+}
+
+/// The commands of a file, which are converted one at a time, so that each command may use the
+/// notations declared by the previous ones.
+pub struct Commands<'build>(Pairs<'build, Rule>);
+
+impl<'build> Commands<'build> {
+    /// Converts the next command of the file, where operators are those of the given notations.
+    ///
+    /// # Errors
+    /// If unsuccessful, the error that was encountered is returned.
+    #[inline]
+    pub fn next<'arena: 'build>(&mut self, notations: &[Notation<'arena>]) -> Option<Result<Command<'build>>> {
+        self.0.next().map(|pair| match pair.as_rule() {
+            Rule::Invalid => Err(parse_invalid(&pair)),
+            _ => parse_expr(pair, notations),
+        })
+    }
+}
+
+/// Returns the error raised by the input skipped in `pair` while recovering from errors, located in
+/// the whole input.
+fn parse_invalid(pair: &Pair<Rule>) -> Error {
+    let (line, column) = pair.as_span().start_pos().line_col();
+
+    let shift = |position: Position| {
+        if position.line == 1 {
+            Position::new(line, column + position.column - 1)
+        } else {
+            Position::new(line + position.line - 1, position.column)
+        }
+    };
+
+    // no command can be parsed from the start of the skipped input, which ends before the next
+    // command, so that parsing it on its own fails the same way
+    let Err(err) = CommandParser::parse(Rule::command, pair.as_str()) else {
+        unreachable!()
+    };
+
+    let mut err = Error::from(err);
+    err.location = Location {
+        start: shift(err.location.start),
+        end: shift(err.location.end),
+    };
+
+    err
+}
+
+/// Parse a text input into the [commands](Commands) it consists of.
+///
+/// # Errors
+/// If the syntax of the text is incorrect, the first error that was encountered is returned.
+#[inline]
+pub fn file(file: &str) -> Result<Commands<'_>> {
+    CommandParser::parse(Rule::file, file).map(Commands).map_err(std::convert::Into::into)
+}
+
+/// Parse a text input into the [commands](Commands) it consists of, recovering from syntax errors.
+///
+/// The input which cannot be parsed is skipped until the next keyword starting a command (such as
+/// `def`, `check`, `eval` or `import`), and yields an error in place of a command.
+#[inline]
+#[must_use]
+pub fn file_recovering(file: &str) -> Commands<'_> {
+    CommandParser::parse(Rule::file_recovering, file).map_or_else(|_| unreachable!(), Commands)
+}
+
+/// Parse a text input of the form `theorem name : statement := by`, which starts an interactive
+/// proof, and returns the name and the statement of the theorem.
+///
+/// # Errors
+/// If unsuccessful, the first error that was encountered is returned.
+#[inline]
+pub fn theorem<'build, 'arena: 'build>(
+    line: &'build str,
+    notations: &[Notation<'arena>],
+) -> Result<((Location, &'build str), term::Builder<'build>)> {
+    let mut pairs = CommandParser::parse(Rule::theorem, line)?.next().unwrap_or_else(|| unreachable!()).into_inner();
+
+    let name = pairs.next().unwrap_or_else(|| unreachable!());
+    let statement = parse_term(pairs.next().unwrap_or_else(|| unreachable!()), notations)?;
+
+    Ok(((convert_span(name.as_span()), name.as_str()), statement))
 }
 
-/// Parse a text input and try to convert it into a vector of commands.
+/// Parse a text input and try to convert it into a tactic, as a step of an interactive proof.
 ///
 /// # Errors
 /// If unsuccessful, the first error that was encountered is returned.
 #[inline]
-pub fn file(file: &str) -> Result<Vec<Command>> {
-    CommandParser::parse(Rule::file, file)
+pub fn tactic<'build, 'arena: 'build>(line: &'build str, notations: &[Notation<'arena>]) -> Result<tactic::Builder<'build>> {
+    CommandParser::parse(Rule::proof_step, line)
         .map_err(std::convert::Into::into)
-        .and_then(|pairs| pairs.into_iter().map(parse_expr).collect())
+        .and_then(|mut pairs| parse_tactic(pairs.next().unwrap_or_else(|| unreachable!()), notations))
 }
 
 #[cfg(test)]
@@ -301,16 +789,18 @@ mod tests {
     use super::*;
 
     /// Error messages
-    const COMMAND_ERR: &str = "expected def var := term, def var : term := term, def decl.{ vars, ... } := term, def decl.{ vars, ... } : term := term, check term : term, check term, eval term, import path_to_file, or search var";
-    const SIMPLE_TERM_ERR: &str = "expected variable, abstraction, Prop, Type, Sort, or universe argument";
-    const TERM_ERR: &str = "expected variable, abstraction, dependent product, application, product, Prop, Type, or Sort";
+    const COMMAND_ERR: &str = "expected def var := term, def var : term := term, def decl.{ vars, ... } := term, def decl.{ vars, ... } : term := term, axiom var : term, theorem var : term := term, check term : term, check term, eval term, import path_to_file, search : term, search pattern, print axioms var, print deps var, inductive var : term := | constructors, structure var where fields, infix \"symbol\" precedence := var, namespace var, section var, variable (var : term), end var, or open var";
+    const SIMPLE_TERM_ERR: &str =
+        "expected variable, abstraction, local definition, operator, Prop, Type, Sort, explicit variable, natural number, hole, or universe argument";
+    const TERM_ERR: &str = "expected variable, abstraction, local definition, dependent product, application, product, notation, tactic block, Prop, Type, Sort, explicit variable, natural number, or hole";
     const TOO_LARGE_NUMBER: &str = "number too large to fit in target type";
-    const UNIVERSE_ERR: &str = "expected number, variable, abstraction, Prop, Type, Sort, plus, max, or imax";
+    const UNIVERSE_ERR: &str =
+        "expected number, variable, abstraction, local definition, operator, Prop, Type, Sort, plus, max, imax, explicit variable, natural number, or hole";
 
     #[test]
     fn failure_universe_level() {
         assert_eq!(
-            line("check fun x : Prop -> Type"),
+            line("check fun x : Prop -> Type", &[]),
             Err(Error {
                 kind: Kind::UnexpectedToken(UNIVERSE_ERR.to_owned()),
                 location: Location::new((1, 27), (1, 28)),
@@ -321,7 +811,7 @@ mod tests {
     #[test]
     fn successful_define_with_type_annotation() {
         assert_eq!(
-            line("def x : Type := Prop"),
+            line("def x : Type := Prop", &[]),
             Ok(Define(
                 (Location::new((1, 5), (1, 6)), "x"),
                 Some(Builder::new(Location::new((1, 9), (1, 14)), Type(Box::new(level::Builder::Const(0))))),
@@ -333,7 +823,7 @@ mod tests {
     #[test]
     fn successful_declare_with_type_annotation() {
         assert_eq!(
-            line("def x.{u} : Type u := foo.{u}"),
+            line("def x.{u} : Type u := foo.{u}", &[]),
             Ok(Declaration(
                 (Location::new((1, 5), (1, 6)), "x"),
                 Some(declaration::Builder::Decl(
@@ -348,7 +838,7 @@ mod tests {
         );
 
         assert_eq!(
-            line("def x := y.{max 1 2}"),
+            line("def x := y.{max 1 2}", &[]),
             Ok(Define(
                 (Location::new((1, 5), (1, 6)), "x"),
                 None,
@@ -365,28 +855,255 @@ mod tests {
 
     #[test]
     fn successful_import() {
-        assert_eq!(line("import "), Ok(Import(vec![])));
+        assert_eq!(line("import ", &[]), Ok(Import(vec![])));
 
         assert_eq!(
-            line("import file1 dir/file2"),
+            line("import file1 dir/file2", &[]),
             Ok(Import(vec![(Location::new((1, 8), (1, 13)), "file1"), (Location::new((1, 14), (1, 23)), "dir/file2")]))
         );
     }
 
+    #[test]
+    fn successful_inductive() {
+        let sort = |start, end| Builder::new(Location::new((1, start), (1, end)), Sort(Box::new(level::Builder::Var("u"))));
+
+        assert_eq!(
+            line("inductive I.{u} (A : Sort u) : Sort u := | c : A -> I A", &[]),
+            Ok(Inductive(inductive::Builder {
+                name: (Location::new((1, 11), (1, 12)), "I"),
+                vars: vec!["u"],
+                params: 1,
+                arity: Box::new(Builder::new(
+                    Location::new((1, 1), (1, 56)),
+                    Prod("A", Box::new(sort(22, 28)), Box::new(sort(32, 38)))
+                )),
+                constructors: vec![(
+                    (Location::new((1, 44), (1, 45)), "c"),
+                    Builder::new(
+                        Location::new((1, 42), (1, 56)),
+                        Prod(
+                            "A",
+                            Box::new(sort(22, 28)),
+                            Box::new(Builder::new(
+                                Location::new((1, 48), (1, 56)),
+                                Prod(
+                                    "_",
+                                    Box::new(Builder::new(Location::new((1, 48), (1, 49)), Var("A"))),
+                                    Box::new(Builder::new(
+                                        Location::new((1, 53), (1, 56)),
+                                        App(
+                                            Box::new(Builder::new(Location::new((1, 53), (1, 54)), Var("I"))),
+                                            Box::new(Builder::new(Location::new((1, 55), (1, 56)), Var("A")))
+                                        )
+                                    ))
+                                )
+                            ))
+                        )
+                    )
+                )],
+            }))
+        );
+
+        assert_eq!(
+            line("inductive False : Prop :=", &[]),
+            Ok(Inductive(inductive::Builder {
+                name: (Location::new((1, 11), (1, 16)), "False"),
+                vars: vec![],
+                params: 0,
+                arity: Box::new(Builder::new(Location::new((1, 19), (1, 23)), Prop)),
+                constructors: vec![],
+            }))
+        );
+    }
+
+    #[test]
+    fn successful_structure() {
+        assert_eq!(
+            line("structure Pair (A : Type) where fst : A; snd : Pair.mk", &[]),
+            Ok(Structure(structure::Builder {
+                name: (Location::new((1, 11), (1, 15)), "Pair"),
+                vars: vec![],
+                params: vec![("A", Builder::new(Location::new((1, 21), (1, 25)), Type(Box::new(level::Builder::Const(0)))))],
+                sort: None,
+                fields: vec![
+                    ((Location::new((1, 33), (1, 36)), "fst"), Builder::new(Location::new((1, 39), (1, 40)), Var("A"))),
+                    ((Location::new((1, 42), (1, 45)), "snd"), Builder::new(Location::new((1, 48), (1, 55)), Var("Pair.mk"))),
+                ],
+            }))
+        );
+
+        assert_eq!(
+            line("structure True.{u} : Prop where", &[]),
+            Ok(Structure(structure::Builder {
+                name: (Location::new((1, 11), (1, 15)), "True"),
+                vars: vec!["u"],
+                params: vec![],
+                sort: Some(Box::new(Builder::new(Location::new((1, 22), (1, 26)), Prop))),
+                fields: vec![],
+            }))
+        );
+    }
+
+    #[test]
+    fn successful_implicit_binders() {
+        let prop = Builder::new(Location::new((1, 12), (1, 16)), Prop);
+        let var_a = |start, end| Box::new(Builder::new(Location::new((1, start), (1, end)), Var("A")));
+
+        assert_eq!(
+            line("def f {A : Prop} (x : A) : A := @g x", &[]),
+            Ok(Define(
+                (Location::new((1, 5), (1, 6)), "f"),
+                Some(Builder::new(
+                    Location::new((1, 1), (1, 37)),
+                    ImplicitProd(
+                        "A",
+                        Box::new(prop.clone()),
+                        Box::new(Builder::new(Location::new((1, 1), (1, 37)), Prod("x", var_a(23, 24), var_a(28, 29))))
+                    )
+                )),
+                Builder::new(
+                    Location::new((1, 1), (1, 37)),
+                    ImplicitAbs(
+                        "A",
+                        Box::new(prop),
+                        Box::new(Builder::new(
+                            Location::new((1, 1), (1, 37)),
+                            Abs(
+                                "x",
+                                var_a(23, 24),
+                                Box::new(Builder::new(
+                                    Location::new((1, 33), (1, 37)),
+                                    App(
+                                        Box::new(Builder::new(
+                                            Location::new((1, 33), (1, 35)),
+                                            Explicit(Box::new(Builder::new(Location::new((1, 34), (1, 35)), Var("g"))))
+                                        )),
+                                        Box::new(Builder::new(Location::new((1, 36), (1, 37)), Var("x")))
+                                    )
+                                ))
+                            )
+                        ))
+                    )
+                )
+            ))
+        );
+
+        assert_eq!(
+            line("check fun {_ : Prop} => Prop", &[]),
+            Ok(GetType(Builder::new(
+                Location::new((1, 7), (1, 29)),
+                ImplicitAbs(
+                    "_",
+                    Box::new(Builder::new(Location::new((1, 16), (1, 20)), Prop)),
+                    Box::new(Builder::new(Location::new((1, 25), (1, 29)), Prop))
+                )
+            )))
+        );
+    }
+
     #[test]
     fn successful_search() {
-        assert_eq!(line("search variable1"), Ok(Search("variable1")));
+        assert_eq!(line("search variable1", &[]), Ok(Search("variable1")));
+        assert_eq!(line("search Classical.*_or?", &[]), Ok(Search("Classical.*_or?")));
+        assert_eq!(
+            line("search : _ -> Prop", &[]),
+            Ok(SearchType(Builder::new(
+                Location::new((1, 10), (1, 19)),
+                Prod(
+                    "_",
+                    Box::new(Builder::new(Location::new((1, 10), (1, 11)), Hole(None))),
+                    Box::new(Builder::new(Location::new((1, 15), (1, 19)), Prop))
+                )
+            )))
+        );
+    }
+
+    #[test]
+    fn successful_axiom() {
+        assert_eq!(
+            line("axiom em (P : Prop) : P", &[]).map(|command| command.to_string()),
+            line("axiom em : (P : Prop) -> P", &[]).map(|command| command.to_string())
+        );
+        assert!(matches!(
+            line("axiom em : Prop", &[]),
+            Ok(Axiom((_, "em"), declaration::Builder::Decl(_, ref vars))) if vars.is_empty()
+        ));
+        assert!(matches!(
+            line("axiom choice.{u} : (A : Sort u) -> A", &[]),
+            Ok(Axiom((_, "choice"), declaration::Builder::Decl(_, ref vars))) if vars == &["u"]
+        ));
+        assert!(line("axiom em := Prop", &[]).is_err());
+    }
+
+    #[test]
+    fn successful_theorem_declaration() {
+        assert_eq!(
+            line("theorem foo (P : Prop) (h : P) : P := h", &[]).map(|command| command.to_string()),
+            line("theorem foo : (P : Prop) -> (h : P) -> P := fun (P : Prop) (h : P) => h", &[]).map(|command| command.to_string())
+        );
+        assert!(matches!(
+            line("theorem id.{u} : (A : Sort u) -> A -> A := fun (A : Sort u) (x : A) => x", &[]),
+            Ok(Theorem((_, "id"), _, declaration::Builder::Decl(_, ref vars))) if vars == &["u"]
+        ));
+        assert!(matches!(line("theorem foo : Prop := by exact Prop", &[]), Ok(Theorem((_, "foo"), ..))));
+        assert!(line("theorem foo := Prop", &[]).is_err());
+    }
+
+    #[test]
+    fn successful_print() {
+        assert_eq!(line("print axioms foo", &[]), Ok(PrintAxioms((Location::new((1, 14), (1, 17)), "foo"))));
+        assert_eq!(line("print deps Foo.bar", &[]), Ok(PrintDeps((Location::new((1, 12), (1, 19)), "Foo.bar"))));
+        assert!(line("print foo", &[]).is_err());
     }
 
     #[test]
     fn successful_eval() {
-        assert_eq!(line("eval Prop"), Ok(Eval(Builder::new(Location::new((1, 6), (1, 10)), Prop))));
+        assert_eq!(line("eval Prop", &[]), Ok(Eval(Builder::new(Location::new((1, 6), (1, 10)), Prop))));
+    }
+
+    #[test]
+    fn successful_nat_lit() {
+        assert_eq!(
+            line("eval f 0 123456789012345678901234567890", &[]),
+            Ok(Eval(Builder::new(
+                Location::new((1, 6), (1, 40)),
+                App(
+                    Box::new(Builder::new(
+                        Location::new((1, 6), (1, 40)),
+                        App(
+                            Box::new(Builder::new(Location::new((1, 6), (1, 7)), Var("f"))),
+                            Box::new(Builder::new(Location::new((1, 8), (1, 9)), NatLit(BigUint::from(0_u32))))
+                        )
+                    )),
+                    Box::new(Builder::new(
+                        Location::new((1, 10), (1, 40)),
+                        NatLit("123456789012345678901234567890".parse().unwrap())
+                    ))
+                )
+            )))
+        );
+    }
+
+    #[test]
+    fn successful_let() {
+        assert_eq!(
+            line("eval let x : Type := Prop; x", &[]),
+            Ok(Eval(Builder::new(
+                Location::new((1, 6), (1, 29)),
+                Let(
+                    "x",
+                    Box::new(Builder::new(Location::new((1, 14), (1, 19)), Type(Box::new(level::Builder::Const(0))))),
+                    Box::new(Builder::new(Location::new((1, 22), (1, 26)), Prop)),
+                    Box::new(Builder::new(Location::new((1, 28), (1, 29)), Var("x")))
+                )
+            )))
+        );
     }
 
     #[test]
     fn successful_define() {
         assert_eq!(
-            line("def x := Prop"),
+            line("def x := Prop", &[]),
             Ok(Define((Location::new((1, 5), (1, 6)), "x"), None, Builder::new(Location::new((1, 10), (1, 14)), Prop)))
         );
     }
@@ -394,7 +1111,7 @@ mod tests {
     #[test]
     fn successful_define_with_l_arg() {
         assert_eq!(
-            line("def x (A B: Prop) (C : Prop) := A"),
+            line("def x (A B: Prop) (C : Prop) := A", &[]),
             Ok(Define(
                 (Location::new((1, 5), (1, 6)), "x"),
                 None,
@@ -427,7 +1144,7 @@ mod tests {
     #[test]
     fn successful_declare() {
         assert_eq!(
-            line("def x.{} := Prop"),
+            line("def x.{} := Prop", &[]),
             Ok(Declaration(
                 (Location::new((1, 5), (1, 6)), "x"),
                 None,
@@ -436,7 +1153,7 @@ mod tests {
         );
 
         assert_eq!(
-            line("def x.{u, v} := Prop"),
+            line("def x.{u, v} := Prop", &[]),
             Ok(Declaration(
                 (Location::new((1, 5), (1, 6)), "x"),
                 None,
@@ -448,7 +1165,7 @@ mod tests {
     #[test]
     fn successful_checktype() {
         assert_eq!(
-            line("check Prop : Type"),
+            line("check Prop : Type", &[]),
             Ok(CheckType(
                 Builder::new(Location::new((1, 7), (1, 11)), Prop),
                 Builder::new(Location::new((1, 14), (1, 18)), Type(Box::new(level::Builder::Const(0))))
@@ -458,13 +1175,13 @@ mod tests {
 
     #[test]
     fn successful_gettype_prop() {
-        assert_eq!(line("check Prop"), Ok(GetType(Builder::new(Location::new((1, 7), (1, 11)), Prop))));
+        assert_eq!(line("check Prop", &[]), Ok(GetType(Builder::new(Location::new((1, 7), (1, 11)), Prop))));
     }
 
     #[test]
     fn successful_gettype_sort() {
         assert_eq!(
-            line("check Sort"),
+            line("check Sort", &[]),
             Ok(GetType(Builder::new(Location::new((1, 7), (1, 11)), Sort(Box::new(level::Builder::Const(0))))))
         );
     }
@@ -472,7 +1189,7 @@ mod tests {
     #[test]
     fn successful_var() {
         assert_eq!(
-            line("check fun A: Prop => A"),
+            line("check fun A: Prop => A", &[]),
             Ok(GetType(Builder::new(
                 Location::new((1, 7), (1, 23)),
                 Abs(
@@ -487,17 +1204,17 @@ mod tests {
     #[test]
     fn successful_type() {
         assert_eq!(
-            line("check Type"),
+            line("check Type", &[]),
             Ok(GetType(Builder::new(Location::new((1, 7), (1, 11)), Type(Box::new(level::Builder::Const(0))))))
         );
 
         assert_eq!(
-            line("check Type 0"),
+            line("check Type 0", &[]),
             Ok(GetType(Builder::new(Location::new((1, 7), (1, 13)), Type(Box::new(level::Builder::Const(0))))))
         );
 
         assert_eq!(
-            line("check Type 1"),
+            line("check Type 1", &[]),
             Ok(GetType(Builder::new(Location::new((1, 7), (1, 13)), Type(Box::new(level::Builder::Const(1))))))
         );
     }
@@ -505,22 +1222,22 @@ mod tests {
     #[test]
     fn successful_sort() {
         assert_eq!(
-            line("check Sort"),
+            line("check Sort", &[]),
             Ok(GetType(Builder::new(Location::new((1, 7), (1, 11)), Sort(Box::new(level::Builder::Const(0))))))
         );
 
         assert_eq!(
-            line("check Sort 0"),
+            line("check Sort 0", &[]),
             Ok(GetType(Builder::new(Location::new((1, 7), (1, 13)), Sort(Box::new(level::Builder::Const(0))))))
         );
 
         assert_eq!(
-            line("check Sort 1"),
+            line("check Sort 1", &[]),
             Ok(GetType(Builder::new(Location::new((1, 7), (1, 13)), Sort(Box::new(level::Builder::Const(1))))))
         );
 
         assert_eq!(
-            line("check Sort (0 + 1)"),
+            line("check Sort (0 + 1)", &[]),
             Ok(GetType(Builder::new(
                 Location::new((1, 7), (1, 19)),
                 Sort(Box::new(level::Builder::Plus(Box::new(level::Builder::Const(0)), 1)))
@@ -528,7 +1245,7 @@ mod tests {
         );
 
         assert_eq!(
-            line("check Sort (0 + 1 + 2)"),
+            line("check Sort (0 + 1 + 2)", &[]),
             Ok(GetType(Builder::new(
                 Location::new((1, 7), (1, 23)),
                 Sort(Box::new(level::Builder::Plus(Box::new(level::Builder::Plus(Box::new(level::Builder::Const(0)), 1)), 2)))
@@ -536,7 +1253,7 @@ mod tests {
         );
 
         assert_eq!(
-            line("check Sort max 0 0"),
+            line("check Sort max 0 0", &[]),
             Ok(GetType(Builder::new(
                 Location::new((1, 7), (1, 19)),
                 Sort(Box::new(level::Builder::Max(Box::new(level::Builder::Const(0)), Box::new(level::Builder::Const(0)))))
@@ -544,7 +1261,7 @@ mod tests {
         );
 
         assert_eq!(
-            line("check Sort imax 0 0"),
+            line("check Sort imax 0 0", &[]),
             Ok(GetType(Builder::new(
                 Location::new((1, 7), (1, 20)),
                 Sort(Box::new(level::Builder::IMax(Box::new(level::Builder::Const(0)), Box::new(level::Builder::Const(0)))))
@@ -555,7 +1272,7 @@ mod tests {
     #[test]
     fn cannot_transform_sort_level() {
         assert_eq!(
-            line("check Sort 10000000000000000000000"),
+            line("check Sort 10000000000000000000000", &[]),
             Err(Error {
                 kind: Kind::TransformError(TOO_LARGE_NUMBER.to_owned()),
                 location: Location::new((1, 12), (1, 35)),
@@ -563,7 +1280,7 @@ mod tests {
         );
 
         assert_eq!(
-            line("check Sort (10000000000000000000000 + 0)"),
+            line("check Sort (10000000000000000000000 + 0)", &[]),
             Err(Error {
                 kind: Kind::TransformError(TOO_LARGE_NUMBER.to_owned()),
                 location: Location::new((1, 13), (1, 36)),
@@ -571,7 +1288,7 @@ mod tests {
         );
 
         assert_eq!(
-            line("check Sort (0 + 10000000000000000000000)"),
+            line("check Sort (0 + 10000000000000000000000)", &[]),
             Err(Error {
                 kind: Kind::TransformError(TOO_LARGE_NUMBER.to_owned()),
                 location: Location::new((1, 17), (1, 40)),
@@ -579,7 +1296,7 @@ mod tests {
         );
 
         assert_eq!(
-            line("check Sort max 0 10000000000000000000000"),
+            line("check Sort max 0 10000000000000000000000", &[]),
             Err(Error {
                 kind: Kind::TransformError(TOO_LARGE_NUMBER.to_owned()),
                 location: Location::new((1, 18), (1, 41)),
@@ -587,7 +1304,7 @@ mod tests {
         );
 
         assert_eq!(
-            line("check Sort max 10000000000000000000000 0"),
+            line("check Sort max 10000000000000000000000 0", &[]),
             Err(Error {
                 kind: Kind::TransformError(TOO_LARGE_NUMBER.to_owned()),
                 location: Location::new((1, 16), (1, 39)),
@@ -595,7 +1312,7 @@ mod tests {
         );
 
         assert_eq!(
-            line("check Sort imax 0 10000000000000000000000"),
+            line("check Sort imax 0 10000000000000000000000", &[]),
             Err(Error {
                 kind: Kind::TransformError(TOO_LARGE_NUMBER.to_owned()),
                 location: Location::new((1, 19), (1, 42)),
@@ -603,7 +1320,7 @@ mod tests {
         );
 
         assert_eq!(
-            line("check Sort imax 10000000000000000000000 0"),
+            line("check Sort imax 10000000000000000000000 0", &[]),
             Err(Error {
                 kind: Kind::TransformError(TOO_LARGE_NUMBER.to_owned()),
                 location: Location::new((1, 17), (1, 40)),
@@ -615,7 +1332,7 @@ mod tests {
     fn cannot_transform_sort_term() {
         // VarDecl
         assert_eq!(
-            line("check foo.{10000000000000000000000}"),
+            line("check foo.{10000000000000000000000}", &[]),
             Err(Error {
                 kind: Kind::TransformError(TOO_LARGE_NUMBER.to_owned()),
                 location: Location::new((1, 12), (1, 35)),
@@ -624,7 +1341,7 @@ mod tests {
 
         // Type
         assert_eq!(
-            line("check (x: Type 10000000000000000000000) -> x"),
+            line("check (x: Type 10000000000000000000000) -> x", &[]),
             Err(Error {
                 kind: Kind::TransformError(TOO_LARGE_NUMBER.to_owned()),
                 location: Location::new((1, 16), (1, 39)),
@@ -633,7 +1350,7 @@ mod tests {
 
         // App
         assert_eq!(
-            line("check ((x: Type 10000000000000000000000) -> x) A"),
+            line("check ((x: Type 10000000000000000000000) -> x) A", &[]),
             Err(Error {
                 kind: Kind::TransformError(TOO_LARGE_NUMBER.to_owned()),
                 location: Location::new((1, 17), (1, 40)),
@@ -642,7 +1359,7 @@ mod tests {
 
         // Abs
         assert_eq!(
-            line("check fun x: Prop => (x: Type 10000000000000000000000) -> x"),
+            line("check fun x: Prop => (x: Type 10000000000000000000000) -> x", &[]),
             Err(Error {
                 kind: Kind::TransformError(TOO_LARGE_NUMBER.to_owned()),
                 location: Location::new((1, 31), (1, 54)),
@@ -651,7 +1368,7 @@ mod tests {
 
         // dProd
         assert_eq!(
-            line("check (x: Type) -> (y: Sort 10000000000000000000000) -> x"),
+            line("check (x: Type) -> (y: Sort 10000000000000000000000) -> x", &[]),
             Err(Error {
                 kind: Kind::TransformError(TOO_LARGE_NUMBER.to_owned()),
                 location: Location::new((1, 29), (1, 52)),
@@ -660,7 +1377,7 @@ mod tests {
 
         // Prod
         assert_eq!(
-            line("check Prop -> Sort 10000000000000000000000"),
+            line("check Prop -> Sort 10000000000000000000000", &[]),
             Err(Error {
                 kind: Kind::TransformError(TOO_LARGE_NUMBER.to_owned()),
                 location: Location::new((1, 20), (1, 43)),
@@ -672,7 +1389,7 @@ mod tests {
     fn cannot_transform_sort_expr() {
         // CheckType
         assert_eq!(
-            line("check Sort 10000000000000000000000 : Type"),
+            line("check Sort 10000000000000000000000 : Type", &[]),
             Err(Error {
                 kind: Kind::TransformError(TOO_LARGE_NUMBER.to_owned()),
                 location: Location::new((1, 12), (1, 35)),
@@ -680,7 +1397,7 @@ mod tests {
         );
 
         assert_eq!(
-            line("check Type : Sort 10000000000000000000000"),
+            line("check Type : Sort 10000000000000000000000", &[]),
             Err(Error {
                 kind: Kind::TransformError(TOO_LARGE_NUMBER.to_owned()),
                 location: Location::new((1, 19), (1, 42)),
@@ -689,7 +1406,7 @@ mod tests {
 
         // Define
         assert_eq!(
-            line("def x := Sort 10000000000000000000000"),
+            line("def x := Sort 10000000000000000000000", &[]),
             Err(Error {
                 kind: Kind::TransformError(TOO_LARGE_NUMBER.to_owned()),
                 location: Location::new((1, 15), (1, 38)),
@@ -698,7 +1415,7 @@ mod tests {
 
         // DefineCheckType
         assert_eq!(
-            line("def x : Sort imax 10000000000000000000000 0 := Prop"),
+            line("def x : Sort imax 10000000000000000000000 0 := Prop", &[]),
             Err(Error {
                 kind: Kind::TransformError(TOO_LARGE_NUMBER.to_owned()),
                 location: Location::new((1, 19), (1, 42)),
@@ -706,7 +1423,7 @@ mod tests {
         );
 
         assert_eq!(
-            line("def x : Type := Sort 10000000000000000000000"),
+            line("def x : Type := Sort 10000000000000000000000", &[]),
             Err(Error {
                 kind: Kind::TransformError(TOO_LARGE_NUMBER.to_owned()),
                 location: Location::new((1, 22), (1, 45)),
@@ -715,7 +1432,7 @@ mod tests {
 
         // Declaration
         assert_eq!(
-            line("def x.{u} := Sort 10000000000000000000000"),
+            line("def x.{u} := Sort 10000000000000000000000", &[]),
             Err(Error {
                 kind: Kind::TransformError(TOO_LARGE_NUMBER.to_owned()),
                 location: Location::new((1, 19), (1, 42)),
@@ -724,7 +1441,7 @@ mod tests {
 
         // DeclarationCheckType
         assert_eq!(
-            line("def x.{u} : Sort 10000000000000000000000 := Prop"),
+            line("def x.{u} : Sort 10000000000000000000000 := Prop", &[]),
             Err(Error {
                 kind: Kind::TransformError(TOO_LARGE_NUMBER.to_owned()),
                 location: Location::new((1, 18), (1, 41)),
@@ -732,7 +1449,7 @@ mod tests {
         );
 
         assert_eq!(
-            line("def x.{u} : Type := Sort 10000000000000000000000"),
+            line("def x.{u} : Type := Sort 10000000000000000000000", &[]),
             Err(Error {
                 kind: Kind::TransformError(TOO_LARGE_NUMBER.to_owned()),
                 location: Location::new((1, 26), (1, 49)),
@@ -741,7 +1458,7 @@ mod tests {
 
         // Eval
         assert_eq!(
-            line("eval Prop -> Sort 10000000000000000000000"),
+            line("eval Prop -> Sort 10000000000000000000000", &[]),
             Err(Error {
                 kind: Kind::TransformError(TOO_LARGE_NUMBER.to_owned()),
                 location: Location::new((1, 19), (1, 42)),
@@ -752,7 +1469,7 @@ mod tests {
     #[test]
     fn successful_app() {
         assert_eq!(
-            line("check A B C"),
+            line("check A B C", &[]),
             Ok(GetType(Builder::new(
                 Location::new((1, 7), (1, 12)),
                 App(
@@ -769,7 +1486,7 @@ mod tests {
         );
 
         assert_eq!(
-            line("check (A B) C"),
+            line("check (A B) C", &[]),
             Ok(GetType(Builder::new(
                 Location::new((1, 7), (1, 14)),
                 App(
@@ -786,7 +1503,7 @@ mod tests {
         );
 
         assert_eq!(
-            line("check A (B C)"),
+            line("check A (B C)", &[]),
             Ok(GetType(Builder::new(
                 Location::new((1, 7), (1, 14)),
                 App(
@@ -806,7 +1523,7 @@ mod tests {
     #[test]
     fn successful_prod() {
         assert_eq!(
-            line("check A -> B -> C"),
+            line("check A -> B -> C", &[]),
             Ok(GetType(Builder::new(
                 Location::new((1, 7), (1, 18)),
                 Prod(
@@ -825,7 +1542,7 @@ mod tests {
         );
 
         assert_eq!(
-            line("check A -> (B -> C)"),
+            line("check A -> (B -> C)", &[]),
             Ok(GetType(Builder::new(
                 Location::new((1, 7), (1, 20)),
                 Prod(
@@ -844,7 +1561,7 @@ mod tests {
         );
 
         assert_eq!(
-            line("check (A -> B) -> C"),
+            line("check (A -> B) -> C", &[]),
             Ok(GetType(Builder::new(
                 Location::new((1, 7), (1, 20)),
                 Prod(
@@ -866,7 +1583,7 @@ mod tests {
     #[test]
     fn successful_dprod() {
         assert_eq!(
-            line("check (x: Type) -> (y: Type 1) -> x"),
+            line("check (x: Type) -> (y: Type 1) -> x", &[]),
             Ok(GetType(Builder::new(
                 Location::new((1, 7), (1, 36)),
                 Prod(
@@ -885,7 +1602,7 @@ mod tests {
         );
 
         assert_eq!(
-            line("check (x: Type) -> ((y: Type 1) -> x)"),
+            line("check (x: Type) -> ((y: Type 1) -> x)", &[]),
             Ok(GetType(Builder::new(
                 Location::new((1, 7), (1, 38)),
                 Prod(
@@ -907,7 +1624,7 @@ mod tests {
     #[test]
     fn successful_abs() {
         assert_eq!(
-            line("check fun (w x: Prop) (y z: Prop) => x"),
+            line("check fun (w x: Prop) (y z: Prop) => x", &[]),
             Ok(GetType(Builder::new(
                 Location::new((1, 7), (1, 39)),
                 Abs(
@@ -943,14 +1660,14 @@ mod tests {
     #[test]
     fn failed_dprod() {
         assert_eq!(
-            line("check (x:A)"),
+            line("check (x:A)", &[]),
             Err(Error {
                 kind: Kind::UnexpectedToken(SIMPLE_TERM_ERR.to_owned()),
                 location: Location::new((1, 7), (1, 12)),
             })
         );
         assert_eq!(
-            line("check (x:A) -> (y:B)"),
+            line("check (x:A) -> (y:B)", &[]),
             Err(Error {
                 kind: Kind::UnexpectedToken(SIMPLE_TERM_ERR.to_owned()),
                 location: Location::new((1, 16), (1, 21)),
@@ -961,28 +1678,28 @@ mod tests {
     #[test]
     fn failed_left_arg() {
         assert_eq!(
-            line("def foo (A: Sort 100000000000000000000) := A"),
+            line("def foo (A: Sort 100000000000000000000) := A", &[]),
             Err(Error {
                 kind: Kind::TransformError(TOO_LARGE_NUMBER.to_owned()),
                 location: Location::new((1, 18), (1, 39)),
             })
         );
         assert_eq!(
-            line("def foo.{u} (A: Sort 100000000000000000000) := A"),
+            line("def foo.{u} (A: Sort 100000000000000000000) := A", &[]),
             Err(Error {
                 kind: Kind::TransformError(TOO_LARGE_NUMBER.to_owned()),
                 location: Location::new((1, 22), (1, 43)),
             })
         );
         assert_eq!(
-            line("def foo (A: Sort 100000000000000000000): Prop := A"),
+            line("def foo (A: Sort 100000000000000000000): Prop := A", &[]),
             Err(Error {
                 kind: Kind::TransformError(TOO_LARGE_NUMBER.to_owned()),
                 location: Location::new((1, 18), (1, 39)),
             })
         );
         assert_eq!(
-            line("def foo.{u} (A: Sort 100000000000000000000): Prop := A"),
+            line("def foo.{u} (A: Sort 100000000000000000000): Prop := A", &[]),
             Err(Error {
                 kind: Kind::TransformError(TOO_LARGE_NUMBER.to_owned()),
                 location: Location::new((1, 22), (1, 43)),
@@ -993,7 +1710,7 @@ mod tests {
     #[test]
     fn context_for_abs_args() {
         assert_eq!(
-            line("check fun (x : Prop) (x : x) (x : x) => x"),
+            line("check fun (x : Prop) (x : x) (x : x) => x", &[]),
             Ok(GetType(Builder::new(
                 Location::new((1, 7), (1, 42)),
                 Abs(
@@ -1019,7 +1736,7 @@ mod tests {
         );
 
         assert_eq!(
-            line("check fun (x : Prop) (x x : x) => x"),
+            line("check fun (x : Prop) (x x : x) => x", &[]),
             Ok(GetType(Builder::new(
                 Location::new((1, 7), (1, 36)),
                 Abs(
@@ -1045,7 +1762,7 @@ mod tests {
         );
 
         assert_eq!(
-            line("check fun (x : Prop) (y z : x) => z"),
+            line("check fun (x : Prop) (y z : x) => z", &[]),
             Ok(GetType(Builder::new(
                 Location::new((1, 7), (1, 36)),
                 Abs(
@@ -1074,7 +1791,7 @@ mod tests {
     #[test]
     fn context_for_dprod_args() {
         assert_eq!(
-            line("check (x : Prop) -> (x : x) -> (x : x) -> x"),
+            line("check (x : Prop) -> (x : x) -> (x : x) -> x", &[]),
             Ok(GetType(Builder::new(
                 Location::new((1, 7), (1, 44)),
                 Prod(
@@ -1100,7 +1817,7 @@ mod tests {
         );
 
         assert_eq!(
-            line("check (x : Prop) -> (x x : x) -> x"),
+            line("check (x : Prop) -> (x x : x) -> x", &[]),
             Ok(GetType(Builder::new(
                 Location::new((1, 7), (1, 35)),
                 Prod(
@@ -1126,7 +1843,7 @@ mod tests {
         );
 
         assert_eq!(
-            line("check (x : Prop) -> (y z : x) -> z"),
+            line("check (x : Prop) -> (y z : x) -> z", &[]),
             Ok(GetType(Builder::new(
                 Location::new((1, 7), (1, 35)),
                 Prod(
@@ -1155,7 +1872,7 @@ mod tests {
     #[test]
     fn parenthesis_in_abs() {
         assert_eq!(
-            line("check fun (((w x : Prop))) (y z : Prop) => x"),
+            line("check fun (((w x : Prop))) (y z : Prop) => x", &[]),
             Ok(GetType(Builder::new(
                 Location::new((1, 7), (1, 45)),
                 Abs(
@@ -1191,7 +1908,7 @@ mod tests {
     #[test]
     fn parenthesis_in_prod() {
         assert_eq!(
-            line("check (((Type))) -> (((Type 1 -> Type 2)))"),
+            line("check (((Type))) -> (((Type 1 -> Type 2)))", &[]),
             Ok(GetType(Builder::new(
                 Location::new((1, 7), (1, 43)),
                 Prod(
@@ -1213,7 +1930,7 @@ mod tests {
     #[test]
     fn parenthesis_in_dprod() {
         assert_eq!(
-            line("check (((x:Type))) -> ((((y:Type 1) -> x)))"),
+            line("check (((x:Type))) -> ((((y:Type 1) -> x)))", &[]),
             Ok(GetType(Builder::new(
                 Location::new((1, 7), (1, 44)),
                 Prod(
@@ -1235,7 +1952,7 @@ mod tests {
     #[test]
     fn parenthesis_in_app() {
         assert_eq!(
-            line("check ((((((A))) (((B C))))))"),
+            line("check ((((((A))) (((B C))))))", &[]),
             Ok(GetType(Builder::new(
                 Location::new((1, 10), (1, 27)),
                 App(
@@ -1261,29 +1978,32 @@ mod tests {
             check fun x:Prop => x
         ";
 
+        let mut commands = file(input).unwrap();
+
         // Since the location will differ, we just check that the kind is correct by displaying output
-        assert_eq!(format!("{}", file(input).unwrap()[0]), format!("{}", line("def x := Prop -> Prop").unwrap()));
-        assert_eq!(format!("{}", file(input).unwrap()[1]), format!("{}", line("check fun x:Prop => x").unwrap()));
+        assert_eq!(format!("{}", commands.next(&[]).unwrap().unwrap()), format!("{}", line("def x := Prop -> Prop", &[]).unwrap()));
+        assert_eq!(format!("{}", commands.next(&[]).unwrap().unwrap()), format!("{}", line("check fun x:Prop => x", &[]).unwrap()));
+        assert!(commands.next(&[]).is_none());
     }
 
     #[test]
     fn successful_convert_error() {
         assert_eq!(
-            line("chehk 2x"),
+            line("chehk 2x", &[]),
             Err(Error {
                 kind: Kind::UnexpectedToken(COMMAND_ERR.to_owned()),
                 location: Location::new((1, 1), (1, 6)),
             })
         );
         assert_eq!(
-            line("check 2x"),
+            line("check 2x", &[]),
             Err(Error {
                 kind: Kind::UnexpectedToken(TERM_ERR.to_owned()),
                 location: Location::new((1, 7), (1, 9)),
             })
         );
         assert_eq!(
-            line("check x:"),
+            line("check x:", &[]),
             Err(Error {
                 kind: Kind::UnexpectedToken(TERM_ERR.to_owned()),
                 location: Location::new((1, 9), (1, 10)),
@@ -1298,18 +2018,48 @@ mod tests {
                 "def x : Type := Prop -> Prop
                  // this is a comment
                         check .x"
-            ),
-            Err(Error {
+            )
+            .err(),
+            Some(Error {
                 kind: Kind::UnexpectedToken(TERM_ERR.to_owned()),
                 location: Location::new((3, 31), (3, 33)),
             })
         );
     }
 
+    #[test]
+    fn recovering_parsers() {
+        let mut commands = file_recovering(
+            "def x : Type := Prop -> Prop
+             check .x
+             def y := (Prop
+
+             // this is a comment
+             check x",
+        );
+
+        assert_eq!(
+            commands.next(&[]).unwrap().unwrap().to_string(),
+            line("def x : Type := Prop -> Prop", &[]).unwrap().to_string()
+        );
+        assert_eq!(
+            commands.next(&[]).unwrap(),
+            Err(Error {
+                kind: Kind::UnexpectedToken(TERM_ERR.to_owned()),
+                location: Location::new((2, 20), (2, 22)),
+            })
+        );
+        assert_eq!(commands.next(&[]).unwrap().unwrap_err().location.start, Position::new(6, 14));
+        assert_eq!(commands.next(&[]).unwrap().unwrap().to_string(), line("check x", &[]).unwrap().to_string());
+        assert!(commands.next(&[]).is_none());
+
+        assert!(file_recovering("").next(&[]).is_none());
+    }
+
     #[test]
     fn successful_abs_wildcard() {
         assert_eq!(
-            line("check fun _:Prop => Prop"),
+            line("check fun _:Prop => Prop", &[]),
             Ok(GetType(Builder::new(
                 Location::new((1, 7), (1, 25)),
                 Abs(
@@ -1324,7 +2074,7 @@ mod tests {
     #[test]
     fn successful_prod_wildcard() {
         assert_eq!(
-            line("check (_:Prop) -> Prop"),
+            line("check (_:Prop) -> Prop", &[]),
             Ok(GetType(Builder::new(
                 Location::new((1, 7), (1, 23)),
                 Prod(
@@ -1335,4 +2085,197 @@ mod tests {
             )))
         );
     }
+
+    #[test]
+    fn successful_holes() {
+        assert_eq!(
+            line("check f _ ?goal", &[]),
+            Ok(GetType(Builder::new(
+                Location::new((1, 7), (1, 16)),
+                App(
+                    Box::new(Builder::new(
+                        Location::new((1, 7), (1, 16)),
+                        App(
+                            Box::new(Builder::new(Location::new((1, 7), (1, 8)), Var("f"))),
+                            Box::new(Builder::new(Location::new((1, 9), (1, 10)), Hole(None)))
+                        )
+                    )),
+                    Box::new(Builder::new(Location::new((1, 11), (1, 16)), Hole(Some("goal"))))
+                )
+            )))
+        );
+    }
+
+    #[test]
+    fn successful_tactics() {
+        assert_eq!(
+            line("check by intro x; rewrite <- h; exact x", &[]),
+            Ok(GetType(Builder::new(
+                Location::new((1, 7), (1, 40)),
+                By(vec![
+                    tactic::Builder::new(Location::new((1, 10), (1, 17)), tactic::Payload::Intro(vec!["x"])),
+                    tactic::Builder::new(
+                        Location::new((1, 19), (1, 31)),
+                        tactic::Payload::Rewrite(true, Box::new(Builder::new(Location::new((1, 30), (1, 31)), Var("h"))))
+                    ),
+                    tactic::Builder::new(
+                        Location::new((1, 33), (1, 40)),
+                        tactic::Payload::Exact(Box::new(Builder::new(Location::new((1, 39), (1, 40)), Var("x"))))
+                    ),
+                ])
+            )))
+        );
+    }
+
+    #[test]
+    fn successful_proof() {
+        assert_eq!(
+            theorem("theorem foo : Prop -> Prop := by", &[]),
+            Ok((
+                (Location::new((1, 9), (1, 12)), "foo"),
+                Builder::new(
+                    Location::new((1, 15), (1, 28)),
+                    Prod(
+                        "_",
+                        Box::new(Builder::new(Location::new((1, 15), (1, 19)), Prop)),
+                        Box::new(Builder::new(Location::new((1, 23), (1, 27)), Prop))
+                    )
+                )
+            ))
+        );
+
+        assert_eq!(
+            tactic("apply f", &[]),
+            Ok(tactic::Builder::new(
+                Location::new((1, 1), (1, 8)),
+                tactic::Payload::Apply(Box::new(Builder::new(Location::new((1, 7), (1, 8)), Var("f"))))
+            ))
+        );
+    }
+
+    #[test]
+    fn successful_notation_declaration() {
+        assert_eq!(
+            line("infix \"=\" 50 := Eq _", &[]),
+            Ok(Command::Notation {
+                fixity: Fixity::Infix,
+                symbol: "=",
+                precedence: 50,
+                name: (Location::new((1, 17), (1, 19)), "Eq"),
+                hidden: 1,
+            })
+        );
+
+        assert_eq!(line("prefix \"~\" 40 := Not", &[]).unwrap().to_string(), "prefix \"~\" 40 := Not");
+    }
+
+    #[test]
+    fn successful_namespaces() {
+        assert_eq!(line("namespace Foo", &[]), Ok(Command::Namespace((Location::new((1, 11), (1, 14)), "Foo"))));
+        assert_eq!(line("end Foo.Bar", &[]), Ok(Command::End(Location::new((1, 1), (1, 12)), Some("Foo.Bar"))));
+        assert_eq!(
+            line("open Foo Bar", &[]),
+            Ok(Command::Open(vec![(Location::new((1, 6), (1, 9)), "Foo"), (Location::new((1, 10), (1, 13)), "Bar")]))
+        );
+
+        assert_eq!(line("check Foo.x", &[]).unwrap().to_string(), "check Foo.x");
+        assert!(line("open", &[]).is_err());
+        assert!(line("def end := Prop", &[]).is_err());
+    }
+
+    #[test]
+    fn successful_sections() {
+        assert_eq!(line("section", &[]), Ok(Command::Section(None)));
+        assert_eq!(line("section Foo", &[]), Ok(Command::Section(Some("Foo"))));
+        assert_eq!(line("end", &[]), Ok(Command::End(Location::new((1, 1), (1, 4)), None)));
+
+        assert_eq!(
+            line("variable (A B : Prop) {x : A}", &[]),
+            Ok(Command::Variable(vec![
+                ((Location::new((1, 11), (1, 12)), "A"), Builder::new(Location::new((1, 17), (1, 21)), Prop), false),
+                ((Location::new((1, 13), (1, 14)), "B"), Builder::new(Location::new((1, 17), (1, 21)), Prop), false),
+                ((Location::new((1, 24), (1, 25)), "x"), Builder::new(Location::new((1, 28), (1, 29)), Var("A")), true),
+            ]))
+        );
+
+        assert_eq!(line("variable (A : Prop) {x : A}", &[]).unwrap().to_string(), "variable (A: Prop) {x: A}");
+        assert!(line("variable", &[]).is_err());
+        assert!(line("variable A : Prop", &[]).is_err());
+    }
+
+    #[test]
+    fn successful_notations() {
+        use kernel::memory::arena::use_arena_with_axioms;
+        use kernel::memory::term::builder::prop;
+
+        use_arena_with_axioms(|arena| {
+            let prop = arena.build(prop()).unwrap();
+            arena.bind("And", prop);
+            arena.bind("Or", prop);
+            arena.bind("Not", prop);
+
+            arena.add_notation("=", Fixity::Infix, 50, "Eq", 1);
+            arena.add_notation("/\\", Fixity::InfixRight, 35, "And", 0);
+            arena.add_notation("\\/", Fixity::InfixRight, 30, "Or", 0);
+            arena.add_notation("~", Fixity::Prefix, 40, "Not", 0);
+
+            let notations = arena.notations();
+
+            assert_eq!(
+                line("check a = b", notations),
+                Ok(GetType(Builder::new(
+                    Location::new((1, 7), (1, 12)),
+                    App(
+                        Box::new(Builder::new(
+                            Location::new((1, 7), (1, 8)),
+                            App(
+                                Box::new(Builder::new(
+                                    Location::new((1, 9), (1, 10)),
+                                    App(
+                                        Box::new(Builder::new(Location::new((1, 9), (1, 10)), Var("Eq"))),
+                                        Box::new(Builder::new(Location::new((1, 9), (1, 10)), Hole(None)))
+                                    )
+                                )),
+                                Box::new(Builder::new(Location::new((1, 7), (1, 8)), Var("a")))
+                            )
+                        )),
+                        Box::new(Builder::new(Location::new((1, 11), (1, 12)), Var("b")))
+                    )
+                )))
+            );
+
+            let same = |input, expected| {
+                assert_eq!(line(input, notations).unwrap().to_string(), line(expected, &[]).unwrap().to_string());
+            };
+
+            same("check A /\\ B \\/ ~A", "check Or (And A B) (Not A)");
+            same("check A /\\ B /\\ C", "check And A (And B C)");
+            same("check (A /\\ B) /\\ C", "check And (And A B) C");
+            same("check ~ ~A", "check Not (Not A)");
+            same("check f x = g (y = z) -> A", "check Eq _ (f x) (g (Eq _ y z)) -> A");
+
+            assert_eq!(
+                line("check a = b = c", notations),
+                Err(Error {
+                    kind: Kind::NonAssociativeOperator("=".to_owned()),
+                    location: Location::new((1, 13), (1, 14)),
+                })
+            );
+
+            assert_eq!(
+                line("check a <> b", notations),
+                Err(Error {
+                    kind: Kind::UnknownOperator("<>".to_owned()),
+                    location: Location::new((1, 9), (1, 11)),
+                })
+            );
+        });
+    }
+
+    #[test]
+    fn failure_proof() {
+        assert!(theorem("theorem foo : Prop := Prop", &[]).is_err());
+        assert!(theorem("theorem foo : Prop := by exact Prop", &[]).is_err());
+        assert!(tactic("intro x; exact x", &[]).is_err());
+    }
 }
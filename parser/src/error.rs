@@ -26,6 +26,14 @@ pub enum Kind {
 
     /// Token cannot be transformed
     TransformError(String),
+
+    /// No notation has been declared with this symbol, with the expected number of operands.
+    #[display(fmt = "unknown operator {_0}")]
+    UnknownOperator(String),
+
+    /// This operator is not associative, and cannot be chained without parentheses.
+    #[display(fmt = "operator {_0} is not associative, parentheses are needed")]
+    NonAssociativeOperator(String),
 }
 
 impl From<pest::error::Error<Rule>> for Error {
@@ -35,13 +43,17 @@ impl From<pest::error::Error<Rule>> for Error {
         let err = err.renamed_rules(|rule| match *rule {
             Rule::string | Rule::Var => "variable".to_owned(),
             Rule::number => "number".to_owned(),
+            Rule::NatLit => "natural number".to_owned(),
             Rule::Define => "def var := term".to_owned(),
             Rule::Declaration => "def decl.{ vars, ... } := term".to_owned(),
             Rule::DeclarationCheckType => "def decl.{ vars, ... } : term := term".to_owned(),
             Rule::CheckType => "check term : term".to_owned(),
             Rule::GetType => "check term".to_owned(),
             Rule::DefineCheckType => "def var : term := term".to_owned(),
+            Rule::Axiom => "axiom var : term".to_owned(),
+            Rule::TheoremDecl => "theorem var : term := term".to_owned(),
             Rule::Abs => "abstraction".to_owned(),
+            Rule::Let => "local definition".to_owned(),
             Rule::dProd => "dependent product".to_owned(),
             Rule::Prod => "product".to_owned(),
             Rule::App => "application".to_owned(),
@@ -51,7 +63,24 @@ impl From<pest::error::Error<Rule>> for Error {
             Rule::Eval => "eval term".to_owned(),
             Rule::filename => "path_to_file".to_owned(),
             Rule::ImportFile => "import path_to_file".to_owned(),
-            Rule::Search => "search var".to_owned(),
+            Rule::SearchType => "search : term".to_owned(),
+            Rule::Search => "search pattern".to_owned(),
+            Rule::name_pattern => "pattern".to_owned(),
+            Rule::PrintAxioms => "print axioms var".to_owned(),
+            Rule::PrintDeps => "print deps var".to_owned(),
+            Rule::Inductive => "inductive var : term := | constructors".to_owned(),
+            Rule::Constructor => "| var : term".to_owned(),
+            Rule::Structure => "structure var where fields".to_owned(),
+            Rule::Field => "var : term".to_owned(),
+            Rule::Theorem => "theorem var : term := by".to_owned(),
+            Rule::NotationDecl | Rule::Fixity => "infix \"symbol\" precedence := var".to_owned(),
+            Rule::Notation => "notation".to_owned(),
+            Rule::Namespace => "namespace var".to_owned(),
+            Rule::Section => "section var".to_owned(),
+            Rule::Variable => "variable (var : term)".to_owned(),
+            Rule::End => "end var".to_owned(),
+            Rule::Open => "open var".to_owned(),
+            Rule::operator => "operator".to_owned(),
             Rule::Max => "max".to_owned(),
             Rule::Plus => "plus".to_owned(),
             Rule::IMax => "imax".to_owned(),
@@ -59,6 +88,20 @@ impl From<pest::error::Error<Rule>> for Error {
             Rule::univ_decl => "universe declaration".to_owned(),
             Rule::Args => "left-arguments".to_owned(),
             Rule::arg => "left-argument".to_owned(),
+            Rule::Binders => "left-arguments".to_owned(),
+            Rule::implicit_arg => "implicit left-argument".to_owned(),
+            Rule::Explicit => "explicit variable".to_owned(),
+            Rule::Hole => "hole".to_owned(),
+            Rule::wildcard => "wildcard".to_owned(),
+            Rule::By => "tactic block".to_owned(),
+            Rule::Intro => "intro vars".to_owned(),
+            Rule::Exact => "exact term".to_owned(),
+            Rule::Apply => "apply term".to_owned(),
+            Rule::Refine => "refine term".to_owned(),
+            Rule::RewriteRev | Rule::Rewrite => "rewrite term".to_owned(),
+            Rule::Induction => "induction var".to_owned(),
+            Rule::Exfalso => "exfalso".to_owned(),
+            Rule::Assumption => "assumption".to_owned(),
             _ => {
                 unreachable!("low level rules cannot appear in error messages")
             },
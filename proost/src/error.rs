@@ -1,28 +1,14 @@
 //! Error management
 
 use derive_more::{Display, From};
-use elaboration::location::Location;
-use kernel::memory::term::Term;
-use kernel::trace::Traceable;
 
-use crate::evaluator;
-
-/// The type of errors encountered by Proost during an interactive session.
+/// The type of errors which end an interactive session.
 ///
-/// Please note that some traits like `Clone` or `PartialEq` cannot be implemented here because
-/// [`std::io::Error`] does not implement them.
+/// Errors raised by the commands themselves are displayed by the toplevel, and do not end the
+/// session. Please note that some traits like `Clone` or `PartialEq` cannot be implemented here
+/// because [`std::io::Error`] does not implement them.
 #[derive(Display, From)]
-pub enum Error<'arena, 'build> {
-    /// An error raised by the [`kernel`].
-    #[display(fmt = "{_1}")]
-    Kernel(&'build dyn Traceable<Location>, kernel::error::Error<'arena>),
-
-    /// An error raised by the [`parser`].
-    Parser(parser::error::Error),
-
-    /// An error raised by the [evaluator](crate::evaluator).
-    TopLevel(evaluator::Error),
-
+pub enum Error {
     /// An input/output error (see [`std::io::Error`]).
     Io(std::io::Error),
 
@@ -30,16 +16,13 @@ pub enum Error<'arena, 'build> {
     RustyLine(rustyline::error::ReadlineError),
 }
 
-impl core::fmt::Debug for Error<'_, '_> {
+impl core::fmt::Debug for Error {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         core::fmt::Display::fmt(self, f)
     }
 }
 
-impl std::error::Error for Error<'_, '_> {}
+impl std::error::Error for Error {}
 
 /// The type of results yielded by the toplevel.
-pub type Result<'arena, 'build, T> = core::result::Result<T, Error<'arena, 'build>>;
-
-/// The type of objects which typically results from the processing of a command.
-pub type ResultProcess<'arena, 'build> = Result<'arena, 'build, Option<Term<'arena>>>;
+pub type Result<T> = core::result::Result<T, Error>;
@@ -1,25 +1,90 @@
-// mod error;
-// mod evaluator;
-// mod rustyline_helper;
+#![doc(html_logo_url = "https://gitlab.crans.org/loutr/proost/-/raw/main/docs/media/logo.png")]
+#![feature(let_chains)]
+#![deny(
+    clippy::complexity,
+    clippy::correctness,
+    clippy::nursery,
+    clippy::pedantic,
+    clippy::perf,
+    clippy::restriction,
+    clippy::style,
+    clippy::suspicious
+)]
+#![allow(
+    clippy::absolute_paths,
+    clippy::arithmetic_side_effects,
+    clippy::blanket_clippy_restriction_lints,
+    clippy::else_if_without_else,
+    clippy::error_impl_error,
+    clippy::exhaustive_enums,
+    clippy::exhaustive_structs,
+    clippy::implicit_return,
+    clippy::indexing_slicing,
+    clippy::let_underscore_must_use,
+    clippy::let_underscore_untyped,
+    clippy::match_same_arms,
+    clippy::match_wildcard_for_single_variants,
+    clippy::min_ident_chars,
+    clippy::missing_trait_methods,
+    clippy::mod_module_files,
+    clippy::panic_in_result_fn,
+    clippy::pattern_type_mismatch,
+    clippy::print_stdout,
+    clippy::question_mark_used,
+    clippy::ref_patterns,
+    clippy::separated_literal_suffix,
+    clippy::shadow_reuse,
+    clippy::shadow_unrelated,
+    clippy::single_call_fn,
+    clippy::std_instead_of_core,
+    clippy::string_slice,
+    clippy::unreachable,
+    clippy::wildcard_enum_match_arm
+)]
+#![cfg_attr(
+    test,
+    allow(
+        clippy::assertions_on_result_states,
+        clippy::enum_glob_use,
+        clippy::indexing_slicing,
+        clippy::non_ascii_literal,
+        clippy::too_many_lines,
+        clippy::unwrap_used,
+        clippy::wildcard_imports,
+    )
+)]
+
+//! Proost, a small proof assistant written in Rust.
+//!
+//! `proost` denotes the toplevel executable. Please refer to the manual for detailed usage
+//! instructions.
+
+extern crate alloc;
+
+mod error;
+mod rustyline_helper;
 
-use std::cmp::max;
 use std::env::current_dir;
 use std::io::IsTerminal;
 
 use clap::Parser;
-use colored::Colorize;
 use elaboration::location::Location;
-use proost::error::{Error, Result, ResultProcess};
-use evaluator::Evaluator;
-use kernel::memory::term::pretty;
+use error::Result;
+use kernel::memory::arena::Arena;
+use mini_proost::display;
+use mini_proost::error::Error;
+use mini_proost::evaluator::Evaluator;
+use mini_proost::proof::Proof;
 use parser::command::{self, Command};
-use proost::{evaluator, rustyline_helper};
 use rustyline::error::ReadlineError;
 use rustyline::{Cmd, Config, Editor, EventHandler, KeyCode, KeyEvent, Modifiers};
 use rustyline_helper::{RustyLineHelper, TabEventHandler};
-use proost::display;
 
 /// Command line arguments, interpreted with `clap`.
+///
+/// Each boolean is an independent command-line flag, which is why the struct has more of them
+/// than `clippy::struct_excessive_bools` allows.
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 struct Args {
@@ -31,6 +96,12 @@ struct Args {
     /// print the content of imported files
     #[arg(short, long)]
     verbose: bool,
+    /// report every error of the files, instead of stopping at the first one
+    #[arg(long)]
+    check: bool,
+    /// forbid assuming axioms
+    #[arg(long)]
+    no_axioms: bool,
 }
 
 /// The version of the program
@@ -39,18 +110,37 @@ const VERSION: &str = env!("CARGO_PKG_VERSION");
 /// The name of the program
 const NAME: &str = env!("CARGO_PKG_NAME");
 
-fn main() -> Result<'static, 'static, ()> {
+fn main() -> Result<()> {
     let args = Args::parse();
 
     let current_path = current_dir()?;
     let mut evaluator = Evaluator::new(current_path, args.verbose);
 
+    if args.no_axioms {
+        evaluator.forbid_axioms();
+    }
+
     // check if files are provided as command-line arguments
     if !args.files.is_empty() {
         return kernel::memory::arena::use_arena_with_axioms(|arena| {
+            if args.check {
+                let files = args.files.iter().map(String::as_str).collect::<Vec<_>>();
+                let diagnostics = evaluator.check_files(arena, &files);
+
+                if diagnostics.is_empty() {
+                    println!("\u{2713}");
+                }
+
+                for err in diagnostics {
+                    println!("\u{2717} {err}");
+                }
+
+                return Ok(());
+            }
+
             let command = Command::Import(args.files.iter().map(|file| (Location::default(), file.as_str())).collect());
 
-            display(evaluator.process_line(arena, &command), false);
+            display(evaluator.process_line(arena, &command), false, arena.notations());
             Ok(())
         });
     }
@@ -70,19 +160,23 @@ fn main() -> Result<'static, 'static, ()> {
     kernel::memory::arena::use_arena_with_axioms(|arena| {
         println!("Welcome to {NAME} {VERSION}");
 
+        // the proof in progress, if any, whose lines are tactics
+        let mut proof: Option<Proof> = None;
+
         loop {
-            let readline = rl.readline("\u{00BB} ");
+            let readline = rl.readline(if proof.is_some() { "\u{22A2} " } else { "\u{00BB} " });
             match readline {
                 Ok(line) if is_command(&line) => {
                     let _ = rl.add_history_entry(line.as_str());
 
-                    match command::parse::line(line.as_str()) {
-                        Ok(command) => display(evaluator.process_line(arena, &command), true),
-                        Err(err) => display(Err(Error::Parser(err)), true),
-                    }
+                    process_line(line, &mut proof, &mut evaluator, arena);
                 },
                 Ok(_) => (),
-                Err(ReadlineError::Interrupted) => {},
+                Err(ReadlineError::Interrupted) => {
+                    if proof.take().is_some() {
+                        println!("proof aborted");
+                    }
+                },
                 Err(ReadlineError::Eof) => break,
                 Err(err) => return Err(err.into()),
             }
@@ -92,6 +186,30 @@ fn main() -> Result<'static, 'static, ()> {
     })
 }
 
+/// Processes a line of the interactive session: a tactic, `undo` or `qed` if a proof is in
+/// progress, and a command otherwise. A line of the form `theorem name : statement := by` starts
+/// a new proof.
+fn process_line(line: String, proof: &mut Option<Proof>, evaluator: &mut Evaluator, arena: &mut Arena<'_>) {
+    match *proof {
+        Some(ref mut current) => match line.trim() {
+            "qed" => {
+                if current.qed(evaluator, arena) {
+                    *proof = None;
+                }
+            },
+            "undo" => current.undo(evaluator, arena),
+            _ => current.step(line, evaluator, arena),
+        },
+
+        None if is_theorem(&line) => *proof = Proof::start(line, evaluator, arena),
+
+        None => match command::parse::line(line.as_str(), arena.notations()) {
+            Ok(command) => display(evaluator.process_line(arena, &command), true, arena.notations()),
+            Err(err) => display(Err(Error::Parser(err)), true, arena.notations()),
+        },
+    }
+}
+
 /// Tests whether the string corresponds to a command (here, not a comment)
 fn is_command(input: &str) -> bool {
     input
@@ -100,27 +218,52 @@ fn is_command(input: &str) -> bool {
         .map_or(false, |pos| input.len() < 2 || input[pos..pos + 2] != *"//")
 }
 
+/// Tests whether the string starts an interactive proof, as in `theorem foo : T := by`
+fn is_theorem(input: &str) -> bool {
+    input.trim_start().strip_prefix("theorem").is_some_and(|rest| rest.starts_with(char::is_whitespace))
+        && input.trim_end().strip_suffix("by").is_some_and(|rest| rest.trim_end().ends_with(":="))
+}
+
 #[cfg(test)]
 mod tests {
-    use elaboration::location::Location;
+    use kernel::memory::arena::use_arena_with_axioms;
 
     use super::*;
 
     #[test]
-    fn correct_pretty_print_loc() {
-        assert_eq!(pretty_print_loc(Location::new((1, 3), (1, 3))), "  ^".to_owned());
-        assert_eq!(pretty_print_loc(Location::new((1, 3), (1, 4))), "  ^".to_owned());
-        assert_eq!(pretty_print_loc(Location::new((1, 3), (1, 5))), "  ^^".to_owned());
-        assert_eq!(pretty_print_loc(Location::new((1, 3), (1, 6))), "  ^-^".to_owned());
-        assert_eq!(pretty_print_loc(Location::new((1, 3), (1, 7))), "  ^--^".to_owned());
-    }
+    fn proof_mode() {
+        let mut evaluator = Evaluator::new(current_dir().unwrap(), false);
+        let mut proof = None;
 
-    /// Robustness against multilines
-    #[test]
-    fn robust_pretty_print_loc() {
-        pretty_print_loc(Location::new((2, 3), (2, 3)));
-        pretty_print_loc(Location::new((1, 3), (2, 3)));
-        pretty_print_loc(Location::new((1, 3), (2, 1)));
+        use_arena_with_axioms(|arena| {
+            process_line("theorem imp : (P : Prop) -> P -> P := by".to_owned(), &mut proof, &mut evaluator, arena);
+            assert_eq!(proof.as_ref().map(Proof::tactics), Some([].as_slice()));
+
+            process_line("intro P".to_owned(), &mut proof, &mut evaluator, arena);
+            process_line("intro h".to_owned(), &mut proof, &mut evaluator, arena);
+            assert_eq!(proof.as_ref().unwrap().tactics(), ["intro P", "intro h"]);
+
+            // a failing tactic is discarded
+            process_line("exact P".to_owned(), &mut proof, &mut evaluator, arena);
+            assert_eq!(proof.as_ref().unwrap().tactics(), ["intro P", "intro h"]);
+
+            process_line("undo".to_owned(), &mut proof, &mut evaluator, arena);
+            assert_eq!(proof.as_ref().unwrap().tactics(), ["intro P"]);
+
+            // the proof cannot be ended while goals are left
+            process_line("qed".to_owned(), &mut proof, &mut evaluator, arena);
+            assert!(proof.is_some());
+            assert!(!arena.is_bound("imp"));
+
+            process_line("exact fun h : P => h".to_owned(), &mut proof, &mut evaluator, arena);
+            process_line("qed".to_owned(), &mut proof, &mut evaluator, arena);
+            assert!(proof.is_none());
+            assert!(arena.is_bound("imp"));
+
+            // the name of a theorem cannot be reused
+            process_line("theorem imp : (P : Prop) -> P -> P := by".to_owned(), &mut proof, &mut evaluator, arena);
+            assert!(proof.is_none());
+        });
     }
 
     #[test]
@@ -139,6 +282,16 @@ mod tests {
         assert!(!super::is_command("// comment"));
     }
 
+    #[test]
+    fn is_theorem() {
+        assert!(super::is_theorem("theorem foo : Prop := by"));
+        assert!(super::is_theorem("  theorem foo : Prop := by"));
+        assert!(!super::is_theorem("theorems"));
+        assert!(!super::is_theorem("def theorem := Prop"));
+        assert!(!super::is_theorem("theorem foo : Prop := by exact Prop"));
+        assert!(!super::is_theorem("theorem foo : Nat := Zero"));
+    }
+
     #[test]
     fn is_command_true() {
         assert!(super::is_command("     check x"));
@@ -11,7 +11,27 @@ use rustyline::{Cmd, ConditionalEventHandler, Context, Event, EventContext, Repe
 use rustyline_derive::{Helper, Hinter};
 
 /// Language keywords that should be highlighted
-const KEYWORDS: [&str; 5] = ["check", "def", "eval", "import", "search"];
+const KEYWORDS: [&str; 19] = [
+    "axiom",
+    "check",
+    "def",
+    "end",
+    "eval",
+    "import",
+    "inductive",
+    "infix",
+    "infixl",
+    "infixr",
+    "namespace",
+    "open",
+    "prefix",
+    "print",
+    "search",
+    "section",
+    "structure",
+    "theorem",
+    "variable",
+];
 
 /// An Helper for a `RustyLine` Editor that implements:
 /// - a standard hinter;